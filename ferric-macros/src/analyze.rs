@@ -1,18 +1,31 @@
 // Copyright 2022 The Ferric AI Project Developers
-use std::collections::HashMap;
+use proc_macro2::{TokenStream, TokenTree};
+use quote::quote;
+use std::collections::{HashMap, HashSet};
 use syn::{Error, Expr, Ident, Type};
 
-use crate::parse::ModelAst;
+use crate::parse::{ModelAst, ParamAst, PlateAst};
 
 /// VariableIR is the Intermediate Representation of a random variable.
 pub struct VariableIR {
     pub var_ident: Ident,
+    /// `Some` for an indexed family of variables, whose value is a `Vec` of `type_ident`.
+    pub plate: Option<PlateAst>,
     pub type_ident: Type,
     pub dependency: Expr,
     /// `true` if defined with `~` (distribution), `false` if defined with `=` (deterministic expr).
     pub is_stochastic: bool,
     pub is_queried: bool,
     pub is_observed: bool,
+    /// `true` if observed with `observe var_name?;`: missing observed values are imputed
+    /// like latent variables instead of conditioned on.
+    pub is_optional: bool,
+    /// The variables referenced by the dependency expression or the plate range, in order
+    /// of their first reference and spanned at that reference.
+    pub parents: Vec<Ident>,
+    /// The variables whose dependency expression or plate range references this one,
+    /// sorted by name.
+    pub children: Vec<Ident>,
 }
 
 /// ModelIR is the Intermediate Representation of the model before code generation.
@@ -21,9 +34,30 @@ pub struct ModelIR {
     pub model_ident: Ident,
     pub use_exprs: Vec<Expr>,
     pub variables: HashMap<String, VariableIR>,
+    /// Parameters and data inputs in the order of their declarations.
+    pub params: Vec<ParamAst>,
+    /// Names of the observed variables in the order of their `observe` statements.
+    pub observe_order: Vec<String>,
+    /// Names of all the variables ordered so that every variable follows its parents.
+    pub topological_order: Vec<String>,
+    /// The log-weight expressions of the `factor` statements, in order.
+    pub factors: Vec<Expr>,
+    /// The boolean expressions of the `condition` statements, in order.
+    pub conditions: Vec<Expr>,
 }
 
 pub fn analyze(ast: ModelAst) -> Result<ModelIR, Error> {
+    // analyze the parameter declarations
+    let mut param_names = HashSet::<String>::new();
+    for param in ast.params.iter() {
+        let param_name = param.param_ident.to_string();
+        if !param_names.insert(param_name.clone()) {
+            return Err(Error::new(
+                param.param_ident.span(),
+                format!("duplicate declaration of parameter `{}`", param_name),
+            ));
+        }
+    }
     let mut variables = HashMap::<String, VariableIR>::new();
     // analyze all the statements
     for stmt in ast.stmts.into_iter() {
@@ -35,20 +69,82 @@ pub fn analyze(ast: ModelAst) -> Result<ModelIR, Error> {
                 format!("duplicate declaration of variable `{}`", var_name),
             ));
         }
+        // nor declared as a parameter
+        if param_names.contains(&var_name) {
+            return Err(Error::new(
+                stmt.var_ident.span(),
+                format!("variable `{}` is already declared as a parameter", var_name),
+            ));
+        }
         let variable = VariableIR {
             var_ident: stmt.var_ident,
+            plate: stmt.plate,
             dependency: stmt.dependency,
             type_ident: stmt.type_ident,
             is_stochastic: stmt.is_stochastic,
             is_queried: false,
             is_observed: false,
+            is_optional: false,
+            parents: Vec::new(),
+            children: Vec::new(),
         };
         variables.insert(var_name, variable);
     }
+    // a plate index must not shadow a variable or parameter of the model
+    for variable in variables.values() {
+        if let Some(plate) = &variable.plate {
+            let index_name = plate.index_ident.to_string();
+            if variables.contains_key(&index_name) || param_names.contains(&index_name) {
+                return Err(Error::new(
+                    plate.index_ident.span(),
+                    format!(
+                        "plate index `{}` shadows a variable of the model",
+                        index_name
+                    ),
+                ));
+            }
+        }
+    }
+    // build the dependency graph, which must be acyclic
+    let mut parents = HashMap::<String, Vec<Ident>>::new();
+    for (var_name, variable) in variables.iter() {
+        let mut var_parents = Vec::new();
+        let dep = &variable.dependency;
+        collect_parents(quote! {#dep}, &variables, &mut var_parents);
+        if let Some(plate) = &variable.plate {
+            let range = &plate.range;
+            collect_parents(quote! {#range}, &variables, &mut var_parents);
+        }
+        parents.insert(var_name.clone(), var_parents);
+    }
+    let mut var_names: Vec<String> = variables.keys().cloned().collect();
+    var_names.sort();
+    for var_name in var_names.iter() {
+        for parent in parents[var_name].iter() {
+            let child = variables[var_name].var_ident.clone();
+            let parent_var = variables
+                .get_mut(&parent.to_string())
+                .expect("parent exists");
+            parent_var.children.push(child);
+        }
+    }
+    for (var_name, var_parents) in parents.into_iter() {
+        variables
+            .get_mut(&var_name)
+            .expect("variable exists")
+            .parents = var_parents;
+    }
+    let topological_order = topological_order(&var_names, &variables)?;
     // analyze the query statements
     for query in ast.queries.into_iter() {
         let var_name = query.to_string();
         match variables.get_mut(&var_name) {
+            None if param_names.contains(&var_name) => {
+                return Err(Error::new(
+                    query.span(),
+                    format!("cannot query parameter `{}`", var_name),
+                ));
+            }
             None => {
                 return Err(Error::new(
                     query.span(),
@@ -68,9 +164,16 @@ pub fn analyze(ast: ModelAst) -> Result<ModelIR, Error> {
         }
     }
     // analyze the observe statements
+    let mut observe_order = Vec::<String>::new();
     for obs in ast.observes.into_iter() {
         let var_name = obs.to_string();
         match variables.get_mut(&var_name) {
+            None if param_names.contains(&var_name) => {
+                return Err(Error::new(
+                    obs.span(),
+                    format!("cannot observe parameter `{}`", var_name),
+                ));
+            }
             None => {
                 return Err(Error::new(
                     obs.span(),
@@ -85,17 +188,106 @@ pub fn analyze(ast: ModelAst) -> Result<ModelIR, Error> {
                     ));
                 } else {
                     variable.is_observed = true;
+                    observe_order.push(var_name);
                 }
             }
         }
     }
+    // only stochastic variables have a prior to impute missing observations from
+    for obs in ast.optional_observes.into_iter() {
+        let variable = variables
+            .get_mut(&obs.to_string())
+            .expect("optional observes are observed");
+        if !variable.is_stochastic {
+            return Err(Error::new(
+                obs.span(),
+                format!(
+                    "cannot observe deterministic variable `{}` with missing values",
+                    obs
+                ),
+            ));
+        }
+        variable.is_optional = true;
+    }
     Ok(ModelIR {
         model_ident: ast.model_ident,
         use_exprs: ast.use_exprs,
         variables,
+        params: ast.params,
+        observe_order,
+        topological_order,
+        factors: ast.factors,
+        conditions: ast.conditions,
     })
 }
 
+// collect the model variables referenced in a dependency expression, skipping those
+// already in `parents`
+fn collect_parents(
+    dep_tokens: TokenStream,
+    variables: &HashMap<String, VariableIR>,
+    parents: &mut Vec<Ident>,
+) {
+    for tt in dep_tokens {
+        match tt {
+            TokenTree::Ident(ref i)
+                if variables.contains_key(&i.to_string()) && !parents.contains(i) =>
+            {
+                parents.push(i.clone());
+            }
+            TokenTree::Group(ref g) => collect_parents(g.stream(), variables, parents),
+            _ => {}
+        }
+    }
+}
+
+// order the variables so that every variable follows its parents, visiting them in the
+// order of `var_names`; a cycle is reported at the reference that closes it
+fn topological_order(
+    var_names: &[String],
+    variables: &HashMap<String, VariableIR>,
+) -> Result<Vec<String>, Error> {
+    fn visit(
+        name: &String,
+        variables: &HashMap<String, VariableIR>,
+        path: &mut Vec<String>,
+        visited: &mut HashSet<String>,
+        order: &mut Vec<String>,
+    ) -> Result<(), Error> {
+        if visited.contains(name) {
+            return Ok(());
+        }
+        path.push(name.clone());
+        for parent in variables[name].parents.iter() {
+            let parent_name = parent.to_string();
+            if let Some(start) = path.iter().position(|n| *n == parent_name) {
+                let cycle: Vec<String> = path[start..]
+                    .iter()
+                    .chain(std::iter::once(&parent_name))
+                    .map(|n| format!("`{}`", n))
+                    .collect();
+                return Err(Error::new(
+                    parent.span(),
+                    format!("cyclic dependency {}", cycle.join(" -> ")),
+                ));
+            }
+            visit(&parent_name, variables, path, visited, order)?;
+        }
+        path.pop();
+        visited.insert(name.clone());
+        order.push(name.clone());
+        Ok(())
+    }
+
+    let mut path = Vec::new();
+    let mut visited = HashSet::new();
+    let mut order = Vec::new();
+    for name in var_names {
+        visit(name, variables, &mut path, &mut visited, &mut order)?;
+    }
+    Ok(order)
+}
+
 #[test]
 fn test_analyze_errors() {
     use quote::quote;
@@ -172,6 +364,126 @@ fn test_analyze_errors() {
         )
         .is_err()
     );
+
+    // missing values of a deterministic observed variable
+    assert!(
+        analyze(
+            parse2::<ModelAst>(quote!(
+                mod grass;
+                use ferric::distributions::Bernoulli;
+                let rain : bool ~ Bernoulli::new( 0.2 );
+                let dry : bool = !rain;
+                observe dry?;
+            ))
+            .unwrap()
+        )
+        .is_err()
+    );
+
+    // duplicate parameter
+    assert!(
+        analyze(
+            parse2::<ModelAst>(quote!(
+                mod m;
+                param n : usize;
+                data n : usize;
+            ))
+            .unwrap()
+        )
+        .is_err()
+    );
+
+    // variable declared with the name of a parameter
+    assert!(
+        analyze(
+            parse2::<ModelAst>(quote!(
+                mod grass;
+                use ferric::distributions::Bernoulli;
+                param rain : bool;
+                let rain : bool ~ Bernoulli::new( 0.2 );
+            ))
+            .unwrap()
+        )
+        .is_err()
+    );
+
+    // query and observe of a parameter
+    assert!(
+        analyze(
+            parse2::<ModelAst>(quote!(
+                mod m;
+                param n : usize;
+                query n;
+            ))
+            .unwrap()
+        )
+        .is_err()
+    );
+    assert!(
+        analyze(
+            parse2::<ModelAst>(quote!(
+                mod m;
+                param n : usize;
+                observe n;
+            ))
+            .unwrap()
+        )
+        .is_err()
+    );
+
+    // plate index shadows a parameter
+    assert!(
+        analyze(
+            parse2::<ModelAst>(quote!(
+                mod m;
+                use ferric::distributions::Bernoulli;
+                param i : usize;
+                let wet[i in 0..3] : bool ~ Bernoulli::new( 0.2 );
+            ))
+            .unwrap()
+        )
+        .is_err()
+    );
+
+    // cyclic dependencies, including a variable that depends on itself
+    assert!(
+        analyze(
+            parse2::<ModelAst>(quote!(
+                mod cycle;
+                use ferric::distributions::Normal;
+                let a : f64 ~ Normal::new( b, 1.0 );
+                let b : f64 ~ Normal::new( a, 1.0 );
+            ))
+            .unwrap()
+        )
+        .is_err()
+    );
+    assert!(
+        analyze(
+            parse2::<ModelAst>(quote!(
+                mod cycle;
+                use ferric::distributions::Normal;
+                let a : f64 ~ Normal::new( 0.0, 1.0 );
+                let x[i in 0..3] : f64 ~ Normal::new( a + x[i - 1], 1.0 );
+            ))
+            .unwrap()
+        )
+        .is_err()
+    );
+
+    // plate index shadows a variable
+    assert!(
+        analyze(
+            parse2::<ModelAst>(quote!(
+                mod grass;
+                use ferric::distributions::Bernoulli;
+                let rain : bool ~ Bernoulli::new( 0.2 );
+                let wet[rain in 0..3] : bool ~ Bernoulli::new( 0.2 );
+            ))
+            .unwrap()
+        )
+        .is_err()
+    );
 }
 
 #[test]
@@ -257,4 +569,57 @@ fn test_analyze_deterministic_var() {
 
     let exp_dep: syn::Expr = parse_quote!(2u8 * x as u8);
     assert_eq!(two_x_var.dependency, exp_dep);
+
+    assert_eq!(model_ir.observe_order, ["two_x"]);
+}
+
+#[test]
+fn test_analyze_dependency_graph() {
+    use quote::quote;
+    use syn::parse2;
+
+    let model_ir = analyze(
+        parse2::<ModelAst>(quote!(
+            mod chain;
+            use ferric::distributions::Normal;
+            let c : f64 ~ Normal::new(b, a);
+            let b : f64 = a * 2.0;
+            let a : f64 ~ Normal::new(0.0, 1.0);
+        ))
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(model_ir.topological_order, ["a", "b", "c"]);
+
+    let names =
+        |idents: &Vec<Ident>| -> Vec<String> { idents.iter().map(|i| i.to_string()).collect() };
+    let a = &model_ir.variables["a"];
+    assert!(a.parents.is_empty());
+    assert_eq!(names(&a.children), ["b", "c"]);
+    let c = &model_ir.variables["c"];
+    assert_eq!(names(&c.parents), ["b", "a"]);
+    assert!(c.children.is_empty());
+}
+
+#[test]
+fn test_analyze_cycle_message() {
+    use quote::quote;
+    use syn::parse2;
+
+    let err = analyze(
+        parse2::<ModelAst>(quote!(
+            mod cycle;
+            use ferric::distributions::Normal;
+            let a : f64 ~ Normal::new( c, 1.0 );
+            let b : f64 = a * 2.0;
+            let c : f64 ~ Normal::new( b, 1.0 );
+        ))
+        .unwrap(),
+    )
+    .err()
+    .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "cyclic dependency `a` -> `c` -> `b` -> `a`"
+    );
 }
@@ -1,8 +1,8 @@
 // Copyright 2022 The Ferric AI Project Developers
 use proc_macro2::{Delimiter, Group, TokenStream, TokenTree};
-use quote::{format_ident, quote};
-use std::collections::HashMap;
-use syn::{Ident, Type};
+use quote::{format_ident, quote, quote_spanned};
+use std::collections::HashSet;
+use syn::{GenericArgument, Ident, PathArguments, Type, parse_quote};
 
 use crate::analyze::{ModelIR, VariableIR};
 
@@ -17,84 +17,106 @@ pub fn codegen(ir: ModelIR) -> TokenStream {
     }
     // additional use statements needed for code generation
     use_stmts.push(quote! {use ferric::FeOption});
+    // the methods of the distributions returned by `evaldist_<var>`
+    use_stmts.push(quote! {use ferric::distributions::Distribution as _});
 
     // All variables — used for World struct fields and reset().
     let mut var_idents = Vec::<Ident>::new(); // var_<variable name>
     let mut var_type_idents = Vec::<Type>::new(); // <variable's type>
 
-    // Stochastic variables — generate eval_<var> + evaldist_<var>.
+    // Stochastic variables — generate eval_<var> + evaldist_<var> (+ indices_<var> for plates).
     let mut stoch_eval_idents = Vec::<Ident>::new(); // eval_<var>
-    let mut stoch_var_idents = Vec::<Ident>::new(); // var_<var>
-    let mut stoch_type_idents = Vec::<Type>::new();
-    let mut stoch_eval_dist_idents = Vec::<Ident>::new(); // evaldist_<var>
-    let mut stoch_eval_dist_exprs = Vec::<TokenStream>::new();
+    let mut stoch_methods = Vec::<TokenStream>::new();
+    let mut stoch_log_probs = Vec::<TokenStream>::new(); // log p(value | parents)
 
     // Deterministic variables — generate eval_<var> only (direct expression, no sampling).
-    let mut det_eval_idents = Vec::<Ident>::new(); // eval_<var>
     let mut det_var_idents = Vec::<Ident>::new(); // var_<var>
-    let mut det_type_idents = Vec::<Type>::new();
-    let mut det_eval_exprs = Vec::<TokenStream>::new();
+    let mut det_methods = Vec::<TokenStream>::new();
 
     // Query variables.
     let mut query_idents = Vec::<Ident>::new(); // <variable name>
     let mut query_type_idents = Vec::<Type>::new(); // <variable's type>
     let mut query_eval_var_idents = Vec::<Ident>::new(); // eval_<variable name>
 
-    // All observed variables — Model struct fields, World::new params, rejection sampling.
-    let mut obs_idents = Vec::<Ident>::new(); // <variable name>
-    let mut obs_type_idents = Vec::<Type>::new(); // <variable's type>
-    let mut obs_obs_idents = Vec::<Ident>::new(); // obs_<variable name>  (World field)
+    // Model inputs (observed variables, then parameters) — Model struct fields, World::new params.
+    let mut input_idents = Vec::<Ident>::new(); // <variable or parameter name>
+    let mut input_type_idents = Vec::<Type>::new(); // <variable's or parameter's type>
+    let mut input_field_idents = Vec::<Ident>::new(); // obs_<var> or param_<param>  (World field)
+
+    // All observed variables — rejection sampling.
     let mut obs_eval_idents = Vec::<Ident>::new(); // eval_<variable name>  (rejection sampling)
+    let mut obs_var_idents = Vec::<Ident>::new(); // var_<variable name>  (rejection sampling)
+    let mut obs_mismatches = Vec::<TokenStream>::new(); // `sampled` differs from obs_<var>
 
     // Stochastic observed variables only — used by weighted sampling.
     let mut obs_stoch_var_idents = Vec::<Ident>::new(); // var_<var>  (reset_for_weighted pinning)
-    let mut obs_stoch_obs_idents = Vec::<Ident>::new(); // obs_<var>  (pinning + log_prob)
-    let mut obs_stoch_eval_dist_idents = Vec::<Ident>::new(); // evaldist_<var>  (log_prob)
+    let mut obs_stoch_obs_idents = Vec::<Ident>::new(); // obs_<var>  (pinning)
+    let mut obs_stoch_log_probs = Vec::<TokenStream>::new(); // log p(obs_<var> | parents)
 
     // Vars reset to Unknown in reset_for_weighted:
     //   = all non-observed vars + deterministic observed vars.
     let mut reset_for_weighted_var_idents = Vec::<Ident>::new();
 
+    // Latent (non-observed) stochastic variables — the sites updated by Metropolis-Hastings.
+    let mut latent_var_idents = Vec::<Ident>::new(); // var_<var>
+    let mut latent_proposals = Vec::<TokenStream>::new();
+
+    // variables and parameters may both be referenced by dependency expressions
+    let names = Names {
+        variables: ir.variables.keys().cloned().collect(),
+        params: ir
+            .params
+            .iter()
+            .map(|param| param.param_ident.to_string())
+            .collect(),
+    };
+
     // process all the variables in the model
     for variable in ir.variables.values() {
         let var_ident = format_ident!("var_{}", &variable.var_ident);
         let eval_var = format_ident!("eval_{}", &variable.var_ident);
+        let value_type = value_type(variable);
 
         var_idents.push(var_ident.clone());
-        var_type_idents.push(variable.type_ident.clone());
+        var_type_idents.push(value_type.clone());
 
         if variable.is_stochastic {
-            let eval_dist_var = format_ident!("evaldist_{}", &variable.var_ident);
             stoch_eval_idents.push(eval_var.clone());
-            stoch_var_idents.push(var_ident.clone());
-            stoch_type_idents.push(variable.type_ident.clone());
-            stoch_eval_dist_idents.push(eval_dist_var.clone());
-            let dep = &variable.dependency;
-            stoch_eval_dist_exprs.push(replace(quote! {#dep}, &ir.variables));
+            stoch_methods.push(stochastic_methods(variable, &names));
+            stoch_log_probs.push(log_prob_tokens(
+                variable,
+                quote! {(*self.#var_ident.unwrap_ref())},
+            ));
         } else {
-            det_eval_idents.push(eval_var.clone());
             det_var_idents.push(var_ident.clone());
-            det_type_idents.push(variable.type_ident.clone());
-            let dep = &variable.dependency;
-            det_eval_exprs.push(replace(quote! {#dep}, &ir.variables));
+            det_methods.push(deterministic_methods(variable, &names));
         }
 
         if variable.is_queried {
             query_idents.push(variable.var_ident.clone());
-            query_type_idents.push(variable.type_ident.clone());
+            query_type_idents.push(value_type.clone());
             query_eval_var_idents.push(eval_var.clone());
         }
 
         if variable.is_observed {
-            obs_idents.push(variable.var_ident.clone());
-            obs_type_idents.push(variable.type_ident.clone());
-            obs_obs_idents.push(format_ident!("obs_{}", &variable.var_ident));
+            let obs_var = format_ident!("obs_{}", &variable.var_ident);
+            input_idents.push(variable.var_ident.clone());
+            input_type_idents.push(observed_type(variable));
+            input_field_idents.push(obs_var.clone());
             obs_eval_idents.push(eval_var.clone());
+            obs_var_idents.push(var_ident.clone());
+            obs_mismatches.push(observed_mismatch_tokens(variable));
+            obs_stoch_log_probs.extend(observed_log_prob_tokens(variable));
 
-            if variable.is_stochastic {
+            if variable.is_optional {
+                // Missing observed values are imputed like latent variables: the known
+                // values are pinned by `eval_<var>` and the missing ones are sampled.
+                reset_for_weighted_var_idents.push(var_ident.clone());
+                latent_var_idents.push(var_ident.clone());
+                latent_proposals.push(prior_proposal(variable));
+            } else if variable.is_stochastic {
                 obs_stoch_var_idents.push(var_ident.clone());
-                obs_stoch_obs_idents.push(format_ident!("obs_{}", &variable.var_ident));
-                obs_stoch_eval_dist_idents.push(format_ident!("evaldist_{}", &variable.var_ident));
+                obs_stoch_obs_idents.push(obs_var.clone());
             } else {
                 // Deterministic observed: reset to Unknown in reset_for_weighted so it gets
                 // re-evaluated from the freshly sampled stochastic variables.
@@ -102,9 +124,31 @@ pub fn codegen(ir: ModelIR) -> TokenStream {
             }
         } else {
             reset_for_weighted_var_idents.push(var_ident.clone());
+            if variable.is_stochastic {
+                latent_var_idents.push(var_ident.clone());
+                latent_proposals.push(prior_proposal(variable));
+            }
         }
     }
 
+    // process all the parameters; each is read through `eval_<param>` like a variable, so
+    // it returns a `Result` although it cannot fail
+    let mut param_methods = Vec::<TokenStream>::new();
+    for param in ir.params.iter() {
+        let param_ident = &param.param_ident;
+        let param_var = format_ident!("param_{}", param_ident);
+        let eval_param = format_ident!("eval_{}", param_ident);
+        let type_ident = &param.type_ident;
+        input_idents.push(param_ident.clone());
+        input_type_idents.push(type_ident.clone());
+        input_field_idents.push(param_var.clone());
+        param_methods.push(quote! {
+            pub fn #eval_param(&self) -> Result<&#type_ident, String> {
+                Ok(&self.#param_var)
+            }
+        });
+    }
+
     // Weighted sampling is only valid when every observed variable is stochastic.
     // When a deterministic variable is observed we cannot evaluate its log-likelihood,
     // so we omit the weighted-sampling infrastructure entirely.
@@ -113,6 +157,153 @@ pub fn codegen(ir: ModelIR) -> TokenStream {
         .values()
         .any(|v| v.is_observed && !v.is_stochastic);
 
+    // The known values of an optionally observed variable are pinned while the world is
+    // conditioned on its observations, as in importance sampling, but drawn from their
+    // prior and compared by rejection sampling.  `pin_observed` tracks which of the two
+    // applies; it only exists when a variable is observed with missing values.
+    let has_optional_observed = ir.variables.values().any(|v| v.is_optional);
+    let (pin_field, pin_new, pin_clone, pin_on, pin_off) = if has_optional_observed {
+        (
+            quote! { pin_observed: bool, },
+            quote! { pin_observed: false, },
+            quote! { pin_observed: self.pin_observed, },
+            quote! { self.pin_observed = true; },
+            quote! { self.pin_observed = false; },
+        )
+    } else {
+        (quote! {}, quote! {}, quote! {}, quote! {}, quote! {})
+    };
+
+    // The `factor` statements add arbitrary terms to the log weight of a world, summed by
+    // `log_factor`, which is only generated for a model with factors.  A NaN term is
+    // treated like an invalid distribution.
+    let factor_terms: Vec<TokenStream> = ir
+        .factors
+        .iter()
+        .map(|factor| dependency(quote! {#factor}, &names))
+        .collect();
+    let factor_strs: Vec<String> = ir
+        .factors
+        .iter()
+        .map(|factor| quote! {#factor}.to_string())
+        .collect();
+    let (log_factor_method, add_log_factor_to_weight, add_log_factor_to_joint) =
+        if ir.factors.is_empty() {
+            (quote! {}, quote! {}, quote! {})
+        } else {
+            (
+                quote! {
+                    /// The sum of the `factor` terms under the current state.
+                    fn log_factor(&mut self) -> Result<f64, String> {
+                        let mut log_factor = 0.0f64;
+                        #(
+                            {
+                                let term: f64 = #factor_terms;
+                                if term.is_nan() {
+                                    return Err(format!("factor `{}` is NaN", #factor_strs));
+                                }
+                                log_factor += term;
+                            }
+                        )*
+                        Ok(log_factor)
+                    }
+                },
+                quote! { log_weight += self.log_factor()?; },
+                quote! { log_joint += self.log_factor()?; },
+            )
+        };
+    // The `condition` statements are hard evidence: a world in which one of them is false
+    // has probability zero.  `conditions_hold` is only generated for a model with
+    // conditions.
+    let condition_terms: Vec<TokenStream> = ir
+        .conditions
+        .iter()
+        .map(|condition| dependency(quote! {#condition}, &names))
+        .collect();
+    let (
+        conditions_hold_method,
+        reject_by_conditions,
+        zero_weight_by_conditions,
+        zero_joint_by_conditions,
+    ) = if ir.conditions.is_empty() {
+        (quote! {}, quote! {}, quote! {}, quote! {})
+    } else {
+        (
+            quote! {
+                /// Whether every `condition` holds in the current state.
+                fn conditions_hold(&mut self) -> Result<bool, String> {
+                    #(
+                        {
+                            let holds: bool = #condition_terms;
+                            if !holds {
+                                return Ok(false);
+                            }
+                        }
+                    )*
+                    Ok(true)
+                }
+            },
+            quote! {
+                if !self.conditions_hold()? {
+                    return Ok(None);
+                }
+            },
+            quote! {
+                if !self.conditions_hold()? {
+                    log_weight = f64::NEG_INFINITY;
+                }
+            },
+            quote! {
+                if !self.conditions_hold()? {
+                    log_joint = f64::NEG_INFINITY;
+                }
+            },
+        )
+    };
+    // A Markov chain cannot leave a starting state in which a condition is false, so such
+    // a state is discarded like an invalid one and the latent variables are drawn again.
+    let (fail_unless_conditions_hold, mh_start_where_conditions_hold) = if ir.conditions.is_empty()
+    {
+        (quote! {}, quote! {})
+    } else {
+        (
+            quote! {
+                if !self.conditions_hold()? {
+                    return Err(String::from("a `condition` of the model is false"));
+                }
+            },
+            quote! {
+                let mut num_invalid = 0;
+                while let Err(err) = world.try_start() {
+                    world.diagnostics.record(err);
+                    num_invalid += 1;
+                    if world.diagnostics.gives_up(num_invalid) {
+                        break;
+                    }
+                    world.reset_for_weighted();
+                }
+            },
+        )
+    };
+    // Rejection sampling accepts a prior draw that matches the observations with
+    // probability `exp(log_factor)`, which requires every factor to be non-positive.
+    let reject_by_log_factor = if ir.factors.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            let log_factor = self.log_factor()?;
+            if log_factor > 0.0 {
+                return Err(format!(
+                    "rejection sampling requires non-positive factors, found a total of {}",
+                    log_factor
+                ));
+            }
+            if self.rng.r#gen::<f64>() >= log_factor.exp() {
+                return Ok(None);
+            }
+        }
+    };
+
     let weighted_structs = if !has_det_observed {
         quote! {
             /// A sample returned by self-normalised importance sampling via
@@ -146,11 +337,18 @@ pub fn codegen(ir: ModelIR) -> TokenStream {
             /// Obtain one via [`Model::weighted_sample_iter`].
             pub struct WeightedWorld<R>(World<R>);
 
+            impl<R: rand::Rng> WeightedWorld<R> {
+                /// The draws discarded so far; see [`World::diagnostics`].
+                pub fn diagnostics(&self) -> &ferric::Diagnostics {
+                    self.0.diagnostics()
+                }
+            }
+
             impl<R: rand::Rng> Iterator for WeightedWorld<R> {
                 type Item = WeightedSample;
 
                 fn next(&mut self) -> Option<Self::Item> {
-                    Some(self.0.weighted_sample())
+                    self.0.weighted_sample()
                 }
             }
         }
@@ -170,9 +368,11 @@ pub fn codegen(ir: ModelIR) -> TokenStream {
             /// log_weight = Σ log p(obs_i | latent variables)
             /// ```
             ///
-            /// Collect the `log_weight` values alongside the queried
-            /// fields and pass them to [`ferric::weighted_mean`] or
-            /// [`ferric::weighted_std`] to obtain posterior estimates.
+            /// plus the terms of any `factor` statements, or to `-inf` if a
+            /// `condition` is false.  Collect the `log_weight` values
+            /// alongside the queried fields and pass them to
+            /// [`ferric::weighted_mean`] or [`ferric::weighted_std`] to obtain
+            /// posterior estimates.
             ///
             /// # When to use
             ///
@@ -180,14 +380,90 @@ pub fn codegen(ir: ModelIR) -> TokenStream {
             /// stochastic (has a distribution).  Also correct (though less
             /// sample-efficient than rejection sampling) for purely discrete
             /// models.
+            ///
+            /// Draws under which a distribution cannot be constructed have
+            /// zero weight, so they are not returned; they are counted in
+            /// [`WeightedWorld::diagnostics`].
             pub fn weighted_sample_iter(&self) -> WeightedWorld<rand::rngs::ThreadRng> {
+                self.weighted_sample_iter_with_rng(rand::thread_rng())
+            }
+
+            /// Like [`Model::weighted_sample_iter`], but draws every random
+            /// number from `rng`.
+            pub fn weighted_sample_iter_with_rng<R: rand::Rng>(&self, rng: R) -> WeightedWorld<R> {
                 WeightedWorld(World::new(
-                    rand::thread_rng(),
+                    rng,
                     #(
-                        self.#obs_idents.clone(),
+                        self.#input_idents.clone(),
                     )*
                 ))
             }
+
+            /// Draw `num_samples` importance-weighted samples on
+            /// `num_threads` threads, each running its own world with an
+            /// independently seeded random number stream.
+            ///
+            /// Equivalent to collecting `num_samples` items of
+            /// [`Model::weighted_sample_iter`]; a thread whose sampler stops
+            /// contributes fewer.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `num_threads` is zero.
+            pub fn par_weighted_sample(
+                &self,
+                num_samples: usize,
+                num_threads: usize,
+            ) -> Vec<WeightedSample> {
+                self.par_weighted_sample_with_rng(num_samples, num_threads, rand::thread_rng())
+            }
+
+            /// Like [`Model::par_weighted_sample`], but seeds the stream of
+            /// every thread from `rng`.
+            pub fn par_weighted_sample_with_rng<R: rand::Rng>(
+                &self,
+                num_samples: usize,
+                num_threads: usize,
+                mut rng: R,
+            ) -> Vec<WeightedSample> {
+                ferric::inference::par_draw(num_samples, num_threads, &mut rng, |stream, count| {
+                    self.weighted_sample_iter_with_rng(stream).take(count).collect()
+                })
+            }
+
+            /// Estimate the log marginal likelihood of the observations,
+            /// `log p(observations)`, from `num_samples` importance-weighted
+            /// samples, together with its Monte Carlo standard error.
+            ///
+            /// See [`ferric::log_evidence`].  The difference between the
+            /// estimates of two models of the same observations is their
+            /// log Bayes factor.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `num_samples` is zero.
+            pub fn log_evidence(&self, num_samples: usize) -> ferric::LogEvidence {
+                self.log_evidence_with_rng(num_samples, rand::thread_rng())
+            }
+
+            /// Like [`Model::log_evidence`], but draws every random number
+            /// from `rng`.
+            pub fn log_evidence_with_rng<R: rand::Rng>(
+                &self,
+                num_samples: usize,
+                rng: R,
+            ) -> ferric::LogEvidence {
+                let mut worlds = self.weighted_sample_iter_with_rng(rng);
+                let mut log_weights: Vec<f64> = worlds
+                    .by_ref()
+                    .take(num_samples)
+                    .map(|ws| ws.log_weight)
+                    .collect();
+                // the discarded draws count with zero weight
+                let num_draws = log_weights.len() + worlds.diagnostics().num_invalid();
+                log_weights.resize(num_draws, f64::NEG_INFINITY);
+                ferric::log_evidence(&log_weights)
+            }
         }
     } else {
         quote! {}
@@ -205,219 +481,2211 @@ pub fn codegen(ir: ModelIR) -> TokenStream {
             /// log_weight = Σ log p(obs_i | latent variables)
             /// ```
             ///
-            /// Valid for discrete and continuous observations alike.
-            pub fn weighted_sample(&mut self) -> WeightedSample {
-                self.reset_for_weighted();
+            /// Valid for discrete and continuous observations alike.  Draws
+            /// under which a distribution cannot be constructed have zero
+            /// weight; they are discarded, counted in
+            /// [`World::diagnostics`], and drawn again.
+            ///
+            /// Returns `None`, and stops for good, once
+            /// [`ferric::Diagnostics::MAX_CONSECUTIVE_INVALID`] draws in a
+            /// row are discarded.
+            pub fn weighted_sample(&mut self) -> Option<WeightedSample> {
+                let mut num_invalid = 0;
+                while !self.diagnostics.stopped() {
+                    self.reset_for_weighted();
+                    match self.try_weighted_sample() {
+                        Ok(weighted_sample) => return Some(weighted_sample),
+                        Err(err) => {
+                            self.diagnostics.record(err);
+                            num_invalid += 1;
+                            self.diagnostics.gives_up(num_invalid);
+                        }
+                    }
+                }
+                None
+            }
+
+            fn try_weighted_sample(&mut self) -> Result<WeightedSample, String> {
                 let mut log_weight = 0.0f64;
                 #(
-                    {
-                        let dist = self.#obs_stoch_eval_dist_idents();
-                        log_weight += dist.log_prob(&self.#obs_stoch_obs_idents);
-                    }
+                    log_weight += #obs_stoch_log_probs;
                 )*
-                WeightedSample {
+                #add_log_factor_to_weight
+                #zero_weight_by_conditions
+                Ok(WeightedSample {
                     log_weight,
-                    sample: Sample {
-                        #(
-                            #query_idents: self.#query_eval_var_idents(),
-                        )*
-                    },
-                }
+                    sample: self.query()?,
+                })
             }
         }
     } else {
         quote! {}
     };
 
-    quote! {
-        pub mod #model_ident {
-            #(
-                #use_stmts;
-            )*
-
-            /// A sample returned by rejection sampling via [`Model::sample_iter`].
-            ///
-            /// Every observed variable matched its observed value exactly, so
-            /// all queried fields are drawn from the exact posterior.
-            pub struct Sample {
+    // Single-site Metropolis-Hastings shares the restriction of weighted sampling: the
+    // log joint density is only defined when every observed variable is stochastic.
+    let num_latents = latent_var_idents.len();
+    let latent_indices = 0..num_latents;
+    let mh_site_update = if num_latents > 0 {
+        quote! {
+            let old_log_joint = self.try_log_joint()?;
+            match self.rng.gen_range(0..#num_latents) {
                 #(
-                    pub #query_idents: #query_type_idents,
+                    #latent_indices => {
+                        // The proposal is the prior of the site given its parents, which
+                        // is unaffected by the change because the model is a DAG.
+                        let old_value = self.#latent_var_idents.clone();
+                        let proposal: Option<(f64, f64)> = #latent_proposals;
+                        if let Some((log_q_old, log_q_new)) = proposal {
+                            self.reset_deterministic();
+                            // a proposal under which a distribution is invalid is rejected
+                            let new_log_joint = self.log_joint();
+                            let log_alpha =
+                                (new_log_joint - log_q_new) - (old_log_joint - log_q_old);
+                            // A NaN `log_alpha` compares false and is rejected.
+                            let accept = self.rng.r#gen::<f64>().ln() < log_alpha;
+                            if !accept {
+                                self.#latent_var_idents = old_value;
+                                self.reset_deterministic();
+                            }
+                        }
+                    }
                 )*
+                _ => unreachable!(),
             }
+        }
+    } else {
+        quote! {}
+    };
 
-            #weighted_structs
-
-            /// The observed data for the model.
-            pub struct Model {
-                #(
-                    pub #obs_idents: #obs_type_idents,
-                )*
-            }
+    let mh_structs = if !has_det_observed {
+        quote! {
+            /// Iterator adaptor over [`World`] that yields [`Sample`]s from a
+            /// single-site Metropolis-Hastings Markov chain.
+            ///
+            /// Obtain one via [`Model::mh_sample_iter`].
+            pub struct MhWorld<R>(World<R>);
 
-            impl Model {
-                /// Returns an iterator of exact posterior samples via
-                /// rejection sampling.
-                ///
-                /// Each call to [`Iterator::next`] loops internally until a
-                /// prior sample is consistent with every observation, then
-                /// returns that sample.
-                ///
-                /// # When to use
-                ///
-                /// Only valid when **all** observed variables are discrete.
-                /// Conditioning on a continuous observed value has probability
-                /// zero and this iterator will loop forever.  Use
-                /// [`Model::weighted_sample_iter`] for models with continuous
-                /// observations.
-                pub fn sample_iter(&self) -> World<rand::rngs::ThreadRng> {
-                    World::new(
-                        rand::thread_rng(),
-                        #(
-                            self.#obs_idents.clone(),
-                        )*
-                    )
+            impl<R: rand::Rng> MhWorld<R> {
+                /// The draws discarded so far; see [`World::diagnostics`].
+                pub fn diagnostics(&self) -> &ferric::Diagnostics {
+                    self.0.diagnostics()
                 }
-
-                #weighted_sample_iter_method
-            }
-
-            pub struct World<R> {
-                rng: R,
-                #(#var_idents: FeOption<#var_type_idents>, )*
-                #(#obs_obs_idents: #obs_type_idents, )*
             }
 
-            impl<R: rand::Rng> Iterator for World<R> {
+            impl<R: rand::Rng> Iterator for MhWorld<R> {
                 type Item = Sample;
 
                 fn next(&mut self) -> Option<Self::Item> {
-                    Some(self.sample())
+                    self.0.mh_step()
                 }
             }
+        }
+    } else {
+        quote! {}
+    };
 
-            impl<R: rand::Rng> World<R> {
-                pub fn new(rng: R, #(#obs_idents: #obs_type_idents,)*) -> World<R> {
-                    World {
-                        rng: rng,
-                        #(#var_idents: FeOption::Unknown, )*
-                        #(#obs_obs_idents: #obs_idents, )*
-                    }
-                }
+    let mh_sample_iter_method = if !has_det_observed {
+        quote! {
+            /// Returns an iterator over the states of a single-site
+            /// Metropolis-Hastings Markov chain.
+            ///
+            /// The chain starts from a prior draw of the latent variables in
+            /// which every `condition` holds.  Each call to
+            /// [`Iterator::next`] performs one [`World::mh_step`] and returns
+            /// the queried variables of the resulting state.  Successive
+            /// samples are correlated, so discard an initial burn-in with
+            /// [`Iterator::skip`] and thin with [`Iterator::step_by`] as
+            /// needed.
+            ///
+            /// # When to use
+            ///
+            /// Valid for all models where every observed variable is
+            /// stochastic.  Unlike [`Model::weighted_sample_iter`] its
+            /// efficiency does not collapse as the number of observations
+            /// grows.
+            ///
+            /// The chain yields nothing if no starting state is found in
+            /// [`ferric::Diagnostics::MAX_CONSECUTIVE_INVALID`] prior draws,
+            /// for example because the `condition`s can never hold together;
+            /// [`MhWorld::diagnostics`] then lists why.
+            pub fn mh_sample_iter(&self) -> MhWorld<rand::rngs::ThreadRng> {
+                self.mh_sample_iter_with_rng(rand::thread_rng())
+            }
 
-                pub fn reset(&mut self) {
+            /// Like [`Model::mh_sample_iter`], but draws every random number
+            /// from `rng`.
+            pub fn mh_sample_iter_with_rng<R: rand::Rng>(&self, rng: R) -> MhWorld<R> {
+                let mut world = World::new(
+                    rng,
                     #(
-                        self.#var_idents = FeOption::Unknown;
+                        self.#input_idents.clone(),
                     )*
-                }
+                );
+                world.reset_for_weighted();
+                #mh_start_where_conditions_hold
+                MhWorld(world)
+            }
+        }
+    } else {
+        quote! {}
+    };
 
-                /// Like `reset`, but pins every stochastic observed variable to its
-                /// known value and resets everything else (including deterministic
-                /// variables) to Unknown so they are re-evaluated from the freshly
-                /// sampled stochastic latents.
-                ///
-                /// Used by importance sampling.
-                fn reset_for_weighted(&mut self) {
-                    #(
-                        self.#reset_for_weighted_var_idents = FeOption::Unknown;
-                    )*
-                    #(
-                        self.#obs_stoch_var_idents = FeOption::Known(self.#obs_stoch_obs_idents.clone());
-                    )*
+    let mh_world_methods = if !has_det_observed {
+        quote! {
+            /// Log joint density of the current state,
+            ///
+            /// ```text
+            /// log_joint = Σ log p(v | parents(v))
+            /// ```
+            ///
+            /// summed over every stochastic variable `v`, latent and
+            /// observed, plus the terms of any `factor` statements; it is
+            /// `-inf` if a `condition` is false.  Latent variables that are
+            /// still unknown are first drawn from their priors.  A state
+            /// under which a distribution cannot be constructed has log
+            /// density `-inf` and is counted in [`World::diagnostics`].
+            pub fn log_joint(&mut self) -> f64 {
+                match self.try_log_joint() {
+                    Ok(log_joint) => log_joint,
+                    Err(err) => {
+                        self.diagnostics.record(err);
+                        f64::NEG_INFINITY
+                    }
                 }
+            }
 
-                /// Draw one exact posterior sample via rejection sampling.
-                ///
-                /// Loops until a prior draw matches every observed value, then
-                /// returns the queried variable values.  Only valid for
-                /// discrete observations.
-                pub fn sample(&mut self) -> Sample {
-                    loop {
-                        self.reset();
-                        #(
-                            {
-                                let sampled = self.#obs_eval_idents();
-                                if self.#obs_obs_idents != sampled {
-                                    continue;
-                                }
-                            }
-                        )*
-                        return Sample {
-                            #(
-                                #query_idents: self.#query_eval_var_idents(),
-                            )*
-                        };
+            fn try_log_joint(&mut self) -> Result<f64, String> {
+                let mut log_joint = 0.0f64;
+                #(
+                    {
+                        self.#stoch_eval_idents()?;
+                        log_joint += #stoch_log_probs;
+                    }
+                )*
+                #add_log_factor_to_joint
+                #zero_joint_by_conditions
+                Ok(log_joint)
+            }
+
+            /// Perform one single-site Metropolis-Hastings update.
+            ///
+            /// A latent stochastic variable is chosen uniformly at random
+            /// (for a plate, one of its elements is then chosen uniformly)
+            /// and a new value is proposed from its prior given its
+            /// parents.  The proposal is accepted with probability
+            ///
+            /// ```text
+            /// min(1, p(x') q(x | x') / (p(x) q(x' | x)))
+            /// ```
+            ///
+            /// where `p` is [`World::log_joint`] and `q` the prior proposal.
+            /// Returns the queried variables of the (possibly unchanged)
+            /// state.
+            ///
+            /// If a distribution of the current state cannot be constructed,
+            /// which can only happen before the first step, the latent
+            /// variables are drawn again from their priors.
+            ///
+            /// Returns `None`, and stops for good, once
+            /// [`ferric::Diagnostics::MAX_CONSECUTIVE_INVALID`] draws in a
+            /// row are invalid.
+            pub fn mh_step(&mut self) -> Option<Sample> {
+                let mut num_invalid = 0;
+                while !self.diagnostics.stopped() {
+                    match self.try_mh_step() {
+                        Ok(sample) => return Some(sample),
+                        Err(err) => {
+                            self.diagnostics.record(err);
+                            num_invalid += 1;
+                            self.diagnostics.gives_up(num_invalid);
+                            self.reset_for_weighted();
+                        }
                     }
                 }
+                None
+            }
 
-                #weighted_sample_method
+            fn try_mh_step(&mut self) -> Result<Sample, String> {
+                #mh_site_update
+                self.query()
+            }
 
-                // --- Stochastic variable methods ---
+            // succeeds if a Markov chain may start from the current state: every
+            // distribution can be constructed and every condition holds
+            fn try_start(&mut self) -> Result<(), String> {
+                self.try_log_joint()?;
+                #fail_unless_conditions_hold
+                Ok(())
+            }
+        }
+    } else {
+        quote! {}
+    };
 
-                #(
-                pub fn #stoch_eval_idents(&mut self) -> #stoch_type_idents {
-                    if self.#stoch_var_idents.is_unknown() {
-                        let dist = self.#stoch_eval_dist_idents();
-                        self.#stoch_var_idents = FeOption::Known(dist.sample(&mut self.rng));
+    // Gradient-based samplers need every latent variable to be real valued so that the
+    // latent state can be flattened into a single unconstrained vector.  Latents are
+    // visited in dependency order so that every parent is updated before the support
+    // of its children is computed.  Only the missing values of a variable observed with
+    // missing values are coordinates; its known values stay pinned.
+    let mut latent_to_unconstrained = Vec::<TokenStream>::new();
+    let mut latent_from_unconstrained = Vec::<TokenStream>::new();
+    let mut all_latents_real = true;
+    for var_name in ir.topological_order.iter() {
+        let variable = &ir.variables[var_name];
+        if !variable.is_stochastic || (variable.is_observed && !variable.is_optional) {
+            continue;
+        }
+        let obs_var = format_ident!("obs_{}", &variable.var_ident);
+        let var_ident = format_ident!("var_{}", &variable.var_ident);
+        let eval_var = format_ident!("eval_{}", &variable.var_ident);
+        let eval_dist_var = format_ident!("evaldist_{}", &variable.var_ident);
+        let type_ident = &variable.type_ident;
+        if variable.plate.is_some() {
+            // a plate of reals has one coordinate per element, each with its own support
+            if real_type_is_vector(type_ident) != Some(false) {
+                all_latents_real = false;
+                continue;
+            }
+            let indices_var = format_ident!("indices_{}", &variable.var_ident);
+            if variable.is_optional {
+                latent_to_unconstrained.push(quote! {
+                    let len = self.#eval_var()?.len();
+                    let indices = self.#indices_var()?;
+                    for (k, i) in indices.into_iter().enumerate().take(len) {
+                        if matches!(self.#obs_var.get(k), Some(FeOption::Known(_))) {
+                            continue;
+                        }
+                        let dist = self.#eval_dist_var(i)?;
+                        let value = self.#var_ident.unwrap_ref()[k];
+                        position.push(ferric::inference::to_unconstrained(value, dist.support()));
                     }
-                    // TODO: handle the case when the value is Null
-                    self.#stoch_var_idents.unwrap_clone()
+                });
+                latent_from_unconstrained.push(quote! {
+                    let indices = self.#indices_var()?;
+                    let mut values = Vec::with_capacity(indices.len());
+                    for (k, i) in indices.into_iter().enumerate() {
+                        if let Some(FeOption::Known(value)) = self.#obs_var.get(k) {
+                            values.push(*value);
+                            continue;
+                        }
+                        let dist = self.#eval_dist_var(i)?;
+                        let (value, log_jac) =
+                            ferric::inference::from_unconstrained(position[offset], dist.support());
+                        offset += 1;
+                        log_jacobian += log_jac;
+                        values.push(value);
+                    }
+                    self.#var_ident = FeOption::Known(values);
+                });
+                continue;
+            }
+            latent_to_unconstrained.push(quote! {
+                let len = self.#eval_var()?.len();
+                let indices = self.#indices_var()?;
+                for (k, i) in indices.into_iter().enumerate().take(len) {
+                    let dist = self.#eval_dist_var(i)?;
+                    let value = self.#var_ident.unwrap_ref()[k];
+                    position.push(ferric::inference::to_unconstrained(value, dist.support()));
                 }
+            });
+            latent_from_unconstrained.push(quote! {
+                let indices = self.#indices_var()?;
+                let mut values = Vec::with_capacity(indices.len());
+                for i in indices {
+                    let dist = self.#eval_dist_var(i)?;
+                    let (value, log_jac) =
+                        ferric::inference::from_unconstrained(position[offset], dist.support());
+                    offset += 1;
+                    log_jacobian += log_jac;
+                    values.push(value);
+                }
+                self.#var_ident = FeOption::Known(values);
+            });
+            continue;
+        }
+        let num_mapped = latent_to_unconstrained.len();
+        match real_type_is_vector(type_ident) {
+            Some(false) => {
+                latent_to_unconstrained.push(quote! {
+                    let value = *self.#eval_var()?;
+                    let dist = self.#eval_dist_var()?;
+                    position.push(ferric::inference::to_unconstrained(value, dist.support()));
+                });
+                latent_from_unconstrained.push(quote! {
+                    let dist = self.#eval_dist_var()?;
+                    let (value, log_jac) =
+                        ferric::inference::from_unconstrained(position[offset], dist.support());
+                    offset += 1;
+                    log_jacobian += log_jac;
+                    self.#var_ident = FeOption::Known(value);
+                });
+            }
+            Some(true) => {
+                latent_to_unconstrained.push(quote! {
+                    let dist = self.#eval_dist_var()?;
+                    let support = dist.support();
+                    let value = self.#eval_var()?;
+                    position.extend(
+                        value
+                            .iter()
+                            .map(|&x| ferric::inference::to_unconstrained(x, support)),
+                    );
+                });
+                latent_from_unconstrained.push(quote! {
+                    // the dimension is fixed by the value drawn when the chain started
+                    let len = self.#eval_var()?.len();
+                    let dist = self.#eval_dist_var()?;
+                    let support = dist.support();
+                    let mut values = Vec::with_capacity(len);
+                    for u in &position[offset..offset + len] {
+                        let (value, log_jac) = ferric::inference::from_unconstrained(*u, support);
+                        log_jacobian += log_jac;
+                        values.push(value);
+                    }
+                    offset += len;
+                    self.#var_ident = FeOption::Known(<#type_ident>::from_vec(values));
+                });
+            }
+            None => all_latents_real = false,
+        }
+        if variable.is_optional && latent_to_unconstrained.len() > num_mapped {
+            for mapping in [&mut latent_to_unconstrained, &mut latent_from_unconstrained] {
+                let unpinned = mapping.pop().expect("just pushed");
+                mapping.push(quote! {
+                    if !self.#obs_var.is_known() {
+                        #unpinned
+                    }
+                });
+            }
+        }
+    }
+    let has_gradient_support = !has_det_observed && num_latents > 0 && all_latents_real;
+
+    let hmc_structs = if has_gradient_support {
+        quote! {
+            /// Iterator adaptor over [`World`] that yields [`Sample`]s from a
+            /// gradient-based Markov chain driven by the kernel `K`.
+            ///
+            /// Obtain one via [`Model::hmc_sample_iter`] or
+            /// [`Model::nuts_sample_iter`].
+            pub struct HmcWorld<R, K> {
+                world: World<R>,
+                kernel: K,
+                position: Vec<f64>,
+            }
 
-                pub fn #stoch_eval_dist_idents(&mut self) -> Box<dyn ferric::distributions::Distribution<R, Domain=#stoch_type_idents>> {
-                    // TODO: handle errors in constructing the distribution object
-                    let dist = #stoch_eval_dist_exprs;
-                    Box::new(dist.unwrap())
+            impl<R: rand::Rng, K> HmcWorld<R, K> {
+                /// The evaluations discarded so far; see [`World::diagnostics`].
+                pub fn diagnostics(&self) -> &ferric::Diagnostics {
+                    self.world.diagnostics()
                 }
-                )*
+            }
 
-                // --- Deterministic variable methods (no distribution, no sampling) ---
+            impl<R: rand::Rng, K: ferric::inference::Kernel> Iterator for HmcWorld<R, K> {
+                type Item = Sample;
 
-                #(
-                pub fn #det_eval_idents(&mut self) -> #det_type_idents {
-                    if self.#det_var_idents.is_unknown() {
-                        let val = #det_eval_exprs;
-                        self.#det_var_idents = FeOption::Known(val);
+                /// Move the chain one transition and return the queried
+                /// variables of its new state.
+                ///
+                /// Returns `None`, and stops for good, if the chain found no
+                /// valid starting state or the queried variables cannot be
+                /// computed in the new state; [`HmcWorld::diagnostics`] then
+                /// lists why.
+                fn next(&mut self) -> Option<Self::Item> {
+                    if self.world.diagnostics.stopped() {
+                        return None;
+                    }
+                    self.kernel.transition(&mut self.world, &mut self.position);
+                    self.world.set_unconstrained(&self.position);
+                    match self.world.query() {
+                        Ok(sample) => Some(sample),
+                        Err(err) => {
+                            self.world.diagnostics.stop(err);
+                            None
+                        }
                     }
-                    self.#det_var_idents.unwrap_clone()
                 }
-                )*
             }
         }
-    }
-}
+    } else {
+        quote! {}
+    };
 
-// replace all occurrences of `var_name` in the dependency expression with `self.eval_var_name()`
-fn replace(dep_tokens: TokenStream, variables: &HashMap<String, VariableIR>) -> TokenStream {
-    let dep_it = dep_tokens.into_iter();
-
-    dep_it
-        .map(|tt| match tt {
-            TokenTree::Ident(ref i) => {
-                let i_name = &i.to_string();
-                if variables.contains_key(i_name) {
-                    let var_ident = Ident::new(&format!("eval_{}", i_name), i.span());
-                    let stream = quote! {self.#var_ident()};
-                    let mut group = Group::new(Delimiter::None, stream);
-                    group.set_span(i.span());
-                    TokenTree::Group(group)
-                } else {
-                    tt
+    let hmc_sample_iter_methods = if has_gradient_support {
+        quote! {
+            /// Returns an iterator over the states of a gradient-based Markov
+            /// chain that uses `kernel`, for example
+            /// [`ferric::inference::Hmc`].
+            ///
+            /// The chain starts from a prior draw of the latent variables and
+            /// moves over their unconstrained parameterisation.  Successive
+            /// samples are correlated.
+            ///
+            /// # When to use
+            ///
+            /// Only generated when every latent variable is `f64` or
+            /// `DVector<f64>` and every observed variable is stochastic.
+            pub fn hmc_sample_iter<K: ferric::inference::Kernel>(
+                &self,
+                kernel: K,
+            ) -> HmcWorld<rand::rngs::ThreadRng, K> {
+                self.hmc_sample_iter_with_rng(kernel, rand::thread_rng())
+            }
+
+            /// Like [`Model::hmc_sample_iter`], but draws every random number
+            /// from `rng`.
+            pub fn hmc_sample_iter_with_rng<R: rand::Rng, K: ferric::inference::Kernel>(
+                &self,
+                kernel: K,
+                rng: R,
+            ) -> HmcWorld<R, K> {
+                let mut world = World::new(
+                    rng,
+                    #(
+                        self.#input_idents.clone(),
+                    )*
+                );
+                world.reset_for_weighted();
+                // a chain without a valid starting state yields nothing
+                let position = world.unconstrained().unwrap_or_default();
+                HmcWorld {
+                    world,
+                    kernel,
+                    position,
                 }
             }
-            TokenTree::Group(ref g) => {
-                let stream = replace(g.stream(), variables);
-                let mut group = Group::new(g.delimiter(), stream);
+
+            /// Returns an iterator over the states of a No-U-Turn Sampler
+            /// chain with the default settings of
+            /// [`ferric::inference::Nuts`].
+            ///
+            /// The step size adapts during the first 1000 samples, which
+            /// should be discarded as burn-in.
+            pub fn nuts_sample_iter(
+                &self,
+            ) -> HmcWorld<rand::rngs::ThreadRng, ferric::inference::Nuts> {
+                self.hmc_sample_iter(ferric::inference::Nuts::default())
+            }
+
+            /// Like [`Model::nuts_sample_iter`], but draws every random number
+            /// from `rng`.
+            pub fn nuts_sample_iter_with_rng<R: rand::Rng>(
+                &self,
+                rng: R,
+            ) -> HmcWorld<R, ferric::inference::Nuts> {
+                self.hmc_sample_iter_with_rng(ferric::inference::Nuts::default(), rng)
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let hmc_world_methods = if has_gradient_support {
+        quote! {
+            /// The latent variables mapped onto the whole real line and
+            /// flattened into one vector, in dependency order.
+            ///
+            /// Latent variables that are still unknown are first drawn from
+            /// their priors.  If a distribution of the state, latent or
+            /// observed, cannot be constructed, or a `condition` of the model
+            /// is false, the latent variables are drawn again until every
+            /// distribution is valid and every condition holds.
+            ///
+            /// Returns `None`, and stops for good, once
+            /// [`ferric::Diagnostics::MAX_CONSECUTIVE_INVALID`] draws in a
+            /// row are invalid.
+            pub fn unconstrained(&mut self) -> Option<Vec<f64>> {
+                let mut num_invalid = 0;
+                while !self.diagnostics.stopped() {
+                    match self.try_start().and_then(|_| self.try_unconstrained()) {
+                        Ok(position) => return Some(position),
+                        Err(err) => {
+                            self.diagnostics.record(err);
+                            num_invalid += 1;
+                            self.diagnostics.gives_up(num_invalid);
+                            self.reset_for_weighted();
+                        }
+                    }
+                }
+                None
+            }
+
+            fn try_unconstrained(&mut self) -> Result<Vec<f64>, String> {
+                let mut position = Vec::new();
+                #(
+                    {
+                        #latent_to_unconstrained
+                    }
+                )*
+                Ok(position)
+            }
+
+            /// Set the latent variables from an unconstrained `position` as
+            /// returned by [`World::unconstrained`] and return the
+            /// log-Jacobian of the mapping, or `-inf` if a distribution
+            /// cannot be constructed at `position`.
+            pub fn set_unconstrained(&mut self, position: &[f64]) -> f64 {
+                match self.try_set_unconstrained(position) {
+                    Ok(log_jacobian) => log_jacobian,
+                    Err(err) => {
+                        self.diagnostics.record(err);
+                        f64::NEG_INFINITY
+                    }
+                }
+            }
+
+            fn try_set_unconstrained(&mut self, position: &[f64]) -> Result<f64, String> {
+                self.reset_deterministic();
+                let mut offset = 0usize;
+                let mut log_jacobian = 0.0f64;
+                #(
+                    {
+                        #latent_from_unconstrained
+                    }
+                )*
+                debug_assert_eq!(offset, position.len());
+                Ok(log_jacobian)
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let hmc_log_density_impl = if has_gradient_support {
+        quote! {
+            impl<R: rand::Rng> ferric::inference::LogDensity for World<R> {
+                type Rng = R;
+
+                fn rng(&mut self) -> &mut R {
+                    &mut self.rng
+                }
+
+                /// [`World::log_joint`] at the unconstrained `position`
+                /// plus the log-Jacobian of the mapping.
+                fn log_density(&mut self, position: &[f64]) -> f64 {
+                    let log_density = self
+                        .try_set_unconstrained(position)
+                        .and_then(|log_jacobian| Ok(self.try_log_joint()? + log_jacobian));
+                    match log_density {
+                        Ok(log_density) => log_density,
+                        Err(err) => {
+                            self.diagnostics.record(err);
+                            f64::NEG_INFINITY
+                        }
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Sequential Monte Carlo incorporates the stochastic observations one at a time in
+    // the order of their `observe` statements, and the `factor` and `condition`
+    // statements as one last step.
+    let mut smc_obs_log_probs = Vec::<TokenStream>::new(); // log p(obs_<var> | parents)
+    let mut smc_observe_methods = Vec::<TokenStream>::new(); // ParticleFilter::observe_<var>
+    for var_name in ir.observe_order.iter() {
+        let variable = &ir.variables[var_name];
+        if let Some(log_prob) = observed_log_prob_tokens(variable) {
+            smc_observe_methods.push(smc_observe_method(variable, smc_obs_log_probs.len()));
+            smc_obs_log_probs.push(log_prob);
+        }
+    }
+    if !ir.factors.is_empty() || !ir.conditions.is_empty() {
+        smc_obs_log_probs.push(quote! {
+            {
+                let mut log_weight = 0.0f64;
+                #add_log_factor_to_weight
+                #zero_weight_by_conditions
+                log_weight
+            }
+        });
+    }
+    let num_observations = smc_obs_log_probs.len();
+    let smc_obs_indices = 0..num_observations;
+    let observation_log_prob_body = if num_observations > 0 {
+        quote! {
+            Ok(match index {
+                #(
+                    #smc_obs_indices => #smc_obs_log_probs,
+                )*
+                _ => unreachable!(),
+            })
+        }
+    } else {
+        quote! {
+            unreachable!("observation {} of a model without observations", index)
+        }
+    };
+
+    let smc_structs = if !has_det_observed {
+        quote! {
+            /// The output of sequential Monte Carlo run to completion via
+            /// [`Model::smc`].
+            pub struct SmcResult {
+                /// The final population of weighted particles.  Pass their
+                /// `log_weight`s to [`ferric::weighted_mean`] and
+                /// [`ferric::weighted_std`] for posterior estimates.
+                pub samples: Vec<WeightedSample>,
+                /// Estimate of the log marginal likelihood
+                /// $\log p(\text{observations})$.
+                pub log_evidence: f64,
+                /// The particles discarded because a distribution could not be
+                /// constructed.
+                pub diagnostics: ferric::Diagnostics,
+            }
+
+            /// A bootstrap particle filter over a population of [`World`]s.
+            ///
+            /// Each observation is incorporated by multiplying every particle's
+            /// weight by its likelihood.  [`ParticleFilter::step`] takes the
+            /// next one, in the order of the `observe` statements, with the
+            /// value given to the [`Model`]; an `observe_<var>` method instead
+            /// incorporates a value that has only just arrived.  Latent
+            /// variables are drawn from their priors the first time an
+            /// observation depends on them.  The `factor` and `condition`
+            /// statements of the model, if any, count as one last observation.
+            /// Whenever the effective sample size falls below half the number
+            /// of particles the population is resampled, which focuses later
+            /// draws on plausible worlds.  A particle under which a
+            /// distribution cannot be constructed gets zero weight and is
+            /// counted in [`ParticleFilter::diagnostics`].
+            ///
+            /// A plate cannot refer to its own earlier elements, so the state
+            /// of a time-indexed model needs one variable per time step.
+            /// Resampling only copies existing particles and nothing moves
+            /// them afterwards, so the values of a latent variable that is
+            /// shared by all time steps collapse onto the few that survive.
+            ///
+            /// Obtain one via [`Model::particle_filter`].
+            pub struct ParticleFilter<R, F> {
+                particles: Vec<World<R>>,
+                log_weights: Vec<f64>,
+                log_evidence: f64,
+                incorporated: Vec<bool>,
+                new_rng: F,
+                diagnostics: ferric::Diagnostics,
+            }
+
+            impl<R: rand::Rng, F: FnMut() -> R> ParticleFilter<R, F> {
+                fn new(
+                    num_particles: usize,
+                    mut new_rng: F,
+                    #(#input_idents: #input_type_idents,)*
+                ) -> ParticleFilter<R, F> {
+                    let particles = (0..num_particles)
+                        .map(|_| {
+                            let mut world = World::new(
+                                new_rng(),
+                                #(
+                                    #input_idents.clone(),
+                                )*
+                            );
+                            world.reset_for_weighted();
+                            world
+                        })
+                        .collect();
+                    ParticleFilter {
+                        particles,
+                        log_weights: vec![0.0; num_particles],
+                        log_evidence: 0.0,
+                        incorporated: vec![false; #num_observations],
+                        new_rng,
+                        diagnostics: ferric::Diagnostics::default(),
+                    }
+                }
+
+                /// Incorporate the next observation that has not been
+                /// incorporated yet, with the value given to the [`Model`].
+                ///
+                /// Returns `false`, without changing the filter, once every
+                /// observation has been incorporated.
+                pub fn step(&mut self) -> bool {
+                    match self.incorporated.iter().position(|&done| !done) {
+                        Some(index) => {
+                            self.incorporate(index);
+                            true
+                        }
+                        None => false,
+                    }
+                }
+
+                #( #smc_observe_methods )*
+
+                /// Multiply every particle's weight by the likelihood of the
+                /// `index`-th observation, then resample if the weights have
+                /// degenerated.
+                fn incorporate(&mut self, index: usize) {
+                    let log_total_before = ferric::log_sum_exp(&self.log_weights);
+                    for (particle, log_weight) in
+                        self.particles.iter_mut().zip(self.log_weights.iter_mut())
+                    {
+                        // a particle with zero weight keeps it
+                        if *log_weight == f64::NEG_INFINITY {
+                            continue;
+                        }
+                        match particle.observation_log_prob(index) {
+                            Ok(log_prob) => *log_weight += log_prob,
+                            Err(err) => {
+                                self.diagnostics.record(err);
+                                *log_weight = f64::NEG_INFINITY;
+                            }
+                        }
+                    }
+                    // log p(obs_k | obs_1..k-1) ≈ log Σ_i W_i p(obs_k | world_i)
+                    if log_total_before > f64::NEG_INFINITY {
+                        self.log_evidence +=
+                            ferric::log_sum_exp(&self.log_weights) - log_total_before;
+                    }
+                    self.incorporated[index] = true;
+                    let ess = self.effective_sample_size();
+                    if ess > 0.0 && ess < 0.5 * self.particles.len() as f64 {
+                        self.resample();
+                    }
+                }
+
+                /// Number of observations incorporated so far.
+                pub fn num_observed(&self) -> usize {
+                    self.incorporated.iter().filter(|&&done| done).count()
+                }
+
+                /// Running estimate of the log marginal likelihood of the
+                /// observations incorporated so far.
+                pub fn log_evidence(&self) -> f64 {
+                    self.log_evidence
+                }
+
+                /// Effective sample size of the current particle weights.
+                pub fn effective_sample_size(&self) -> f64 {
+                    ferric::inference::effective_sample_size(&self.log_weights)
+                }
+
+                /// The particles discarded so far because a distribution could
+                /// not be constructed.
+                pub fn diagnostics(&self) -> &ferric::Diagnostics {
+                    &self.diagnostics
+                }
+
+                /// The queried variables of every particle with its current
+                /// log weight.
+                ///
+                /// Latent variables that no incorporated observation depends on
+                /// are drawn from their priors.  Particles under which the
+                /// distribution of such a variable cannot be constructed have
+                /// zero weight and are left out.
+                pub fn samples(&mut self) -> Vec<WeightedSample> {
+                    let mut samples = Vec::with_capacity(self.particles.len());
+                    for (particle, &log_weight) in self.particles.iter_mut().zip(self.log_weights.iter()) {
+                        match particle.query() {
+                            Ok(sample) => samples.push(WeightedSample { log_weight, sample }),
+                            Err(err) => self.diagnostics.record(err),
+                        }
+                    }
+                    samples
+                }
+
+                /// Incorporate every remaining observation and return the
+                /// weighted particles with the log-evidence estimate.
+                fn finish(mut self) -> SmcResult {
+                    while self.step() {}
+                    SmcResult {
+                        samples: self.samples(),
+                        log_evidence: self.log_evidence,
+                        diagnostics: self.diagnostics,
+                    }
+                }
+
+                /// Replace the population by a systematic resample of it; every
+                /// copy receives a fresh random number generator.
+                fn resample(&mut self) {
+                    let ancestors = ferric::inference::systematic_resample(
+                        &self.log_weights,
+                        &mut self.particles[0].rng,
+                    );
+                    self.particles = ancestors
+                        .into_iter()
+                        .map(|a| self.particles[a].clone_with_rng((self.new_rng)()))
+                        .collect();
+                    // weights are only meaningful relative to each other
+                    self.log_weights.iter_mut().for_each(|lw| *lw = 0.0);
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let smc_methods = if !has_det_observed {
+        quote! {
+            /// Returns a particle filter with `num_particles` particles that
+            /// has not yet incorporated any observation.
+            ///
+            /// Call [`ParticleFilter::step`], or the `observe_<var>` method of
+            /// a newly arrived value, once per observation to update the
+            /// posterior incrementally.
+            pub fn particle_filter(
+                &self,
+                num_particles: usize,
+            ) -> ParticleFilter<rand::rngs::ThreadRng, fn() -> rand::rngs::ThreadRng> {
+                ParticleFilter::new(
+                    num_particles,
+                    rand::thread_rng as fn() -> rand::rngs::ThreadRng,
+                    #(
+                        self.#input_idents.clone(),
+                    )*
+                )
+            }
+
+            /// Like [`Model::particle_filter`], but every particle draws its
+            /// random numbers from a generator seeded from `rng`.
+            pub fn particle_filter_with_rng<R: rand::Rng + rand::SeedableRng>(
+                &self,
+                num_particles: usize,
+                mut rng: R,
+            ) -> ParticleFilter<R, impl FnMut() -> R> {
+                ParticleFilter::new(
+                    num_particles,
+                    move || R::from_rng(&mut rng).unwrap(),
+                    #(
+                        self.#input_idents.clone(),
+                    )*
+                )
+            }
+
+            /// Run sequential Monte Carlo with `num_particles` particles over
+            /// all observations and return the weighted particles together
+            /// with a log-evidence estimate.
+            ///
+            /// # When to use
+            ///
+            /// Valid for all models where every observed variable is
+            /// stochastic.  Most effective for time-indexed models with one
+            /// state variable per time step, whose observations are declared
+            /// in time order.
+            pub fn smc(&self, num_particles: usize) -> SmcResult {
+                self.particle_filter(num_particles).finish()
+            }
+
+            /// Like [`Model::smc`], but every particle draws its random
+            /// numbers from a generator seeded from `rng`.
+            pub fn smc_with_rng<R: rand::Rng + rand::SeedableRng>(
+                &self,
+                num_particles: usize,
+                rng: R,
+            ) -> SmcResult {
+                self.particle_filter_with_rng(num_particles, rng).finish()
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let smc_world_methods = if !has_det_observed {
+        quote! {
+            /// Log-likelihood of the `index`-th observation, in `observe`
+            /// statement order, under the current latent variables.
+            fn observation_log_prob(&mut self, index: usize) -> Result<f64, String> {
+                #observation_log_prob_body
+            }
+
+            /// A copy of this world's variables driven by `rng`.
+            fn clone_with_rng<R2>(&self, rng: R2) -> World<R2> {
+                World {
+                    rng,
+                    #(#var_idents: self.#var_idents.clone(), )*
+                    #(#input_field_idents: self.#input_field_idents.clone(), )*
+                    #pin_clone
+                    diagnostics: ferric::Diagnostics::default(),
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // A seeded view of the model forwards to the `_with_rng` sampler methods with a
+    // freshly seeded generator, so every call starts from the same random stream.
+    let seeded_weighted_methods = if !has_det_observed {
+        quote! {
+            /// Seeded [`Model::weighted_sample_iter`].
+            pub fn weighted_sample_iter(&self) -> WeightedWorld<rand::rngs::StdRng> {
+                self.model.weighted_sample_iter_with_rng(self.rng())
+            }
+
+            /// Seeded [`Model::par_weighted_sample`].
+            pub fn par_weighted_sample(
+                &self,
+                num_samples: usize,
+                num_threads: usize,
+            ) -> Vec<WeightedSample> {
+                self.model
+                    .par_weighted_sample_with_rng(num_samples, num_threads, self.rng())
+            }
+
+            /// Seeded [`Model::log_evidence`].
+            pub fn log_evidence(&self, num_samples: usize) -> ferric::LogEvidence {
+                self.model.log_evidence_with_rng(num_samples, self.rng())
+            }
+
+            /// Seeded [`Model::mh_sample_iter`].
+            pub fn mh_sample_iter(&self) -> MhWorld<rand::rngs::StdRng> {
+                self.model.mh_sample_iter_with_rng(self.rng())
+            }
+
+            /// Seeded [`Model::particle_filter`].
+            pub fn particle_filter(
+                &self,
+                num_particles: usize,
+            ) -> ParticleFilter<rand::rngs::StdRng, impl FnMut() -> rand::rngs::StdRng> {
+                self.model.particle_filter_with_rng(num_particles, self.rng())
+            }
+
+            /// Seeded [`Model::smc`].
+            pub fn smc(&self, num_particles: usize) -> SmcResult {
+                self.model.smc_with_rng(num_particles, self.rng())
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let seeded_hmc_methods = if has_gradient_support {
+        quote! {
+            /// Seeded [`Model::hmc_sample_iter`].
+            pub fn hmc_sample_iter<K: ferric::inference::Kernel>(
+                &self,
+                kernel: K,
+            ) -> HmcWorld<rand::rngs::StdRng, K> {
+                self.model.hmc_sample_iter_with_rng(kernel, self.rng())
+            }
+
+            /// Seeded [`Model::nuts_sample_iter`].
+            pub fn nuts_sample_iter(
+                &self,
+            ) -> HmcWorld<rand::rngs::StdRng, ferric::inference::Nuts> {
+                self.model.nuts_sample_iter_with_rng(self.rng())
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Exact enumeration visits the latent variables in dependency order, one generated
+    // method per latent, so that the distribution of each latent can be evaluated from
+    // the values already assigned to its parents.  The last method in the chain scores
+    // the observations and records the assignment.
+    let enum_latents: Vec<&VariableIR> = ir
+        .topological_order
+        .iter()
+        .map(|var_name| &ir.variables[var_name])
+        .filter(|variable| {
+            variable.is_stochastic && (!variable.is_observed || variable.is_optional)
+        })
+        .collect();
+    let enum_leaf_ident = format_ident!("enumerate_observed");
+    let enum_first_ident = match enum_latents.first() {
+        Some(variable) => format_ident!("enumerate_{}", &variable.var_ident),
+        None => enum_leaf_ident.clone(),
+    };
+    // Assignments are merged onto the distinct values of the queried variables when their
+    // types can be hashed.  Otherwise they are merged by the indices, within their
+    // supports, of the latent variables that the queried ones depend on, which determine
+    // the queried values.
+    let query_is_hashable = query_type_idents.iter().all(is_hashable);
+    let mut keyed_latents = HashSet::<String>::new();
+    if !query_is_hashable {
+        let mut pending: Vec<&Ident> = ir
+            .variables
+            .values()
+            .filter(|variable| variable.is_queried)
+            .map(|variable| &variable.var_ident)
+            .collect();
+        while let Some(var_ident) = pending.pop() {
+            if keyed_latents.insert(var_ident.to_string()) {
+                pending.extend(ir.variables[&var_ident.to_string()].parents.iter());
+            }
+        }
+    }
+    let (enumeration_key_type, enumeration_key, leaf_key_ident) = if query_is_hashable {
+        (
+            quote! {( #( #query_type_idents, )* )},
+            quote! {( #( sample.#query_idents.clone(), )* )},
+            format_ident!("_key"),
+        )
+    } else {
+        (
+            quote! {Vec<usize>},
+            quote! {key.clone()},
+            format_ident!("key"),
+        )
+    };
+    let mut enum_methods = Vec::<TokenStream>::new();
+    for (k, variable) in enum_latents.iter().enumerate() {
+        let next_ident = match enum_latents.get(k + 1) {
+            Some(next) => format_ident!("enumerate_{}", &next.var_ident),
+            None => enum_leaf_ident.clone(),
+        };
+        let keyed = keyed_latents.contains(&variable.var_ident.to_string());
+        enum_methods.push(enumerate_methods(variable, &next_ident, keyed));
+    }
+    let mut enum_det_obs_eval_idents = Vec::<Ident>::new(); // eval_<var>
+    let mut enum_det_obs_var_idents = Vec::<Ident>::new(); // var_<var>
+    let mut enum_det_obs_obs_idents = Vec::<Ident>::new(); // obs_<var>
+    for var_name in ir.observe_order.iter() {
+        let variable = &ir.variables[var_name];
+        if !variable.is_stochastic {
+            enum_det_obs_eval_idents.push(format_ident!("eval_{}", &variable.var_ident));
+            enum_det_obs_var_idents.push(format_ident!("var_{}", &variable.var_ident));
+            enum_det_obs_obs_idents.push(format_ident!("obs_{}", &variable.var_ident));
+        }
+    }
+
+    quote! {
+        pub mod #model_ident {
+            #(
+                #use_stmts;
+            )*
+
+            /// A sample returned by rejection sampling via [`Model::sample_iter`].
+            ///
+            /// Every observed variable matched its observed value exactly, so
+            /// all queried fields are drawn from the exact posterior.
+            pub struct Sample {
+                #(
+                    pub #query_idents: #query_type_idents,
+                )*
+            }
+
+            #weighted_structs
+
+            #mh_structs
+
+            #hmc_structs
+
+            #smc_structs
+
+            // what the assignments merged into one `ExactSample` have in common
+            type EnumerationKey = #enumeration_key_type;
+
+            /// One value of the queried variables found by
+            /// [`Model::enumerate`], together with its exact posterior
+            /// probability.
+            pub struct ExactSample {
+                pub probability: f64,
+                pub sample: Sample,
+            }
+
+            /// The exact posterior returned by [`Model::enumerate`].
+            pub struct Enumeration {
+                /// Every distinct value of the queried variables with non-zero
+                /// posterior probability, in the order in which they were
+                /// first found.  The probabilities of assignments that differ
+                /// only in variables that are not queried are summed into one
+                /// entry.  Values of a type such as `f64`, which cannot be
+                /// hashed, are told apart by the assignments of the latent
+                /// variables that they depend on.
+                pub samples: Vec<ExactSample>,
+                /// The exact log marginal likelihood of the observations.
+                pub log_evidence: f64,
+                /// The assignments skipped because a distribution could not be
+                /// constructed under them.
+                pub diagnostics: ferric::Diagnostics,
+            }
+
+            /// A [`Model`] whose samplers are driven by a seeded random
+            /// number generator, returned by [`Model::with_seed`].
+            ///
+            /// Each sampler method starts from the same seed, so calling it
+            /// again reproduces the same samples.
+            pub struct SeededModel<'a> {
+                model: &'a Model,
+                seed: u64,
+            }
+
+            impl SeededModel<'_> {
+                fn rng(&self) -> rand::rngs::StdRng {
+                    <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(self.seed)
+                }
+
+                /// Seeded [`Model::sample_iter`].
+                pub fn sample_iter(&self) -> World<rand::rngs::StdRng> {
+                    self.model.sample_iter_with_rng(self.rng())
+                }
+
+                /// Seeded [`Model::par_sample`].
+                pub fn par_sample(&self, num_samples: usize, num_threads: usize) -> Vec<Sample> {
+                    self.model.par_sample_with_rng(num_samples, num_threads, self.rng())
+                }
+
+                #seeded_weighted_methods
+
+                #seeded_hmc_methods
+            }
+
+            /// The observed data and the parameters of the model.
+            pub struct Model {
+                #(
+                    pub #input_idents: #input_type_idents,
+                )*
+            }
+
+            impl Model {
+                /// Returns an iterator of exact posterior samples via
+                /// rejection sampling.
+                ///
+                /// Each call to [`Iterator::next`] loops internally until a
+                /// prior sample is consistent with every observation, then
+                /// returns that sample.
+                ///
+                /// # When to use
+                ///
+                /// Only valid when **all** observed variables are discrete.
+                /// Conditioning on a continuous observed value has probability
+                /// zero and this iterator will loop forever.  Use
+                /// [`Model::weighted_sample_iter`] for models with continuous
+                /// observations.  A prior sample in which a `condition` is false
+                /// is rejected, and one that matches the observations and the
+                /// conditions is accepted with probability `exp(f)`, where `f` is the sum
+                /// of the `factor` terms, so every such sum must be
+                /// non-positive; a positive one discards the draw.
+                pub fn sample_iter(&self) -> World<rand::rngs::ThreadRng> {
+                    self.sample_iter_with_rng(rand::thread_rng())
+                }
+
+                /// Like [`Model::sample_iter`], but draws every random number
+                /// from `rng`.
+                pub fn sample_iter_with_rng<R: rand::Rng>(&self, rng: R) -> World<R> {
+                    World::new(
+                        rng,
+                        #(
+                            self.#input_idents.clone(),
+                        )*
+                    )
+                }
+
+                /// Draw `num_samples` exact posterior samples via rejection
+                /// sampling on `num_threads` threads, each running its own
+                /// world with an independently seeded random number stream.
+                ///
+                /// Equivalent to collecting `num_samples` items of
+                /// [`Model::sample_iter`], and subject to the same
+                /// restrictions; a thread whose sampler stops contributes
+                /// fewer.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `num_threads` is zero.
+                pub fn par_sample(&self, num_samples: usize, num_threads: usize) -> Vec<Sample> {
+                    self.par_sample_with_rng(num_samples, num_threads, rand::thread_rng())
+                }
+
+                /// Like [`Model::par_sample`], but seeds the stream of every
+                /// thread from `rng`.
+                pub fn par_sample_with_rng<R: rand::Rng>(
+                    &self,
+                    num_samples: usize,
+                    num_threads: usize,
+                    mut rng: R,
+                ) -> Vec<Sample> {
+                    ferric::inference::par_draw(num_samples, num_threads, &mut rng, |stream, count| {
+                        self.sample_iter_with_rng(stream).take(count).collect()
+                    })
+                }
+
+                /// Returns a view of this model whose samplers draw their
+                /// random numbers from a [`rand::rngs::StdRng`] seeded with
+                /// `seed`, so that every run can be reproduced exactly.
+                pub fn with_seed(&self, seed: u64) -> SeededModel<'_> {
+                    SeededModel { model: self, seed }
+                }
+
+                #weighted_sample_iter_method
+
+                #mh_sample_iter_method
+
+                #hmc_sample_iter_methods
+
+                #smc_methods
+
+                /// Compute the exact posterior by enumerating every joint
+                /// assignment of the latent variables.
+                ///
+                /// Each assignment is weighted by its log joint probability,
+                /// the weights are normalised and the probabilities of
+                /// assignments with the same queried values are summed, so no
+                /// randomness is involved.  The cost grows with the product of
+                /// the support sizes of the latent variables.
+                ///
+                /// # When to use
+                ///
+                /// Only valid when every latent variable is drawn from a
+                /// distribution with a finite support, such as `Bernoulli`,
+                /// `Categorical`, `Binomial` or `DiscreteUniform`.
+                ///
+                /// # Errors
+                ///
+                /// Returns `Err` if a latent variable is drawn from a
+                /// distribution whose support cannot be enumerated.
+                /// Assignments under which a distribution cannot be
+                /// constructed have probability zero and are only counted in
+                /// [`Enumeration::diagnostics`].
+                pub fn enumerate(&self) -> Result<Enumeration, String> {
+                    // every latent variable is assigned from its support
+                    // rather than drawn, so the world never uses its generator
+                    let mut world = World::new(
+                        <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(0),
+                        #(
+                            self.#input_idents.clone(),
+                        )*
+                    );
+                    world.reset_for_weighted();
+                    let mut weighted = Vec::new();
+                    world.#enum_first_ident(0.0, &mut Vec::new(), &mut weighted)?;
+                    let log_weights: Vec<f64> = weighted.iter().map(|(lw, _, _)| *lw).collect();
+                    let log_evidence = ferric::log_sum_exp(&log_weights);
+                    // marginalise the assignments that share a key, in the
+                    // order in which the first of them was found
+                    let mut samples: Vec<ExactSample> = Vec::new();
+                    let mut positions = std::collections::HashMap::<EnumerationKey, usize>::new();
+                    for (log_weight, key, sample) in weighted {
+                        let probability = (log_weight - log_evidence).exp();
+                        match positions.entry(key) {
+                            std::collections::hash_map::Entry::Occupied(entry) => {
+                                samples[*entry.get()].probability += probability;
+                            }
+                            std::collections::hash_map::Entry::Vacant(entry) => {
+                                entry.insert(samples.len());
+                                samples.push(ExactSample {
+                                    probability,
+                                    sample,
+                                });
+                            }
+                        }
+                    }
+                    Ok(Enumeration {
+                        samples,
+                        log_evidence,
+                        diagnostics: world.diagnostics,
+                    })
+                }
+            }
+
+            pub struct World<R> {
+                rng: R,
+                #(#var_idents: FeOption<#var_type_idents>, )*
+                #(#input_field_idents: #input_type_idents, )*
+                #pin_field
+                diagnostics: ferric::Diagnostics,
+            }
+
+            impl<R: rand::Rng> Iterator for World<R> {
+                type Item = Sample;
+
+                fn next(&mut self) -> Option<Self::Item> {
+                    self.sample()
+                }
+            }
+
+            impl<R: rand::Rng> World<R> {
+                pub fn new(rng: R, #(#input_idents: #input_type_idents,)*) -> World<R> {
+                    World {
+                        rng: rng,
+                        #(#var_idents: FeOption::Unknown, )*
+                        #(#input_field_idents: #input_idents, )*
+                        #pin_new
+                        diagnostics: ferric::Diagnostics::default(),
+                    }
+                }
+
+                /// The draws this world discarded because the parameters of a
+                /// distribution were invalid, with the first error messages.
+                pub fn diagnostics(&self) -> &ferric::Diagnostics {
+                    &self.diagnostics
+                }
+
+                pub fn reset(&mut self) {
+                    #(
+                        self.#var_idents = FeOption::Unknown;
+                    )*
+                    #pin_off
+                }
+
+                /// Like `reset`, but pins every stochastic observed variable to its
+                /// known value and resets everything else (including deterministic
+                /// variables) to Unknown so they are re-evaluated from the freshly
+                /// sampled stochastic latents.  Variables observed with missing
+                /// values pin their known values when they are evaluated.
+                ///
+                /// Used by importance sampling.
+                fn reset_for_weighted(&mut self) {
+                    #(
+                        self.#reset_for_weighted_var_idents = FeOption::Unknown;
+                    )*
+                    #(
+                        self.#obs_stoch_var_idents = FeOption::Known(self.#obs_stoch_obs_idents.clone());
+                    )*
+                    #pin_on
+                }
+
+                /// Draw one exact posterior sample via rejection sampling.
+                ///
+                /// Loops until a prior draw matches every observed value, then
+                /// returns the queried variable values.  Only valid for
+                /// discrete observations.  Draws under which a distribution
+                /// cannot be constructed are rejected and counted in
+                /// [`World::diagnostics`].
+                ///
+                /// Returns `None`, and stops for good, once
+                /// [`ferric::Diagnostics::MAX_CONSECUTIVE_INVALID`] draws in a
+                /// row cannot be constructed.
+                pub fn sample(&mut self) -> Option<Sample> {
+                    let mut num_invalid = 0;
+                    while !self.diagnostics.stopped() {
+                        self.reset();
+                        match self.try_sample() {
+                            Ok(Some(sample)) => return Some(sample),
+                            // a valid draw that does not match the observations
+                            Ok(None) => num_invalid = 0,
+                            Err(err) => {
+                                self.diagnostics.record(err);
+                                num_invalid += 1;
+                                self.diagnostics.gives_up(num_invalid);
+                            }
+                        }
+                    }
+                    None
+                }
+
+                // the queried variables of a prior draw, or `None` if it does not match the
+                // observed values
+                fn try_sample(&mut self) -> Result<Option<Sample>, String> {
+                    #(
+                        {
+                            self.#obs_eval_idents()?;
+                            let sampled = self.#obs_var_idents.unwrap_ref();
+                            if #obs_mismatches {
+                                return Ok(None);
+                            }
+                        }
+                    )*
+                    #reject_by_conditions
+                    #reject_by_log_factor
+                    self.query().map(Some)
+                }
+
+                // the queried variables of the current state
+                fn query(&mut self) -> Result<Sample, String> {
+                    Ok(Sample {
+                        #(
+                            #query_idents: self.#query_eval_var_idents()?.clone(),
+                        )*
+                    })
+                }
+
+                #weighted_sample_method
+
+                #mh_world_methods
+
+                #hmc_world_methods
+
+                #smc_world_methods
+
+                /// Resets every deterministic variable to Unknown so that it is
+                /// re-evaluated from the current stochastic variables.
+                fn reset_deterministic(&mut self) {
+                    #(
+                        self.#det_var_idents = FeOption::Unknown;
+                    )*
+                }
+
+                // --- Exact enumeration, one method per latent variable ---
+
+                #(#enum_methods)*
+
+                // the key is threaded as a `Vec` so that the methods above can
+                // push onto it, even where this one only reads it
+                #[allow(clippy::ptr_arg)]
+                fn #enum_leaf_ident(
+                    &mut self,
+                    log_weight: f64,
+                    #leaf_key_ident: &mut Vec<usize>,
+                    weighted: &mut Vec<(f64, EnumerationKey, Sample)>,
+                ) -> Result<(), String> {
+                    match self.enumerate_score(log_weight) {
+                        Ok(Some((log_weight, sample))) => {
+                            weighted.push((log_weight, #enumeration_key, sample))
+                        }
+                        Ok(None) => {}
+                        Err(err) => self.diagnostics.record(err),
+                    }
+                    Ok(())
+                }
+
+                // the log weight and queried variables of a complete assignment, or `None` if
+                // the assignment has probability zero
+                fn enumerate_score(
+                    &mut self,
+                    mut log_weight: f64,
+                ) -> Result<Option<(f64, Sample)>, String> {
+                    #(
+                        log_weight += #obs_stoch_log_probs;
+                    )*
+                    #add_log_factor_to_weight
+                    #reject_by_conditions
+                    #(
+                        {
+                            self.#enum_det_obs_eval_idents()?;
+                            if *self.#enum_det_obs_var_idents.unwrap_ref() != self.#enum_det_obs_obs_idents {
+                                return Ok(None);
+                            }
+                        }
+                    )*
+                    if log_weight == f64::NEG_INFINITY {
+                        return Ok(None);
+                    }
+                    Ok(Some((log_weight, self.query()?)))
+                }
+
+                #log_factor_method
+
+                #conditions_hold_method
+
+                // --- Stochastic variable methods ---
+
+                #(#stoch_methods)*
+
+                // --- Deterministic variable methods (no distribution, no sampling) ---
+
+                #(#det_methods)*
+
+                // --- Parameter methods ---
+
+                #(#param_methods)*
+            }
+
+            #hmc_log_density_impl
+        }
+    }
+}
+
+// the type of a variable's value: `Vec<T>` for a plate of `T`s
+fn value_type(variable: &VariableIR) -> Type {
+    let type_ident = &variable.type_ident;
+    match variable.plate {
+        Some(_) => parse_quote!(Vec<#type_ident>),
+        None => type_ident.clone(),
+    }
+}
+
+// the type of an observed value: each value observed with missing values is a
+// `FeOption<T>`, so a plate of them is a `Vec<FeOption<T>>`
+fn observed_type(variable: &VariableIR) -> Type {
+    let type_ident = &variable.type_ident;
+    match (variable.is_optional, &variable.plate) {
+        (false, _) => value_type(variable),
+        (true, Some(_)) => parse_quote!(Vec<FeOption<#type_ident>>),
+        (true, None) => parse_quote!(FeOption<#type_ident>),
+    }
+}
+
+// `ParticleFilter::observe_<var>`, which sets the observed value of `variable` in every
+// particle and incorporates it as the `index`-th observation
+fn smc_observe_method(variable: &VariableIR, index: usize) -> TokenStream {
+    let var_name = &variable.var_ident;
+    let method = format_ident!("observe_{}", var_name);
+    let obs_var = format_ident!("obs_{}", var_name);
+    let var_ident = format_ident!("var_{}", var_name);
+    let obs_type = observed_type(variable);
+    // a fully observed variable is pinned by `reset_for_weighted`, a partially
+    // observed one by `eval_<var>`
+    let pin = if variable.is_optional {
+        quote! {}
+    } else {
+        quote! { particle.#var_ident = FeOption::Known(value.clone()); }
+    };
+    quote! {
+        /// Incorporate `value` as the observed value of this variable.
+        ///
+        /// Latent variables drawn before this call saw the value given to
+        /// the [`Model`] instead, so call it before incorporating any other
+        /// observation that depends on this variable.  Returns `false`,
+        /// without changing the filter, if the variable has already been
+        /// incorporated.
+        pub fn #method(&mut self, value: #obs_type) -> bool {
+            if self.incorporated[#index] {
+                return false;
+            }
+            for particle in self.particles.iter_mut() {
+                #pin
+                particle.#obs_var = value.clone();
+            }
+            self.incorporate(#index);
+            true
+        }
+    }
+}
+
+// an expression that is `true` if the value `sampled` by rejection sampling differs from
+// a known observed value of the variable
+fn observed_mismatch_tokens(variable: &VariableIR) -> TokenStream {
+    let obs_var = format_ident!("obs_{}", &variable.var_ident);
+    match (variable.is_optional, &variable.plate) {
+        (false, _) => quote! { self.#obs_var != *sampled },
+        (true, Some(_)) => quote! {
+            self.#obs_var.len() != sampled.len()
+                || self.#obs_var.iter().zip(sampled.iter()).any(|(observed, value)| {
+                    matches!(observed, FeOption::Known(observed) if observed != value)
+                })
+        },
+        (true, None) => quote! {
+            matches!(&self.#obs_var, FeOption::Known(observed) if observed != sampled)
+        },
+    }
+}
+
+// the log probability of the known observed values of a stochastic variable given its
+// parents, or `None` for a deterministic variable; missing values contribute nothing
+fn observed_log_prob_tokens(variable: &VariableIR) -> Option<TokenStream> {
+    let obs_var = format_ident!("obs_{}", &variable.var_ident);
+    if !variable.is_stochastic {
+        return None;
+    }
+    if !variable.is_optional {
+        return Some(log_prob_tokens(variable, quote! {self.#obs_var}));
+    }
+    let eval_dist_var = format_ident!("evaldist_{}", &variable.var_ident);
+    Some(match variable.plate {
+        None => quote! {
+            {
+                let dist = self.#eval_dist_var()?;
+                match &self.#obs_var {
+                    FeOption::Known(value) => dist.log_prob(value),
+                    _ => 0.0,
+                }
+            }
+        },
+        Some(_) => {
+            let indices_var = format_ident!("indices_{}", &variable.var_ident);
+            quote! {
+                {
+                    let indices = self.#indices_var()?;
+                    if indices.len() != self.#obs_var.len() {
+                        f64::NEG_INFINITY
+                    } else {
+                        let mut log_prob = 0.0f64;
+                        for (k, i) in indices.into_iter().enumerate() {
+                            let dist = self.#eval_dist_var(i)?;
+                            if let FeOption::Known(value) = &self.#obs_var[k] {
+                                log_prob += dist.log_prob(value);
+                            }
+                        }
+                        log_prob
+                    }
+                }
+            }
+        }
+    })
+}
+
+// `eval_<var>` and `evaldist_<var>` for a stochastic variable; a plate also gets
+// `indices_<var>`, and its `evaldist_<var>` takes the index of an element.  The methods
+// fail with a message naming the variable when a distribution cannot be constructed.  A
+// variable observed with missing values takes its known observed values while they are
+// pinned and samples the rest.
+fn stochastic_methods(variable: &VariableIR, names: &Names) -> TokenStream {
+    let var_ident = format_ident!("var_{}", &variable.var_ident);
+    let eval_var = format_ident!("eval_{}", &variable.var_ident);
+    let eval_dist_var = format_ident!("evaldist_{}", &variable.var_ident);
+    let obs_var = format_ident!("obs_{}", &variable.var_ident);
+    let var_name = variable.var_ident.to_string();
+    let type_ident = &variable.type_ident;
+    let dep = &variable.dependency;
+    let dist_expr = dependency(quote! {#dep}, names);
+    match &variable.plate {
+        None => {
+            let sample_value = if variable.is_optional {
+                quote! {
+                    let value = match &self.#obs_var {
+                        FeOption::Known(value) if self.pin_observed => value.clone(),
+                        _ => {
+                            let dist = self.#eval_dist_var()?;
+                            dist.sample(&mut self.rng)
+                        }
+                    };
+                    self.#var_ident = FeOption::Known(value);
+                }
+            } else {
+                quote! {
+                    let dist = self.#eval_dist_var()?;
+                    self.#var_ident = FeOption::Known(dist.sample(&mut self.rng));
+                }
+            };
+            quote! {
+            pub fn #eval_var(&mut self) -> Result<&#type_ident, String> {
+                if self.#var_ident.is_unknown() {
+                    #sample_value
+                }
+                // TODO: handle the case when the value is Null
+                Ok(self.#var_ident.unwrap_ref())
+            }
+
+            pub fn #eval_dist_var(&mut self) -> Result<impl ferric::distributions::Distribution<R, Domain=#type_ident> + Send + 'static, String> {
+                let dist = (#dist_expr).map_err(|err| {
+                    format!("invalid distribution of `{}`: {}", #var_name, err)
+                })?;
+                Ok(dist)
+            }
+            }
+        }
+        Some(plate) => {
+            let indices_var = format_ident!("indices_{}", &variable.var_ident);
+            let index_ident = &plate.index_ident;
+            let range = &plate.range;
+            let range_expr = dependency(quote! {#range}, names);
+            let sample_values = if variable.is_optional {
+                quote! {
+                    for (k, i) in indices.into_iter().enumerate() {
+                        let value = match self.#obs_var.get(k) {
+                            Some(FeOption::Known(value)) if self.pin_observed => value.clone(),
+                            _ => {
+                                let dist = self.#eval_dist_var(i)?;
+                                dist.sample(&mut self.rng)
+                            }
+                        };
+                        values.push(value);
+                    }
+                }
+            } else {
+                quote! {
+                    for i in indices {
+                        let dist = self.#eval_dist_var(i)?;
+                        values.push(dist.sample(&mut self.rng));
+                    }
+                }
+            };
+            quote! {
+                pub fn #eval_var(&mut self) -> Result<&Vec<#type_ident>, String> {
+                    if self.#var_ident.is_unknown() {
+                        let indices = self.#indices_var()?;
+                        let mut values = Vec::with_capacity(indices.len());
+                        #sample_values
+                        self.#var_ident = FeOption::Known(values);
+                    }
+                    Ok(self.#var_ident.unwrap_ref())
+                }
+
+                pub fn #eval_dist_var(&mut self, #index_ident: usize) -> Result<impl ferric::distributions::Distribution<R, Domain=#type_ident> + Send + 'static, String> {
+                    let dist = (#dist_expr).map_err(|err| {
+                        format!("invalid distribution of `{}[{}]`: {}", #var_name, #index_ident, err)
+                    })?;
+                    Ok(dist)
+                }
+
+                /// The indices of the elements of the plate, in order.
+                fn #indices_var(&mut self) -> Result<Vec<usize>, String> {
+                    Ok((#range_expr).into_iter().collect())
+                }
+            }
+        }
+    }
+}
+
+// `eval_<var>` for a deterministic variable; a plate also gets `indices_<var>`
+fn deterministic_methods(variable: &VariableIR, names: &Names) -> TokenStream {
+    let var_ident = format_ident!("var_{}", &variable.var_ident);
+    let eval_var = format_ident!("eval_{}", &variable.var_ident);
+    let type_ident = &variable.type_ident;
+    let dep = &variable.dependency;
+    let val_expr = dependency(quote! {#dep}, names);
+    match &variable.plate {
+        None => quote! {
+            pub fn #eval_var(&mut self) -> Result<&#type_ident, String> {
+                if self.#var_ident.is_unknown() {
+                    let val = #val_expr;
+                    self.#var_ident = FeOption::Known(val);
+                }
+                Ok(self.#var_ident.unwrap_ref())
+            }
+        },
+        Some(plate) => {
+            let indices_var = format_ident!("indices_{}", &variable.var_ident);
+            let index_ident = &plate.index_ident;
+            let range = &plate.range;
+            let range_expr = dependency(quote! {#range}, names);
+            quote! {
+                #[allow(unused_variables)]
+                pub fn #eval_var(&mut self) -> Result<&Vec<#type_ident>, String> {
+                    if self.#var_ident.is_unknown() {
+                        let indices = self.#indices_var()?;
+                        let mut values = Vec::with_capacity(indices.len());
+                        for #index_ident in indices {
+                            values.push(#val_expr);
+                        }
+                        self.#var_ident = FeOption::Known(values);
+                    }
+                    Ok(self.#var_ident.unwrap_ref())
+                }
+
+                /// The indices of the elements of the plate, in order.
+                fn #indices_var(&mut self) -> Result<Vec<usize>, String> {
+                    Ok((#range_expr).into_iter().collect())
+                }
+            }
+        }
+    }
+}
+
+// an expression for the log probability of `value` under the distribution of a stochastic
+// variable given its parents, which propagates a failure to construct the distribution
+// with `?`; a plate value whose length does not match its indices is impossible.  The
+// elements of a plate whose distribution does not depend on the index are i.i.d., so
+// they are scored in one `log_prob_batch` call.
+fn log_prob_tokens(variable: &VariableIR, value: TokenStream) -> TokenStream {
+    let eval_dist_var = format_ident!("evaldist_{}", &variable.var_ident);
+    match &variable.plate {
+        None => quote! {
+            {
+                let dist = self.#eval_dist_var()?;
+                dist.log_prob(&#value)
+            }
+        },
+        Some(plate) => {
+            let indices_var = format_ident!("indices_{}", &variable.var_ident);
+            let dep = &variable.dependency;
+            if !mentions(quote! {#dep}, &plate.index_ident) {
+                return quote! {
+                    {
+                        let indices = self.#indices_var()?;
+                        if indices.len() != #value.len() {
+                            f64::NEG_INFINITY
+                        } else if let Some(&i) = indices.first() {
+                            let dist = self.#eval_dist_var(i)?;
+                            dist.log_prob_batch(&#value)
+                        } else {
+                            0.0
+                        }
+                    }
+                };
+            }
+            quote! {
+                {
+                    let indices = self.#indices_var()?;
+                    if indices.len() != #value.len() {
+                        f64::NEG_INFINITY
+                    } else {
+                        let mut log_prob = 0.0f64;
+                        for (k, i) in indices.into_iter().enumerate() {
+                            let dist = self.#eval_dist_var(i)?;
+                            log_prob += dist.log_prob(&#value[k]);
+                        }
+                        log_prob
+                    }
+                }
+            }
+        }
+    }
+}
+
+// a Metropolis-Hastings proposal of a latent variable from its prior: sets the new value
+// and evaluates to `Some((log_q_old, log_q_new))`, or to `None` for an empty plate, and
+// propagates a failure to construct a distribution with `?`; a plate proposes a new value
+// for one element chosen uniformly at random.  A variable observed with missing values
+// only proposes missing values, and evaluates to `None` if every value is known.
+fn prior_proposal(variable: &VariableIR) -> TokenStream {
+    let var_ident = format_ident!("var_{}", &variable.var_ident);
+    let eval_var = format_ident!("eval_{}", &variable.var_ident);
+    let eval_dist_var = format_ident!("evaldist_{}", &variable.var_ident);
+    let obs_var = format_ident!("obs_{}", &variable.var_ident);
+    match variable.plate {
+        None => {
+            let proposal = quote! {
+                {
+                    self.#eval_var()?;
+                    let dist = self.#eval_dist_var()?;
+                    let new_value = dist.sample(&mut self.rng);
+                    let log_q_old = dist.log_prob(self.#var_ident.unwrap_ref());
+                    let log_q_new = dist.log_prob(&new_value);
+                    self.#var_ident = FeOption::Known(new_value);
+                    Some((log_q_old, log_q_new))
+                }
+            };
+            if variable.is_optional {
+                quote! {
+                    if self.#obs_var.is_known() {
+                        None
+                    } else {
+                        #proposal
+                    }
+                }
+            } else {
+                proposal
+            }
+        }
+        Some(_) => {
+            let indices_var = format_ident!("indices_{}", &variable.var_ident);
+            // the position `k` of the element to change, or `None` if no element may change
+            let site = if variable.is_optional {
+                quote! {
+                    {
+                        let missing: Vec<usize> = (0..len)
+                            .filter(|&k| !matches!(self.#obs_var.get(k), Some(FeOption::Known(_))))
+                            .collect();
+                        if missing.is_empty() {
+                            None
+                        } else {
+                            Some(missing[self.rng.gen_range(0..missing.len())])
+                        }
+                    }
+                }
+            } else {
+                quote! {
+                    if len == 0 {
+                        None
+                    } else {
+                        Some(self.rng.gen_range(0..len))
+                    }
+                }
+            };
+            quote! {
+                {
+                    let indices = self.#indices_var()?;
+                    let len = self.#eval_var()?.len();
+                    let site: Option<usize> = if len != indices.len() {
+                        None
+                    } else {
+                        #site
+                    };
+                    if let Some(k) = site {
+                        let dist = self.#eval_dist_var(indices[k])?;
+                        let new_value = dist.sample(&mut self.rng);
+                        let log_q_old = dist.log_prob(&self.#var_ident.unwrap_ref()[k]);
+                        let log_q_new = dist.log_prob(&new_value);
+                        if let FeOption::Known(values) = &mut self.#var_ident {
+                            values[k] = new_value;
+                        }
+                        Some((log_q_old, log_q_new))
+                    } else {
+                        None
+                    }
+                }
+            }
+        }
+    }
+}
+
+// the exact-enumeration method `enumerate_<var>` of a latent variable, which tries every
+// value in its finite support and continues with `next_ident`; a plate enumerates the
+// Cartesian product of the supports of its elements one element at a time.  A partial
+// assignment under which a distribution cannot be constructed has probability zero and is
+// skipped.  If `keyed`, the index of each value in its support is pushed onto `key` while
+// the assignment is extended.
+fn enumerate_methods(variable: &VariableIR, next_ident: &Ident, keyed: bool) -> TokenStream {
+    let var_ident = format_ident!("var_{}", &variable.var_ident);
+    let (each_value, push_key, pop_key) = if keyed {
+        (
+            quote! {(index, value) in support.into_iter().enumerate()},
+            quote! {key.push(index);},
+            quote! {key.pop();},
+        )
+    } else {
+        (quote! {value in support}, quote! {}, quote! {})
+    };
+    let eval_dist_var = format_ident!("evaldist_{}", &variable.var_ident);
+    let method_ident = format_ident!("enumerate_{}", &variable.var_ident);
+    let obs_var = format_ident!("obs_{}", &variable.var_ident);
+    let var_name = variable.var_ident.to_string();
+    match variable.plate {
+        None => {
+            // a known observed value is scored with the observations
+            let skip_known = if variable.is_optional {
+                quote! {
+                    if self.#obs_var.is_known() {
+                        return self.#next_ident(log_weight, key, weighted);
+                    }
+                }
+            } else {
+                quote! {}
+            };
+            quote! {
+            fn #method_ident(
+                &mut self,
+                log_weight: f64,
+                key: &mut Vec<usize>,
+                weighted: &mut Vec<(f64, EnumerationKey, Sample)>,
+            ) -> Result<(), String> {
+                #skip_known
+                let dist = match self.#eval_dist_var() {
+                    Ok(dist) => dist,
+                    Err(err) => {
+                        self.diagnostics.record(err);
+                        return Ok(());
+                    }
+                };
+                let support = match dist.finite_support() {
+                    Some(support) => support,
+                    None => {
+                        return Err(format!(
+                            "enumerate: latent variable `{}` does not have a finite support",
+                            #var_name
+                        ));
+                    }
+                };
+                for #each_value {
+                    let log_prob = dist.log_prob(&value);
+                    if log_prob == f64::NEG_INFINITY {
+                        continue;
+                    }
+                    self.#var_ident = FeOption::Known(value);
+                    self.reset_deterministic();
+                    #push_key
+                    self.#next_ident(log_weight + log_prob, key, weighted)?;
+                    #pop_key
+                }
+                Ok(())
+            }
+            }
+        }
+        Some(_) => {
+            let indices_var = format_ident!("indices_{}", &variable.var_ident);
+            let elements_ident = format_ident!("enumerate_{}_elements", &variable.var_ident);
+            let type_ident = &variable.type_ident;
+            let skip_known = if variable.is_optional {
+                quote! {
+                    if let Some(FeOption::Known(value)) = self.#obs_var.get(values.len()) {
+                        let mut extended = values.clone();
+                        extended.push(value.clone());
+                        return self.#elements_ident(indices, extended, log_weight, key, weighted);
+                    }
+                }
+            } else {
+                quote! {}
+            };
+            quote! {
+                fn #method_ident(
+                    &mut self,
+                    log_weight: f64,
+                    key: &mut Vec<usize>,
+                    weighted: &mut Vec<(f64, EnumerationKey, Sample)>,
+                ) -> Result<(), String> {
+                    let indices = match self.#indices_var() {
+                        Ok(indices) => indices,
+                        Err(err) => {
+                            self.diagnostics.record(err);
+                            return Ok(());
+                        }
+                    };
+                    self.#elements_ident(&indices, Vec::new(), log_weight, key, weighted)
+                }
+
+                // `values` holds the elements assigned so far
+                fn #elements_ident(
+                    &mut self,
+                    indices: &[usize],
+                    values: Vec<#type_ident>,
+                    log_weight: f64,
+                    key: &mut Vec<usize>,
+                    weighted: &mut Vec<(f64, EnumerationKey, Sample)>,
+                ) -> Result<(), String> {
+                    if values.len() == indices.len() {
+                        self.#var_ident = FeOption::Known(values);
+                        self.reset_deterministic();
+                        return self.#next_ident(log_weight, key, weighted);
+                    }
+                    #skip_known
+                    let dist = match self.#eval_dist_var(indices[values.len()]) {
+                        Ok(dist) => dist,
+                        Err(err) => {
+                            self.diagnostics.record(err);
+                            return Ok(());
+                        }
+                    };
+                    let support = match dist.finite_support() {
+                        Some(support) => support,
+                        None => {
+                            return Err(format!(
+                                "enumerate: latent variable `{}` does not have a finite support",
+                                #var_name
+                            ));
+                        }
+                    };
+                    for #each_value {
+                        let log_prob = dist.log_prob(&value);
+                        if log_prob == f64::NEG_INFINITY {
+                            continue;
+                        }
+                        let mut extended = values.clone();
+                        extended.push(value);
+                        #push_key
+                        self.#elements_ident(indices, extended, log_weight + log_prob, key, weighted)?;
+                        #pop_key
+                    }
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+// whether `ident` occurs anywhere in `tokens`
+fn mentions(tokens: TokenStream, ident: &Ident) -> bool {
+    tokens.into_iter().any(|tt| match tt {
+        TokenTree::Ident(i) => i == *ident,
+        TokenTree::Group(g) => mentions(g.stream(), ident),
+        _ => false,
+    })
+}
+
+// the names that dependency expressions may reference
+struct Names {
+    variables: HashSet<String>,
+    params: HashSet<String>,
+}
+
+// a dependency expression that first evaluates every variable it references with
+// `eval_<name>`, so that the expression can then read their values in place
+fn dependency(dep_tokens: TokenStream, names: &Names) -> TokenStream {
+    let mut referenced = Vec::new();
+    let expr = replace(dep_tokens, names, &mut referenced);
+    let eval_vars = referenced.iter().map(|name| format_ident!("eval_{}", name));
+    quote! {
+        {
+            #( self.#eval_vars()?; )*
+            #expr
+        }
+    }
+}
+
+// whether an occurrence of a variable between `prev` and `next` only needs a reference to
+// its value: it is borrowed with `&`, or a method, field or index of it is taken
+fn is_borrowed(prev: Option<&TokenTree>, next: Option<&TokenTree>) -> bool {
+    matches!(prev, Some(TokenTree::Punct(p)) if p.as_char() == '&')
+        || matches!(next, Some(TokenTree::Punct(p)) if p.as_char() == '.')
+        || matches!(next, Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Bracket)
+}
+
+// replace all occurrences of a variable `name` in the dependency expression with its value
+// and of a parameter `name` with `self.param_name`, and collect the referenced variables in
+// `referenced`; a borrowed variable is read in place as `*self.var_name.unwrap_ref()`, and
+// one that may be moved is cloned, so that a vector or matrix is only copied if it is used
+// by value
+fn replace(dep_tokens: TokenStream, names: &Names, referenced: &mut Vec<String>) -> TokenStream {
+    let tokens: Vec<TokenTree> = dep_tokens.into_iter().collect();
+    let mut output = Vec::with_capacity(tokens.len());
+    for (idx, tt) in tokens.iter().enumerate() {
+        let replaced = match tt {
+            TokenTree::Ident(i) => {
+                let i_name = i.to_string();
+                let stream = if names.variables.contains(&i_name) {
+                    let var_ident = Ident::new(&format!("var_{}", i_name), i.span());
+                    if !referenced.contains(&i_name) {
+                        referenced.push(i_name);
+                    }
+                    let prev = idx.checked_sub(1).map(|prev| &tokens[prev]);
+                    if is_borrowed(prev, tokens.get(idx + 1)) {
+                        quote_spanned! {i.span()=> (*self.#var_ident.unwrap_ref())}
+                    } else {
+                        quote_spanned! {i.span()=>
+                            ::core::clone::Clone::clone(self.#var_ident.unwrap_ref())
+                        }
+                    }
+                } else if names.params.contains(&i_name) {
+                    let param_ident = Ident::new(&format!("param_{}", i_name), i.span());
+                    quote_spanned! {i.span()=> self.#param_ident}
+                } else {
+                    output.push(tt.clone());
+                    continue;
+                };
+                let mut group = Group::new(Delimiter::None, stream);
+                group.set_span(i.span());
+                TokenTree::Group(group)
+            }
+            TokenTree::Group(g) => {
+                let stream = replace(g.stream(), names, referenced);
+                let mut group = Group::new(g.delimiter(), stream);
                 group.set_span(g.span());
                 TokenTree::Group(group)
             }
-            other => other,
-        })
-        .collect()
+            other => other.clone(),
+        };
+        output.push(replaced);
+    }
+    output.into_iter().collect()
+}
+
+// whether values of type `ty` are known to implement `Clone`, `Eq` and `Hash`: `bool`,
+// `char`, the integer types and `String`, and `Vec`s, `Option`s, arrays and tuples of them
+fn is_hashable(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => {
+            let Some(segment) = type_path.path.segments.last() else {
+                return false;
+            };
+            let name = segment.ident.to_string();
+            match &segment.arguments {
+                PathArguments::None => matches!(
+                    name.as_str(),
+                    "bool"
+                        | "char"
+                        | "u8"
+                        | "u16"
+                        | "u32"
+                        | "u64"
+                        | "u128"
+                        | "usize"
+                        | "i8"
+                        | "i16"
+                        | "i32"
+                        | "i64"
+                        | "i128"
+                        | "isize"
+                        | "String"
+                ),
+                PathArguments::AngleBracketed(args) if name == "Vec" || name == "Option" => {
+                    args.args.len() == 1
+                        && matches!(&args.args[0], GenericArgument::Type(arg) if is_hashable(arg))
+                }
+                _ => false,
+            }
+        }
+        Type::Tuple(tuple) => tuple.elems.iter().all(is_hashable),
+        Type::Array(array) => is_hashable(&array.elem),
+        Type::Paren(paren) => is_hashable(&paren.elem),
+        Type::Group(group) => is_hashable(&group.elem),
+        _ => false,
+    }
+}
+
+// `Some(false)` for `f64`, `Some(true)` for `DVector<f64>`, and `None` for any other type
+fn real_type_is_vector(ty: &Type) -> Option<bool> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident == "f64" && segment.arguments.is_empty() {
+        return Some(false);
+    }
+    if segment.ident != "DVector" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first() {
+        Some(GenericArgument::Type(inner)) if args.args.len() == 1 => {
+            match real_type_is_vector(inner) {
+                Some(false) => Some(true),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
 }
 
 #[test]
 fn output_is_module_item() {
     use proc_macro2::Span;
+    use std::collections::HashMap;
     use syn::{ItemMod, parse_quote, parse2};
     let ir = ModelIR {
         model_ident: Ident::new(&String::from("grass"), Span::call_site()),
@@ -427,17 +2695,22 @@ fn output_is_module_item() {
                 String::from("rain"),
                 VariableIR {
                     var_ident: Ident::new(&String::from("rain"), Span::call_site()),
+                    plate: None,
                     type_ident: parse_quote!(bool),
                     dependency: parse_quote!(Bernoulli::new(0.2)),
                     is_stochastic: true,
                     is_queried: true,
                     is_observed: false,
+                    is_optional: false,
+                    parents: vec![],
+                    children: vec![Ident::new(&String::from("sprinkler"), Span::call_site())],
                 },
             ),
             (
                 String::from("sprinkler"),
                 VariableIR {
                     var_ident: Ident::new(&String::from("sprinkler"), Span::call_site()),
+                    plate: None,
                     type_ident: parse_quote!(bool),
                     dependency: parse_quote!(if rain {
                         Bernoulli::new(0.01)
@@ -447,18 +2720,38 @@ fn output_is_module_item() {
                     is_stochastic: true,
                     is_queried: false,
                     is_observed: true,
+                    is_optional: false,
+                    parents: vec![Ident::new(&String::from("rain"), Span::call_site())],
+                    children: vec![],
                 },
             ),
         ]),
+        params: Vec::new(),
+        observe_order: vec![String::from("sprinkler")],
+        topological_order: vec![String::from("rain"), String::from("sprinkler")],
+        factors: vec![],
+        conditions: vec![],
     };
     let rust = codegen(ir);
 
+    let rust_str = rust.to_string();
+    assert!(rust_str.contains("fn mh_step"));
+    assert!(rust_str.contains("fn particle_filter"));
+    assert!(rust_str.contains("fn observe_sprinkler"));
+    assert!(rust_str.contains("fn enumerate_rain"));
+    assert!(rust_str.contains("fn log_evidence"));
+    assert!(rust_str.contains("fn with_seed"));
+    assert!(rust_str.contains("fn weighted_sample_iter_with_rng"));
+    assert!(rust_str.contains("fn par_weighted_sample"));
+    // boolean latents cannot be explored by gradient-based samplers
+    assert!(!rust_str.contains("fn nuts_sample_iter"));
     assert!(parse2::<ItemMod>(rust).is_ok());
 }
 
 #[test]
 fn output_with_deterministic_var_is_module_item() {
     use proc_macro2::Span;
+    use std::collections::HashMap;
     use syn::{ItemMod, parse_quote, parse2};
     let ir = ModelIR {
         model_ident: Ident::new(&String::from("det"), Span::call_site()),
@@ -468,25 +2761,38 @@ fn output_with_deterministic_var_is_module_item() {
                 String::from("x"),
                 VariableIR {
                     var_ident: Ident::new(&String::from("x"), Span::call_site()),
+                    plate: None,
                     type_ident: parse_quote!(bool),
                     dependency: parse_quote!(Bernoulli::new(0.5)),
                     is_stochastic: true,
                     is_queried: true,
                     is_observed: false,
+                    is_optional: false,
+                    parents: vec![],
+                    children: vec![Ident::new(&String::from("two_x"), Span::call_site())],
                 },
             ),
             (
                 String::from("two_x"),
                 VariableIR {
                     var_ident: Ident::new(&String::from("two_x"), Span::call_site()),
+                    plate: None,
                     type_ident: parse_quote!(u8),
                     dependency: parse_quote!(2u8 * x as u8),
                     is_stochastic: false,
                     is_queried: false,
                     is_observed: true,
+                    is_optional: false,
+                    parents: vec![Ident::new(&String::from("x"), Span::call_site())],
+                    children: vec![],
                 },
             ),
         ]),
+        params: Vec::new(),
+        observe_order: vec![String::from("two_x")],
+        topological_order: vec![String::from("x"), String::from("two_x")],
+        factors: vec![],
+        conditions: vec![],
     };
     let rust = codegen(ir);
 
@@ -496,5 +2802,163 @@ fn output_with_deterministic_var_is_module_item() {
         !rust_str.contains("fn weighted_sample_iter"),
         "weighted_sample_iter should not be generated when a deterministic variable is observed"
     );
+    assert!(
+        !rust_str.contains("fn mh_sample_iter"),
+        "mh_sample_iter should not be generated when a deterministic variable is observed"
+    );
+    // exact enumeration checks deterministic observations directly
+    assert!(rust_str.contains("fn enumerate"));
+    assert!(parse2::<ItemMod>(rust).is_ok());
+}
+
+#[test]
+fn real_types_for_gradient_samplers() {
+    use syn::parse_quote;
+    assert_eq!(real_type_is_vector(&parse_quote!(f64)), Some(false));
+    assert_eq!(real_type_is_vector(&parse_quote!(DVector<f64>)), Some(true));
+    assert_eq!(
+        real_type_is_vector(&parse_quote!(nalgebra::DVector<f64>)),
+        Some(true)
+    );
+    assert_eq!(real_type_is_vector(&parse_quote!(bool)), None);
+    assert_eq!(real_type_is_vector(&parse_quote!(Vec<f64>)), None);
+    assert_eq!(real_type_is_vector(&parse_quote!(DVector<u64>)), None);
+    assert_eq!(real_type_is_vector(&parse_quote!(DVector)), None);
+    assert_eq!(real_type_is_vector(&parse_quote!((f64, f64))), None);
+}
+
+#[test]
+fn hashable_types_for_enumeration() {
+    use syn::parse_quote;
+    assert!(is_hashable(&parse_quote!(bool)));
+    assert!(is_hashable(&parse_quote!(u64)));
+    assert!(is_hashable(&parse_quote!(String)));
+    assert!(is_hashable(&parse_quote!(Vec<usize>)));
+    assert!(is_hashable(&parse_quote!(Option<(bool, i32)>)));
+    assert!(is_hashable(&parse_quote!([u8; 4])));
+    assert!(!is_hashable(&parse_quote!(f64)));
+    assert!(!is_hashable(&parse_quote!(Vec<f64>)));
+    assert!(!is_hashable(&parse_quote!(DVector<u64>)));
+    assert!(!is_hashable(&parse_quote!((bool, f64))));
+}
+
+#[test]
+fn output_with_real_latents_has_gradient_samplers() {
+    use crate::analyze::analyze;
+    use crate::parse::ModelAst;
+    use quote::quote;
+    use syn::{ItemMod, parse2};
+
+    let ir = analyze(
+        parse2::<ModelAst>(quote!(
+            mod real;
+            use ferric::distributions::Normal;
+            let mu : f64 ~ Normal::new(0.0, 1.0);
+            let x : f64 ~ Normal::new(mu, 1.0);
+            observe x;
+            query mu;
+        ))
+        .unwrap(),
+    )
+    .unwrap();
+    let rust = codegen(ir);
+    let rust_str = rust.to_string();
+    assert!(rust_str.contains("fn nuts_sample_iter"));
+    assert!(rust_str.contains("fn set_unconstrained"));
+    assert!(parse2::<ItemMod>(rust).is_ok());
+}
+
+#[test]
+fn output_with_plates_is_module_item() {
+    use crate::analyze::analyze;
+    use crate::parse::ModelAst;
+    use quote::quote;
+    use syn::{ItemMod, parse2};
+
+    let ir = analyze(
+        parse2::<ModelAst>(quote!(
+            mod plates;
+            use ferric::distributions::Normal;
+            let n : usize = 3;
+            let theta[i in 0..n] : f64 ~ Normal::new(0.0, 1.0);
+            let y[i in 0..n] : f64 ~ Normal::new(theta[i], 1.0);
+            let y_sq[i in 0..n] : f64 = y[i] * y[i];
+            observe y;
+            query theta;
+        ))
+        .unwrap(),
+    )
+    .unwrap();
+    // the plate range is a parent of the plate
+    assert_eq!(ir.topological_order, ["n", "theta", "y", "y_sq"]);
+
+    let rust = codegen(ir);
+    let rust_str = rust.to_string();
+    assert!(rust_str.contains("fn indices_theta"));
+    assert!(rust_str.contains("fn evaldist_y (& mut self , i : usize)"));
+    // distributions are returned by value and dispatched statically
+    assert!(!rust_str.contains("Box < dyn"));
+    assert!(rust_str.contains("pub theta : Vec < f64 >"));
+    // only the elements of `theta` are i.i.d., so only they are scored as a batch
+    assert_eq!(rust_str.matches("log_prob_batch").count(), 1);
+    // a plate of reals is flattened into the unconstrained position
+    assert!(rust_str.contains("fn nuts_sample_iter"));
+    assert!(parse2::<ItemMod>(rust).is_ok());
+}
+
+#[test]
+fn output_with_params_is_module_item() {
+    use crate::analyze::analyze;
+    use crate::parse::ModelAst;
+    use quote::quote;
+    use syn::{ItemMod, parse2};
+
+    let ir = analyze(
+        parse2::<ModelAst>(quote!(
+            mod coin;
+            use ferric::distributions::Beta;
+            use ferric::distributions::Binomial;
+            param num_trials : u64;
+            let p : f64 ~ Beta::new(1.0, 1.0);
+            let heads : u64 ~ Binomial::new(num_trials, p);
+            observe heads;
+            query p;
+        ))
+        .unwrap(),
+    )
+    .unwrap();
+
+    let rust = codegen(ir);
+    let rust_str = rust.to_string();
+    // a parameter is a field of the model read like a variable
+    assert!(rust_str.contains("pub num_trials : u64"));
+    assert!(rust_str.contains("param_num_trials : u64"));
+    assert!(rust_str.contains("fn eval_num_trials (& self) -> Result < & u64 , String >"));
+    assert!(rust_str.contains("self . param_num_trials"));
+    // a variable is evaluated first and then read in place instead of cloned
+    assert!(rust_str.contains("self . eval_p () ? ;"));
+    assert!(rust_str.contains("(* self . var_p . unwrap_ref ())"));
     assert!(parse2::<ItemMod>(rust).is_ok());
 }
+
+#[test]
+fn dependency_reads_borrowed_variables_in_place() {
+    let names = Names {
+        variables: HashSet::from([String::from("mu"), String::from("cov")]),
+        params: HashSet::new(),
+    };
+    let dep = dependency(
+        quote!(MultivariateNormal::new(mu, cov.clone() * mu[0])),
+        &names,
+    );
+    let dep_str = dep.to_string();
+    // every referenced variable is evaluated before the expression
+    assert!(dep_str.starts_with("{ self . eval_mu () ? ; self . eval_cov () ? ;"));
+    // a variable that may be moved is cloned, and one that is borrowed is read in place
+    assert!(
+        dep_str
+            .contains("new (:: core :: clone :: Clone :: clone (self . var_mu . unwrap_ref ()) ,")
+    );
+    assert!(dep_str.contains("(* self . var_cov . unwrap_ref ()) . clone ()"));
+    assert!(dep_str.contains("(* self . var_mu . unwrap_ref ()) [0]"));
+}
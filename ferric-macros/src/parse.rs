@@ -1,10 +1,27 @@
 // Copyright 2022 The Ferric AI Project Developers
 use syn::parse::{Parse, ParseStream, Result};
-use syn::{Error, Expr, Ident, Token, Type};
+use syn::{Error, Expr, Ident, Token, Type, bracketed};
+
+/// PlateAst is the `[index in range]` part of an indexed declaration such as
+/// `let y[i in 0..n] : f64 ~ Normal::new(mu, 1.0);`.
+pub struct PlateAst {
+    pub index_ident: Ident,
+    /// An expression that iterates over the `usize` indices of the family.
+    pub range: Expr,
+}
+
+/// ParamAst is a `param name : Type;` or `data name : Type;` declaration: an input
+/// supplied with the model rather than sampled or observed.
+pub struct ParamAst {
+    pub param_ident: Ident,
+    pub type_ident: Type,
+}
 
 /// StmtAst is the Abstract Syntax Tree representation of a single dependency statement.
 pub struct StmtAst {
     pub var_ident: Ident,
+    /// `Some` if the statement declares an indexed family of variables.
+    pub plate: Option<PlateAst>,
     pub type_ident: Type,
     pub dependency: Expr,
     /// `true` if defined with `~` (distribution), `false` if defined with `=` (deterministic expr).
@@ -17,8 +34,16 @@ pub struct ModelAst {
     pub model_ident: Ident,
     pub use_exprs: Vec<Expr>,
     pub stmts: Vec<StmtAst>,
+    pub params: Vec<ParamAst>,
     pub queries: Vec<Ident>,
     pub observes: Vec<Ident>,
+    /// The observed variables declared with `observe var_name?;`, whose observed values
+    /// may be missing.
+    pub optional_observes: Vec<Ident>,
+    /// The `f64` log-weight expressions of the `factor` statements, in order.
+    pub factors: Vec<Expr>,
+    /// The `bool` expressions of the `condition` statements, in order.
+    pub conditions: Vec<Expr>,
 }
 
 impl Parse for ModelAst {
@@ -30,8 +55,12 @@ impl Parse for ModelAst {
 
         let mut stmts = Vec::<StmtAst>::new();
         let mut use_exprs = Vec::<Expr>::new();
+        let mut params = Vec::<ParamAst>::new();
         let mut queries = Vec::<Ident>::new();
         let mut observes = Vec::<Ident>::new();
+        let mut optional_observes = Vec::<Ident>::new();
+        let mut factors = Vec::<Expr>::new();
+        let mut conditions = Vec::<Expr>::new();
 
         while !input.is_empty() {
             // let var_name ~ dep_expr;
@@ -39,6 +68,17 @@ impl Parse for ModelAst {
                 // peek confirmed the token; this parse cannot fail.
                 input.parse::<Token![let]>().expect("peek confirmed");
                 let var_ident: Ident = input.parse()?;
+                // optional plate: let var_name[index in range] ...
+                let plate = if input.peek(syn::token::Bracket) {
+                    let content;
+                    bracketed!(content in input);
+                    let index_ident: Ident = content.parse()?;
+                    content.parse::<Token![in]>()?;
+                    let range: Expr = content.parse()?;
+                    Some(PlateAst { index_ident, range })
+                } else {
+                    None
+                };
                 input.parse::<Token![:]>()?;
                 let type_ident: Type = input.parse()?;
                 let is_stochastic = if input.peek(Token![~]) {
@@ -54,6 +94,7 @@ impl Parse for ModelAst {
                 input.parse::<Token![;]>()?;
                 stmts.push(StmtAst {
                     var_ident,
+                    plate,
                     type_ident,
                     dependency,
                     is_stochastic,
@@ -68,12 +109,39 @@ impl Parse for ModelAst {
                 // peek confirmed an Ident; this parse cannot fail.
                 let keyword: Ident = input.parse().expect("peek confirmed");
                 match keyword.to_string().as_ref() {
+                    "param" | "data" => {
+                        // param name : Type;
+                        let param_ident: Ident = input.parse()?;
+                        input.parse::<Token![:]>()?;
+                        let type_ident: Type = input.parse()?;
+                        input.parse::<Token![;]>()?;
+                        params.push(ParamAst {
+                            param_ident,
+                            type_ident,
+                        });
+                    }
                     "observe" => {
-                        // observe var_name;
+                        // observe var_name; or observe var_name?;
                         let var_name: Ident = input.parse()?;
+                        if input.peek(Token![?]) {
+                            input.parse::<Token![?]>().expect("peek confirmed");
+                            optional_observes.push(var_name.clone());
+                        }
                         input.parse::<Token![;]>()?;
                         observes.push(var_name);
                     }
+                    "factor" => {
+                        // factor log_weight_expr;
+                        let factor: Expr = input.parse()?;
+                        input.parse::<Token![;]>()?;
+                        factors.push(factor);
+                    }
+                    "condition" => {
+                        // condition bool_expr;
+                        let condition: Expr = input.parse()?;
+                        input.parse::<Token![;]>()?;
+                        conditions.push(condition);
+                    }
                     "query" => {
                         // query var_name;
                         let var_name: Ident = input.parse()?;
@@ -83,20 +151,26 @@ impl Parse for ModelAst {
                     _ => {
                         return Err(Error::new(
                             keyword.span(),
-                            "expected let | use | observe | query",
+                            "expected let | use | param | data | observe | factor | condition | query",
                         ));
                     }
                 }
             } else {
-                return Err(input.error("expected let | use | observe | query"));
+                return Err(input.error(
+                    "expected let | use | param | data | observe | factor | condition | query",
+                ));
             }
         }
         Ok(ModelAst {
             model_ident,
             use_exprs,
             stmts,
+            params,
             queries,
             observes,
+            optional_observes,
+            factors,
+            conditions,
         })
     }
 }
@@ -162,6 +236,12 @@ fn test_parse_errors_per_token() {
     assert!(parse2::<ModelAst>(quote!(mod m; let x : bool ~ Foo)).is_err());
     // `let x : bool = expr` with no trailing `;`.
     assert!(parse2::<ModelAst>(quote!(mod m; let x : bool = true)).is_err());
+    // plate with no index.
+    assert!(parse2::<ModelAst>(quote!(mod m; let x[] : bool = true;)).is_err());
+    // plate with no `in`.
+    assert!(parse2::<ModelAst>(quote!(mod m; let x[i] : bool = true;)).is_err());
+    // plate with no range.
+    assert!(parse2::<ModelAst>(quote!(mod m; let x[i in] : bool = true;)).is_err());
 
     // --- use statement ---
     // `use` with no expression.
@@ -169,6 +249,16 @@ fn test_parse_errors_per_token() {
     // `use foo` with no trailing `;`.
     assert!(parse2::<ModelAst>(quote!(mod m; use foo)).is_err());
 
+    // --- param statement ---
+    // `param` with no name.
+    assert!(parse2::<ModelAst>(quote!(mod m; param ;)).is_err());
+    // `param n` with no `:`.
+    assert!(parse2::<ModelAst>(quote!(mod m; param n ;)).is_err());
+    // `param n :` with no type.
+    assert!(parse2::<ModelAst>(quote!(mod m; param n : ;)).is_err());
+    // `data n : usize` with no trailing `;`.
+    assert!(parse2::<ModelAst>(quote!(mod m; data n : usize)).is_err());
+
     // --- observe statement ---
     // `observe` with no var_name.
     assert!(parse2::<ModelAst>(quote!(mod m; observe ;)).is_err());
@@ -282,3 +372,136 @@ fn test_parse_vec_type() {
     let exp_type: Type = parse_quote!(Vec<f64>);
     assert_eq!(model_ast.stmts[0].type_ident, exp_type);
 }
+
+#[test]
+fn test_parse_plate() {
+    use quote::quote;
+    use syn::{parse_quote, parse2};
+
+    let model_ast = parse2::<ModelAst>(quote!(
+        mod regression;
+        use ferric::distributions::Normal;
+
+        let mu : f64 ~ Normal::new(0.0, 1.0);
+        let y[i in 0..3] : f64 ~ Normal::new(mu, 1.0);
+
+        observe y;
+        query mu;
+    ))
+    .unwrap();
+
+    assert!(model_ast.stmts[0].plate.is_none());
+    let plate = model_ast.stmts[1].plate.as_ref().unwrap();
+    let exp_index: Ident = parse_quote!(i);
+    let exp_range: Expr = parse_quote!(0..3);
+    assert_eq!(plate.index_ident, exp_index);
+    assert_eq!(plate.range, exp_range);
+    let exp_type: Type = parse_quote!(f64);
+    assert_eq!(model_ast.stmts[1].type_ident, exp_type);
+}
+
+#[test]
+fn test_parse_params() {
+    use quote::quote;
+    use syn::{parse_quote, parse2};
+
+    let model_ast = parse2::<ModelAst>(quote!(
+        mod coin;
+        use ferric::distributions::Beta;
+        use ferric::distributions::Binomial;
+
+        param num_trials : u64;
+        data prior : (f64, f64);
+        let p : f64 ~ Beta::new(prior.0, prior.1);
+        let heads : u64 ~ Binomial::new(num_trials, p);
+
+        observe heads;
+        query p;
+    ))
+    .unwrap();
+
+    assert_eq!(model_ast.params.len(), 2);
+    let exp_name: Ident = parse_quote!(num_trials);
+    let exp_type: Type = parse_quote!(u64);
+    assert_eq!(model_ast.params[0].param_ident, exp_name);
+    assert_eq!(model_ast.params[0].type_ident, exp_type);
+    let exp_name: Ident = parse_quote!(prior);
+    let exp_type: Type = parse_quote!((f64, f64));
+    assert_eq!(model_ast.params[1].param_ident, exp_name);
+    assert_eq!(model_ast.params[1].type_ident, exp_type);
+    assert_eq!(model_ast.stmts.len(), 2);
+}
+
+#[test]
+fn test_parse_optional_observe() {
+    use quote::quote;
+    use syn::{parse_quote, parse2};
+
+    let model_ast = parse2::<ModelAst>(quote!(
+        mod sensor;
+        use ferric::distributions::Normal;
+
+        let level : f64 ~ Normal::new(0.0, 1.0);
+        let reading[t in 0..4] : f64 ~ Normal::new(level, 0.5);
+        let spare : f64 ~ Normal::new(level, 0.5);
+
+        observe reading?;
+        observe spare;
+        query level;
+    ))
+    .unwrap();
+
+    let exp_reading: Ident = parse_quote!(reading);
+    let exp_spare: Ident = parse_quote!(spare);
+    assert_eq!(model_ast.observes, [exp_reading.clone(), exp_spare]);
+    assert_eq!(model_ast.optional_observes, [exp_reading]);
+    // `?` must directly follow the observed variable
+    assert!(parse2::<ModelAst>(quote!(mod m; observe ? foo;)).is_err());
+    assert!(parse2::<ModelAst>(quote!(mod m; observe foo?)).is_err());
+}
+
+#[test]
+fn test_parse_factor() {
+    use quote::quote;
+    use syn::{parse_quote, parse2};
+
+    let model_ast = parse2::<ModelAst>(quote!(
+        mod soft;
+        use ferric::distributions::Normal;
+
+        let x : f64 ~ Normal::new(0.0, 1.0);
+        factor -0.5 * (x - 1.0).powi(2);
+        query x;
+    ))
+    .unwrap();
+
+    let exp_factor: Expr = parse_quote!(-0.5 * (x - 1.0).powi(2));
+    assert_eq!(model_ast.factors, [exp_factor]);
+    // `factor` with no expression or no trailing `;`.
+    assert!(parse2::<ModelAst>(quote!(mod m; factor ;)).is_err());
+    assert!(parse2::<ModelAst>(quote!(mod m; factor x)).is_err());
+}
+
+#[test]
+fn test_parse_condition() {
+    use quote::quote;
+    use syn::{parse_quote, parse2};
+
+    let model_ast = parse2::<ModelAst>(quote!(
+        mod sum;
+        use ferric::distributions::Normal;
+
+        let x : f64 ~ Normal::new(0.0, 1.0);
+        let y : f64 ~ Normal::new(0.0, 1.0);
+        condition x + y > 3.0;
+        query x;
+    ))
+    .unwrap();
+
+    let exp_condition: Expr = parse_quote!(x + y > 3.0);
+    assert_eq!(model_ast.conditions, [exp_condition]);
+    assert!(model_ast.factors.is_empty());
+    // `condition` with no expression or no trailing `;`.
+    assert!(parse2::<ModelAst>(quote!(mod m; condition ;)).is_err());
+    assert!(parse2::<ModelAst>(quote!(mod m; condition x)).is_err());
+}
@@ -0,0 +1,213 @@
+// Copyright 2022 The Ferric AI Project Developers
+//! Benchmarks of `sample` and `log_prob` for every distribution.
+//!
+//! Run with `cargo bench -p ferric --bench distributions`, optionally followed
+//! by a filter such as `-- normal`.
+
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use ferric::distributions::*;
+use nalgebra::{DMatrix, DVector};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+// benchmarks `<name>/sample` and `<name>/log_prob` at the value `x`
+macro_rules! bench {
+    ($c:expr, $name:literal, $dist:expr, $x:expr) => {{
+        let dist = $dist.unwrap();
+        let x = $x;
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut group = $c.benchmark_group($name);
+        group.bench_function("sample", |b| {
+            b.iter(|| Distribution::<StdRng>::sample(black_box(&dist), &mut rng))
+        });
+        group.bench_function("log_prob", |b| {
+            b.iter(|| Distribution::<StdRng>::log_prob(black_box(&dist), black_box(&x)))
+        });
+        group.finish();
+    }};
+}
+
+// benchmarks `<name>/log_prob_loop` against `<name>/log_prob_batch` over the
+// i.i.d. observations `xs`
+macro_rules! bench_batch {
+    ($c:expr, $name:literal, $dist:expr, $xs:expr) => {{
+        let dist = $dist.unwrap();
+        let xs: Vec<_> = $xs;
+        let mut group = $c.benchmark_group(concat!("batch/", $name));
+        group.bench_function("log_prob_loop", |b| {
+            b.iter(|| {
+                black_box(&xs)
+                    .iter()
+                    .map(|x| Distribution::<StdRng>::log_prob(black_box(&dist), x))
+                    .sum::<f64>()
+            })
+        });
+        group.bench_function("log_prob_batch", |b| {
+            b.iter(|| Distribution::<StdRng>::log_prob_batch(black_box(&dist), black_box(&xs)))
+        });
+        group.finish();
+    }};
+}
+
+fn univariate_continuous(c: &mut Criterion) {
+    bench!(c, "beta", Beta::new(2.0, 3.0), 0.4);
+    bench!(c, "cauchy", Cauchy::new(0.0, 1.0), 0.5);
+    bench!(c, "chi", Chi::new(3.0), 1.5);
+    bench!(c, "chi_squared", ChiSquared::new(3.0), 1.5);
+    bench!(c, "erlang", Erlang::new(3, 2.0), 1.5);
+    bench!(c, "exponential", Exponential::new(2.0), 0.5);
+    bench!(c, "fisher_f", FisherF::new(4.0, 6.0), 1.2);
+    bench!(c, "frechet", Frechet::new(0.0, 1.0, 2.0), 1.5);
+    bench!(c, "gamma", Gamma::new(2.0, 1.5), 2.5);
+    bench!(c, "gumbel", Gumbel::new(0.0, 1.0), 0.5);
+    bench!(c, "half_normal", HalfNormal::new(1.0), 0.5);
+    bench!(c, "inverse_gamma", InverseGamma::new(3.0, 2.0), 1.0);
+    bench!(c, "inverse_gaussian", InverseGaussian::new(1.0, 2.0), 0.8);
+    bench!(c, "laplace", Laplace::new(0.0, 1.0), 0.5);
+    bench!(c, "log_normal", LogNormal::new(0.0, 0.5), 1.2);
+    bench!(c, "logistic", Logistic::new(0.0, 1.0), 0.5);
+    bench!(c, "normal", Normal::new(0.0, 1.0), 0.5);
+    bench!(c, "pareto", Pareto::new(1.0, 3.0), 1.5);
+    bench!(c, "rayleigh", Rayleigh::new(1.0), 1.2);
+    bench!(c, "student_t", StudentT::new(5.0), 0.5);
+    bench!(c, "triangular", Triangular::new(0.0, 2.0, 0.5), 0.8);
+    bench!(c, "uniform", Uniform::new(-1.0, 2.0), 0.5);
+    bench!(c, "weibull", Weibull::new(1.0, 1.5), 0.8);
+}
+
+fn univariate_discrete(c: &mut Criterion) {
+    bench!(c, "bernoulli", Bernoulli::new(0.3), true);
+    bench!(c, "beta_binomial", BetaBinomial::new(20, 2.0, 3.0), 7u64);
+    bench!(c, "binomial", Binomial::new(20, 0.3), 7u64);
+    bench!(
+        c,
+        "categorical",
+        Categorical::new(vec![0.1, 0.2, 0.3, 0.4]),
+        2usize
+    );
+    bench!(c, "discrete_uniform", DiscreteUniform::new(1, 6), 4i64);
+    bench!(c, "geometric", Geometric::new(0.3), 2u64);
+    bench!(c, "hypergeometric", Hypergeometric::new(50, 20, 10), 4u64);
+    bench!(
+        c,
+        "negative_binomial",
+        NegativeBinomial::new(3.0, 0.4),
+        5u64
+    );
+    bench!(c, "poisson", Poisson::new(4.0), 3u64);
+}
+
+fn generic(c: &mut Criterion) {
+    bench!(c, "dirac", Dirac::new(1.5f64), 1.5f64);
+    bench!(
+        c,
+        "empirical",
+        Empirical::new(vec![(1i64, 0.2), (2, 0.5), (3, 0.3)]),
+        2i64
+    );
+    bench!(
+        c,
+        "empirical_hashed",
+        Empirical::new_hashed(vec![(1i64, 0.2), (2, 0.5), (3, 0.3)]),
+        2i64
+    );
+    bench!(
+        c,
+        "mixture",
+        Mixture::new(
+            vec![0.3, 0.7],
+            vec![
+                Normal::new(-1.0, 1.0).unwrap(),
+                Normal::new(2.0, 0.5).unwrap()
+            ]
+        ),
+        0.5
+    );
+    bench!(
+        c,
+        "truncated",
+        Truncated::new(Normal::new(0.0, 1.0).unwrap(), -1.0, 2.0),
+        0.5
+    );
+}
+
+fn multivariate(c: &mut Criterion) {
+    let mean = DVector::from_vec(vec![0.0, 1.0, -1.0]);
+    let cov = DMatrix::from_row_slice(3, 3, &[2.0, 0.5, 0.0, 0.5, 1.0, 0.2, 0.0, 0.2, 1.5]);
+    let x = DVector::from_vec(vec![0.5, 0.5, 0.5]);
+    bench!(
+        c,
+        "dirichlet",
+        Dirichlet::new(vec![1.0, 2.0, 3.0]),
+        vec![0.2, 0.3, 0.5]
+    );
+    bench!(
+        c,
+        "multinomial",
+        Multinomial::new(10, vec![0.2, 0.3, 0.5]),
+        vec![2u64, 3, 5]
+    );
+    bench!(
+        c,
+        "multivariate_normal",
+        MultivariateNormal::new(mean.clone(), cov.clone()),
+        x.clone()
+    );
+    bench!(
+        c,
+        "multivariate_student_t",
+        MultivariateStudentT::new(mean, cov.clone(), 4.0),
+        x
+    );
+    bench!(
+        c,
+        "matrix_normal",
+        MatrixNormal::new(DMatrix::zeros(3, 2), cov.clone(), DMatrix::identity(2, 2)),
+        DMatrix::from_element(3, 2, 0.5)
+    );
+    bench!(c, "wishart", Wishart::new(5.0, cov.clone()), cov);
+}
+
+fn batch(c: &mut Criterion) {
+    const N: usize = 10_000;
+    bench_batch!(
+        c,
+        "normal",
+        Normal::new(0.5, 2.0),
+        (0..N).map(|i| i as f64 / N as f64).collect()
+    );
+    bench_batch!(
+        c,
+        "exponential",
+        Exponential::new(2.0),
+        (0..N).map(|i| i as f64 / N as f64).collect()
+    );
+    bench_batch!(
+        c,
+        "gamma",
+        Gamma::new(2.0, 1.5),
+        (1..=N).map(|i| i as f64 / N as f64).collect()
+    );
+    bench_batch!(
+        c,
+        "poisson",
+        Poisson::new(4.0),
+        (0..N).map(|i| (i % 10) as u64).collect()
+    );
+    bench_batch!(
+        c,
+        "bernoulli",
+        Bernoulli::new(0.3),
+        (0..N).map(|i| i % 3 == 0).collect()
+    );
+}
+
+criterion_group!(
+    benches,
+    univariate_continuous,
+    univariate_discrete,
+    generic,
+    multivariate,
+    batch
+);
+criterion_main!(benches);
@@ -61,6 +61,23 @@ fn main() {
     let post_rain_weighted = ferric::weighted_mean(&rain_vals, &log_weights);
     let post_sprinkler_weighted = ferric::weighted_mean(&sprinkler_vals, &log_weights);
 
+    // --- Exact enumeration ---
+    let posterior = model.enumerate().unwrap();
+    let mut post_rain_exact = 0.0;
+    let mut post_sprinkler_exact = 0.0;
+    for s in posterior.samples.iter() {
+        if s.sample.rain {
+            post_rain_exact += s.probability;
+        }
+        if s.sample.sprinkler {
+            post_sprinkler_exact += s.probability;
+        }
+    }
+
+    println!(
+        "exact       : rain = {:.4} sprinkler = {:.4}",
+        post_rain_exact, post_sprinkler_exact
+    );
     println!(
         "rejection   : rain = {:.4} sprinkler = {:.4}  ({} ms, {} samples)",
         post_rain_reject, post_sprinkler_reject, reject_elapsed, num_samples
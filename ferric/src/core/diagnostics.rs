@@ -0,0 +1,124 @@
+// Copyright 2022 The Ferric AI Project Developers
+//! Diagnostics of the worlds discarded by a sampler.
+//!
+//! A world is invalid when the parameters computed for one of its
+//! distributions are illegal, for example a `Normal` whose standard deviation
+//! is a sampled variable that came out negative.  Invalid worlds have
+//! probability zero, so samplers discard them and record why in a
+//! [`Diagnostics`].
+
+/// The number of error messages kept by a [`Diagnostics`].
+const MAX_MESSAGES: usize = 10;
+
+/// A count of the invalid worlds that a sampler discarded, with the error
+/// messages of the first few.
+///
+/// # Examples
+///
+/// ```
+/// # use ferric::Diagnostics;
+/// let mut diagnostics = Diagnostics::default();
+/// assert_eq!(diagnostics.num_invalid(), 0);
+///
+/// diagnostics.record(String::from("invalid distribution of `x`"));
+/// assert_eq!(diagnostics.num_invalid(), 1);
+/// assert_eq!(diagnostics.messages(), ["invalid distribution of `x`"]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Diagnostics {
+    num_invalid: usize,
+    messages: Vec<String>,
+    stopped: bool,
+}
+
+impl Diagnostics {
+    /// The number of worlds in a row a sampler may discard before it gives
+    /// up; see [`Diagnostics::gives_up`].
+    pub const MAX_CONSECUTIVE_INVALID: usize = 10_000;
+
+    /// Record one discarded world and the error that made it invalid.
+    pub fn record(&mut self, message: String) {
+        self.num_invalid += 1;
+        if self.messages.len() < MAX_MESSAGES {
+            self.messages.push(message);
+        }
+    }
+
+    /// The number of worlds discarded so far.
+    pub fn num_invalid(&self) -> usize {
+        self.num_invalid
+    }
+
+    /// The error messages of the first ten discarded worlds, in the order
+    /// they were recorded.
+    pub fn messages(&self) -> &[String] {
+        &self.messages
+    }
+
+    /// Whether a sampler which has discarded `num_in_a_row` worlds in a row,
+    /// without drawing a valid one in between, should give up.
+    ///
+    /// A model in which no world is valid, such as one with a `Normal` of
+    /// negative standard deviation, would otherwise make the sampler retry
+    /// forever.  Once `num_in_a_row` reaches
+    /// [`Diagnostics::MAX_CONSECUTIVE_INVALID`] the sampler is marked as
+    /// [`stopped`](Diagnostics::stopped).
+    pub fn gives_up(&mut self, num_in_a_row: usize) -> bool {
+        if num_in_a_row >= Self::MAX_CONSECUTIVE_INVALID {
+            self.stopped = true;
+        }
+        self.stopped
+    }
+
+    /// Record the error that made a sampler stop.
+    pub fn stop(&mut self, message: String) {
+        self.record(message);
+        self.stopped = true;
+    }
+
+    /// Whether the sampler has stopped for good, either after too many
+    /// invalid worlds in a row or because of an error it cannot recover
+    /// from.  The recorded [`messages`](Diagnostics::messages) tell why.
+    pub fn stopped(&self) -> bool {
+        self.stopped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_first_messages() {
+        let mut diagnostics = Diagnostics::default();
+        for k in 0..25 {
+            diagnostics.record(format!("error {}", k));
+        }
+        assert_eq!(diagnostics.num_invalid(), 25);
+        assert_eq!(diagnostics.messages().len(), MAX_MESSAGES);
+        assert_eq!(diagnostics.messages()[0], "error 0");
+        assert_eq!(diagnostics.messages()[9], "error 9");
+    }
+
+    #[test]
+    fn gives_up_after_too_many_in_a_row() {
+        let mut diagnostics = Diagnostics::default();
+        diagnostics.record(String::from("invalid distribution of `x`"));
+        assert!(!diagnostics.gives_up(Diagnostics::MAX_CONSECUTIVE_INVALID - 1));
+        assert!(!diagnostics.stopped());
+        assert!(diagnostics.gives_up(Diagnostics::MAX_CONSECUTIVE_INVALID));
+        assert!(diagnostics.stopped());
+        // a stopped sampler stays stopped
+        assert!(diagnostics.gives_up(1));
+        assert_eq!(diagnostics.messages(), ["invalid distribution of `x`"]);
+    }
+
+    #[test]
+    fn stop_records_the_error() {
+        let mut diagnostics = Diagnostics::default();
+        diagnostics.stop(String::from("cannot query `x`"));
+        assert!(diagnostics.stopped());
+        assert_eq!(diagnostics.num_invalid(), 1);
+        assert_eq!(diagnostics.messages(), ["cannot query `x`"]);
+    }
+}
@@ -42,10 +42,7 @@ impl<T> FeOption<T> {
     /// ```
     #[inline]
     pub const fn is_null(&self) -> bool {
-        match *self {
-            Null => true,
-            _ => false,
-        }
+        matches!(*self, Null)
     }
 
     /// Returns `true` if the FeOption is an [`Unknown`] value.
@@ -65,10 +62,7 @@ impl<T> FeOption<T> {
     /// ```
     #[inline]
     pub const fn is_unknown(&self) -> bool {
-        match *self {
-            Unknown => true,
-            _ => false,
-        }
+        matches!(*self, Unknown)
     }
 
     /// Returns `true` if the FeOption is a [`Known`] value.
@@ -88,10 +82,7 @@ impl<T> FeOption<T> {
     /// ```
     #[inline]
     pub const fn is_known(&self) -> bool {
-        match *self {
-            Known(_) => true,
-            _ => false,
-        }
+        matches!(*self, Known(_))
     }
 
     /// Returns the contained [`Known`] value, consuming the `self` value.
@@ -127,12 +118,35 @@ impl<T> FeOption<T> {
         }
     }
 
+    /// Returns a reference to the contained [`Known`] value.
+    ///
+    /// The generated `World::eval_*` methods use this to hand out the value
+    /// of a variable stored in a `World` field without copying it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the self value equals [`Unknown`] or [`Null`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ferric::*;
+    /// let x: FeOption<Vec<i32>> = Known(vec![1, 2, 3]);
+    /// assert_eq!(x.unwrap_ref().len(), 3);
+    /// ```
+    pub fn unwrap_ref(&self) -> &T {
+        match self {
+            Known(val) => val,
+            Null => unwrap_failed_null(),
+            Unknown => unwrap_failed_unknown(),
+        }
+    }
+
     /// Returns a clone of the contained [`Known`] value without consuming `self`.
     ///
     /// Unlike [`unwrap`](FeOption::unwrap), this borrows `self` and clones the
-    /// inner value.  This is needed when the value is stored inside a struct
-    /// field (`&mut self`) rather than owned outright — for example, in the
-    /// generated `World::eval_*` methods where `self.var_x` cannot be moved.
+    /// inner value, which is needed when the value is stored inside a struct
+    /// field rather than owned outright.
     ///
     /// # Panics
     ///
@@ -159,6 +173,26 @@ impl<T> FeOption<T> {
     }
 }
 
+impl<T> From<Option<T>> for FeOption<T> {
+    /// Converts `Some(value)` to [`Known`] and `None` to [`Null`], so that a
+    /// missing observation can be passed to a model as an `Option`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ferric::*;
+    /// let readings: Vec<FeOption<f64>> = vec![Some(1.5), None].into_iter().map(FeOption::from).collect();
+    /// assert!(readings[0].is_known());
+    /// assert!(readings[1].is_null());
+    /// ```
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => Known(value),
+            None => Null,
+        }
+    }
+}
+
 // Non-generic, cold panic helpers. Pulling the panic out of the generic
 // `unwrap` keeps each panic site at a stable, non-monomorphized source
 // location so coverage tools can attribute the line correctly.
@@ -251,6 +285,28 @@ mod tests {
     }
 
     #[test]
+    fn unwrap_ref_known_borrows() {
+        let k: FeOption<Vec<i32>> = Known(vec![1, 2, 3]);
+        assert_eq!(k.unwrap_ref(), &vec![1, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "called `FeOption::unwrap()` on an `Unknown` value")]
+    fn unwrap_ref_unknown_panics() {
+        let u: FeOption<Vec<i32>> = Unknown;
+        let _ = u.unwrap_ref();
+    }
+
+    #[test]
+    fn from_option_maps_none_to_null() {
+        let k: FeOption<u32> = Some(7).into();
+        let n: FeOption<u32> = None.into();
+        assert_eq!(k.unwrap(), 7);
+        assert!(n.is_null());
+    }
+
+    #[test]
+    #[allow(clippy::clone_on_copy)]
     fn copy_and_clone_are_supported() {
         // Exercise the derived Copy/Clone impls on FeOption for every variant
         // so each arm of the derived `clone` match is hit.
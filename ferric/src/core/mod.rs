@@ -1,5 +1,7 @@
 // Copyright 2022 The Ferric AI Project Developers
+mod diagnostics;
 mod feoption;
 
 // Re-exports
+pub use self::diagnostics::Diagnostics;
 pub use self::feoption::FeOption;
@@ -0,0 +1,110 @@
+// Copyright 2022 The Ferric AI Project Developers
+
+use rand::Rng;
+
+/// Walker's alias table for drawing an index from a finite distribution in
+/// constant time.
+///
+/// The table is built with Vose's method in $O(k)$. Each draw picks a column
+/// uniformly and then either keeps it or jumps to its alias, so sampling
+/// costs one uniform integer and one uniform float regardless of $k$.
+pub(crate) struct AliasTable {
+    /// Probability of keeping column $i$ rather than jumping to `alias[i]`.
+    prob: Vec<f64>,
+    alias: Vec<usize>,
+}
+
+impl AliasTable {
+    /// Build the table for the normalised, non-empty probability vector
+    /// `probs`.
+    pub(crate) fn new(probs: &[f64]) -> AliasTable {
+        let n = probs.len();
+        // columns left over by rounding error are topped up to 1 and alias
+        // the most likely category, so a zero-probability one is never drawn
+        let fallback = (0..n)
+            .max_by(|&i, &j| probs[i].total_cmp(&probs[j]))
+            .expect("probs is not empty");
+        let mut scaled: Vec<f64> = probs.iter().map(|p| p * n as f64).collect();
+        let mut prob = vec![0.0; n];
+        let mut alias = vec![fallback; n];
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) =
+            (0..n).partition(|&i| scaled[i] < 1.0);
+        while let (Some(&s), Some(&l)) = (small.last(), large.last()) {
+            small.pop();
+            large.pop();
+            prob[s] = scaled[s];
+            alias[s] = l;
+            scaled[l] = (scaled[l] + scaled[s]) - 1.0;
+            if scaled[l] < 1.0 {
+                small.push(l);
+            } else {
+                large.push(l);
+            }
+        }
+        for i in large {
+            prob[i] = 1.0;
+        }
+        for i in small {
+            prob[i] = if probs[i] > 0.0 { 1.0 } else { 0.0 };
+        }
+        AliasTable { prob, alias }
+    }
+
+    /// Draw an index in `0..k` with probability `probs[i]`.
+    pub(crate) fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        let i = rng.gen_range(0..self.prob.len());
+        if rng.r#gen::<f64>() < self.prob[i] {
+            i
+        } else {
+            self.alias[i]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+
+    #[test]
+    fn alias_matches_probs() {
+        let mut rng = thread_rng();
+        let probs = vec![0.05, 0.4, 0.0, 0.25, 0.3];
+        let table = AliasTable::new(&probs);
+        let trials = 200_000;
+        let mut counts = [0u64; 5];
+        for _ in 0..trials {
+            counts[table.sample(&mut rng)] += 1;
+        }
+        for (i, &expected_p) in probs.iter().enumerate() {
+            let empirical_p = counts[i] as f64 / trials as f64;
+            let err = 5.0 * (expected_p * (1.0 - expected_p) / trials as f64).sqrt();
+            assert!(
+                (empirical_p - expected_p).abs() <= err,
+                "index {}: empirical {} expected {}",
+                i,
+                empirical_p,
+                expected_p
+            );
+        }
+    }
+
+    #[test]
+    fn alias_never_draws_zero_probability() {
+        let mut rng = thread_rng();
+        let mut probs = vec![0.0; 1000];
+        probs[0] = 1.0 / 3.0;
+        probs[999] = 2.0 / 3.0;
+        let table = AliasTable::new(&probs);
+        for _ in 0..10_000 {
+            let i = table.sample(&mut rng);
+            assert!(i == 0 || i == 999, "drew index {}", i);
+        }
+    }
+
+    #[test]
+    fn alias_single_category() {
+        let table = AliasTable::new(&[1.0]);
+        assert_eq!(table.sample(&mut thread_rng()), 0);
+    }
+}
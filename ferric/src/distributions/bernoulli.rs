@@ -1,6 +1,8 @@
 // Copyright 2022 The Ferric AI Project Developers
 
-use crate::distributions::Distribution;
+use crate::distributions::distribution::check_batch_len;
+use crate::distributions::univariate::check_probability;
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
 use rand::Rng;
 
 /// Bernoulli distribution over `{false, true}`.
@@ -28,6 +30,9 @@ use rand::Rng;
 pub struct Bernoulli {
     /// Probability of success.
     p: f64,
+    /// $\ln p$ and $\ln(1-p)$.
+    log_p: f64,
+    log_q: f64,
 }
 
 impl Bernoulli {
@@ -36,11 +41,20 @@ impl Bernoulli {
     /// # Errors
     ///
     /// Returns `Err` if `p` is not in `[0, 1]`.
-    pub fn new(p: f64) -> Result<Bernoulli, String> {
+    pub fn new(p: f64) -> Result<Bernoulli, DistributionError> {
         if !(0f64..=1f64).contains(&p) {
-            Err(format! {"Bernoulli: illegal probability `{}` should be between 0 and 1", p})
+            Err(DistributionError::InvalidParameter {
+                distribution: "Bernoulli",
+                name: "probability",
+                value: p,
+                constraint: "between 0 and 1",
+            })
         } else {
-            Ok(Bernoulli { p })
+            Ok(Bernoulli {
+                p,
+                log_p: p.ln(),
+                log_q: (1.0 - p).ln(),
+            })
         }
     }
 }
@@ -55,12 +69,80 @@ impl<R: Rng + ?Sized> Distribution<R> for Bernoulli {
 
     /// Returns $\log p$ when `x` is `true`, or $\log(1-p)$ when `x` is `false`.
     fn log_prob(&self, x: &bool) -> f64 {
-        if *x { self.p.ln() } else { (1.0 - self.p).ln() }
+        if *x { self.log_p } else { self.log_q }
+    }
+
+    /// Returns $s\log p + f\log(1-p)$ for $s$ successes and $f$ failures.
+    fn log_prob_batch(&self, xs: &[bool]) -> f64 {
+        let successes = xs.iter().filter(|&&x| x).count();
+        let failures = xs.len() - successes;
+        // an absent outcome contributes nothing, even if its log probability is -inf
+        let mut log_prob = 0.0;
+        if successes > 0 {
+            log_prob += successes as f64 * self.log_p;
+        }
+        if failures > 0 {
+            log_prob += failures as f64 * self.log_q;
+        }
+        log_prob
+    }
+
+    fn log_prob_batch_into(&self, xs: &[bool], out: &mut [f64]) {
+        check_batch_len(xs.len(), out.len());
+        for (x, log_prob) in xs.iter().zip(out.iter_mut()) {
+            *log_prob = if *x { self.log_p } else { self.log_q };
+        }
     }
 
     fn is_discrete(&self) -> bool {
         true
     }
+
+    fn finite_support(&self) -> Option<Vec<bool>> {
+        Some(vec![false, true])
+    }
+}
+
+impl Moments for Bernoulli {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = bool;
+
+    fn mean(&self) -> Option<f64> {
+        Some(self.p)
+    }
+
+    fn variance(&self) -> Option<f64> {
+        Some(self.p * (1.0 - self.p))
+    }
+
+    fn mode(&self) -> Option<bool> {
+        if self.p == 0.5 {
+            None
+        } else {
+            Some(self.p > 0.5)
+        }
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        let xlnx = |x: f64| if x > 0.0 { x * x.ln() } else { 0.0 };
+        Some(-xlnx(self.p) - xlnx(1.0 - self.p))
+    }
+}
+
+impl Univariate for Bernoulli {
+    fn cdf(&self, x: &bool) -> f64 {
+        if *x { 1.0 } else { 1.0 - self.p }
+    }
+
+    fn sf(&self, x: &bool) -> f64 {
+        if *x { 0.0 } else { self.p }
+    }
+
+    fn quantile(&self, p: f64) -> bool {
+        check_probability(p);
+        p > 1.0 - self.p
+    }
 }
 
 impl std::fmt::Display for Bernoulli {
@@ -132,4 +214,26 @@ mod tests {
         assert!(format!("{}", dist).contains("Bernoulli"));
         assert!(Distribution::<rand::rngs::ThreadRng>::is_discrete(&dist));
     }
+
+    #[test]
+    fn bernoulli_log_prob_batch() {
+        let dist = Bernoulli::new(0.3).unwrap();
+        let xs = [true, false, false, true, false];
+        let total = Distribution::<rand::rngs::ThreadRng>::log_prob_batch(&dist, &xs);
+        assert!((total - (2.0 * 0.3f64.ln() + 3.0 * 0.7f64.ln())).abs() < 1e-10);
+        let mut out = [0.0; 5];
+        Distribution::<rand::rngs::ThreadRng>::log_prob_batch_into(&dist, &xs, &mut out);
+        assert_eq!(out[0], 0.3f64.ln());
+        assert_eq!(out[1], 0.7f64.ln());
+        // an outcome with probability zero only matters if it occurs
+        let d0 = Bernoulli::new(0.0).unwrap();
+        assert_eq!(
+            Distribution::<rand::rngs::ThreadRng>::log_prob_batch(&d0, &[false, false]),
+            0.0
+        );
+        assert_eq!(
+            Distribution::<rand::rngs::ThreadRng>::log_prob_batch(&d0, &[false, true]),
+            f64::NEG_INFINITY
+        );
+    }
 }
@@ -1,9 +1,12 @@
 // Copyright 2022 The Ferric AI Project Developers
 
 use rand_distr::Beta as Beta2;
+use rand_distr::BetaError;
 use rand_distr::Distribution as Distribution2;
 
-use crate::distributions::Distribution;
+use crate::distributions::univariate::{continuous_quantile, continuous_quantile_sf};
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
+use crate::special::{beta_inc, digamma, ln_beta};
 use rand::Rng;
 
 /// Beta distribution over the open interval $(0, 1)$.
@@ -33,6 +36,9 @@ use rand::Rng;
 pub struct Beta {
     alpha: f64,
     beta: f64,
+    sampler: Beta2<f64>,
+    /// $\ln B(\alpha,\beta)$.
+    log_norm: f64,
 }
 
 impl Beta {
@@ -42,20 +48,28 @@ impl Beta {
     /// # Errors
     ///
     /// Returns `Err` if either `alpha` or `beta` is not strictly positive.
-    pub fn new(alpha: f64, beta: f64) -> Result<Beta, String> {
-        if alpha <= 0.0 {
-            Err(format!(
-                "Beta: illegal alpha `{}` should be greater than 0",
-                alpha
-            ))
-        } else if beta <= 0.0 {
-            Err(format!(
-                "Beta: illegal beta `{}` should be greater than 0",
-                beta
-            ))
-        } else {
-            Ok(Beta { alpha, beta })
-        }
+    pub fn new(alpha: f64, beta: f64) -> Result<Beta, DistributionError> {
+        Beta2::new(alpha, beta)
+            .map(|sampler| Beta {
+                alpha,
+                beta,
+                sampler,
+                log_norm: ln_beta(alpha, beta),
+            })
+            .map_err(|e| match e {
+                BetaError::AlphaTooSmall => DistributionError::InvalidParameter {
+                    distribution: "Beta",
+                    name: "alpha",
+                    value: alpha,
+                    constraint: "greater than 0",
+                },
+                BetaError::BetaTooSmall => DistributionError::InvalidParameter {
+                    distribution: "Beta",
+                    name: "beta",
+                    value: beta,
+                    constraint: "greater than 0",
+                },
+            })
     }
 }
 
@@ -63,7 +77,7 @@ impl<R: Rng + ?Sized> Distribution<R> for Beta {
     type Domain = f64;
 
     fn sample(&self, rng: &mut R) -> f64 {
-        Beta2::new(self.alpha, self.beta).unwrap().sample(rng)
+        self.sampler.sample(rng)
     }
 
     /// Returns $(\alpha-1)\ln x + (\beta-1)\ln(1-x) - \ln B(\alpha,\beta)$
@@ -72,14 +86,73 @@ impl<R: Rng + ?Sized> Distribution<R> for Beta {
         if *x <= 0.0 || *x >= 1.0 {
             return f64::NEG_INFINITY;
         }
-        let ln_beta = libm::lgamma(self.alpha) + libm::lgamma(self.beta)
-            - libm::lgamma(self.alpha + self.beta);
-        (self.alpha - 1.0) * x.ln() + (self.beta - 1.0) * (1.0 - x).ln() - ln_beta
+        (self.alpha - 1.0) * x.ln() + (self.beta - 1.0) * (-x).ln_1p() - self.log_norm
     }
 
     fn is_discrete(&self) -> bool {
         false
     }
+
+    fn support(&self) -> (f64, f64) {
+        (0.0, 1.0)
+    }
+}
+
+impl Moments for Beta {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = f64;
+
+    fn mean(&self) -> Option<f64> {
+        Some(self.alpha / (self.alpha + self.beta))
+    }
+
+    fn variance(&self) -> Option<f64> {
+        let total = self.alpha + self.beta;
+        Some(self.alpha * self.beta / (total * total * (total + 1.0)))
+    }
+
+    fn mode(&self) -> Option<f64> {
+        match (self.alpha > 1.0, self.beta > 1.0) {
+            (true, true) => Some((self.alpha - 1.0) / (self.alpha + self.beta - 2.0)),
+            (false, true) => Some(0.0),
+            (true, false) => Some(1.0),
+            (false, false) => None,
+        }
+    }
+
+    /// Returns $\ln B(\alpha,\beta) - (\alpha-1)\psi(\alpha) - (\beta-1)\psi(\beta)
+    /// + (\alpha+\beta-2)\psi(\alpha+\beta)$.
+    fn entropy(&self) -> Option<f64> {
+        let (a, b) = (self.alpha, self.beta);
+        Some(
+            ln_beta(a, b) - (a - 1.0) * digamma(a) - (b - 1.0) * digamma(b)
+                + (a + b - 2.0) * digamma(a + b),
+        )
+    }
+}
+
+impl Univariate for Beta {
+    fn cdf(&self, x: &f64) -> f64 {
+        beta_inc(self.alpha, self.beta, *x)
+    }
+
+    fn sf(&self, x: &f64) -> f64 {
+        // `1 - x` is only exact enough to pass on in the upper half.
+        if *x < 0.5 {
+            1.0 - self.cdf(x)
+        } else {
+            beta_inc(self.beta, self.alpha, 1.0 - x)
+        }
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        continuous_quantile(p, (0.0, 1.0), |x| self.cdf(&x))
+    }
+
+    fn quantile_sf(&self, q: f64) -> f64 {
+        continuous_quantile_sf(q, (0.0, 1.0), |x| self.sf(&x))
+    }
 }
 
 impl std::fmt::Display for Beta {
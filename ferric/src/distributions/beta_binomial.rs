@@ -2,10 +2,13 @@
 
 use rand::Rng;
 use rand_distr::Beta as Beta2;
+use rand_distr::BetaError;
 use rand_distr::Binomial;
 use rand_distr::Distribution as Distribution2;
 
-use crate::distributions::Distribution;
+use crate::distributions::univariate::{discrete_quantile, discrete_quantile_sf};
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
+use crate::special::{ln_beta, ln_gamma};
 
 /// Beta-binomial distribution over $\{0, 1, \ldots, n\}$.
 ///
@@ -37,6 +40,10 @@ pub struct BetaBinomial {
     n: u64,
     alpha: f64,
     beta: f64,
+    /// The Beta prior of the success probability.
+    prior: Beta2<f64>,
+    /// $\ln B(\alpha,\beta)$.
+    log_norm: f64,
 }
 
 impl BetaBinomial {
@@ -47,23 +54,36 @@ impl BetaBinomial {
     ///
     /// Returns `Err` if `n` is zero or `alpha`/`beta` are not strictly
     /// positive.
-    pub fn new(n: u64, alpha: f64, beta: f64) -> Result<BetaBinomial, String> {
+    pub fn new(n: u64, alpha: f64, beta: f64) -> Result<BetaBinomial, DistributionError> {
         if n == 0 {
-            return Err("BetaBinomial: n must be at least 1".into());
+            return Err(DistributionError::InvalidParameter {
+                distribution: "BetaBinomial",
+                name: "n",
+                value: n as f64,
+                constraint: "at least 1",
+            });
         }
-        if alpha <= 0.0 {
-            return Err(format!(
-                "BetaBinomial: illegal alpha `{}` should be greater than 0",
-                alpha
-            ));
-        }
-        if beta <= 0.0 {
-            return Err(format!(
-                "BetaBinomial: illegal beta `{}` should be greater than 0",
-                beta
-            ));
-        }
-        Ok(BetaBinomial { n, alpha, beta })
+        let prior = Beta2::new(alpha, beta).map_err(|e| match e {
+            BetaError::AlphaTooSmall => DistributionError::InvalidParameter {
+                distribution: "BetaBinomial",
+                name: "alpha",
+                value: alpha,
+                constraint: "greater than 0",
+            },
+            BetaError::BetaTooSmall => DistributionError::InvalidParameter {
+                distribution: "BetaBinomial",
+                name: "beta",
+                value: beta,
+                constraint: "greater than 0",
+            },
+        })?;
+        Ok(BetaBinomial {
+            n,
+            alpha,
+            beta,
+            prior,
+            log_norm: ln_beta(alpha, beta),
+        })
     }
 }
 
@@ -73,7 +93,7 @@ impl<R: Rng + ?Sized> Distribution<R> for BetaBinomial {
     /// Draw a sample by first drawing $p \sim \mathrm{Beta}(\alpha, \beta)$,
     /// then $X \sim \mathrm{Binomial}(n, p)$.
     fn sample(&self, rng: &mut R) -> u64 {
-        let p = Beta2::new(self.alpha, self.beta).unwrap().sample(rng);
+        let p = self.prior.sample(rng);
         Binomial::new(self.n, p).unwrap().sample(rng)
     }
 
@@ -84,21 +104,59 @@ impl<R: Rng + ?Sized> Distribution<R> for BetaBinomial {
         if k > n {
             return f64::NEG_INFINITY;
         }
-        log_binom(n, k) + log_beta(k + self.alpha, n - k + self.beta)
-            - log_beta(self.alpha, self.beta)
+        log_binom(n, k) + ln_beta(k + self.alpha, n - k + self.beta) - self.log_norm
     }
 
     fn is_discrete(&self) -> bool {
         true
     }
+
+    fn finite_support(&self) -> Option<Vec<u64>> {
+        Some((0..=self.n).collect())
+    }
 }
 
 fn log_binom(n: f64, k: f64) -> f64 {
-    libm::lgamma(n + 1.0) - libm::lgamma(k + 1.0) - libm::lgamma(n - k + 1.0)
+    ln_gamma(n + 1.0) - ln_gamma(k + 1.0) - ln_gamma(n - k + 1.0)
+}
+
+impl Moments for BetaBinomial {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = u64;
+
+    fn mean(&self) -> Option<f64> {
+        Some(self.n as f64 * self.alpha / (self.alpha + self.beta))
+    }
+
+    fn variance(&self) -> Option<f64> {
+        let n = self.n as f64;
+        let total = self.alpha + self.beta;
+        Some(n * self.alpha * self.beta * (total + n) / (total * total * (total + 1.0)))
+    }
 }
 
-fn log_beta(a: f64, b: f64) -> f64 {
-    libm::lgamma(a) + libm::lgamma(b) - libm::lgamma(a + b)
+impl Univariate for BetaBinomial {
+    fn cdf(&self, x: &u64) -> f64 {
+        let prob = |k| <Self as Distribution>::log_prob(self, &k).exp();
+        (0..=(*x).min(self.n)).map(prob).sum::<f64>().min(1.0)
+    }
+
+    fn sf(&self, x: &u64) -> f64 {
+        let prob = |k| <Self as Distribution>::log_prob(self, &k).exp();
+        (x.saturating_add(1)..=self.n)
+            .map(prob)
+            .sum::<f64>()
+            .min(1.0)
+    }
+
+    fn quantile(&self, p: f64) -> u64 {
+        discrete_quantile(p, (0, self.n), |k| self.cdf(&k))
+    }
+
+    fn quantile_sf(&self, q: f64) -> u64 {
+        discrete_quantile_sf(q, (0, self.n), |k| self.sf(&k))
+    }
 }
 
 impl std::fmt::Display for BetaBinomial {
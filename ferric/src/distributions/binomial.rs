@@ -3,7 +3,9 @@
 use rand_distr::Binomial as Binomial2;
 use rand_distr::Distribution as Distribution2;
 
-use crate::distributions::Distribution;
+use crate::distributions::univariate::{discrete_quantile, discrete_quantile_sf};
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
+use crate::special::{beta_inc, ln_gamma};
 use rand::Rng;
 
 /// Binomial distribution over $\{0, 1, \ldots, n\}$.
@@ -32,6 +34,11 @@ use rand::Rng;
 pub struct Binomial {
     n: u64,
     p: f64,
+    sampler: Binomial2,
+    /// $\ln p$, $\ln(1-p)$ and $\ln n!$.
+    log_p: f64,
+    log_q: f64,
+    log_n_factorial: f64,
 }
 
 impl Binomial {
@@ -40,16 +47,30 @@ impl Binomial {
     /// # Errors
     ///
     /// Returns `Err` if `n` is zero or if `p` is not in the open interval $(0, 1)$.
-    pub fn new(n: u64, p: f64) -> Result<Binomial, String> {
+    pub fn new(n: u64, p: f64) -> Result<Binomial, DistributionError> {
         if n == 0 {
-            Err("Binomial: n must be at least 1".to_string())
+            Err(DistributionError::InvalidParameter {
+                distribution: "Binomial",
+                name: "n",
+                value: n as f64,
+                constraint: "at least 1",
+            })
         } else if !(p > 0.0 && p < 1.0) {
-            Err(format!(
-                "Binomial: illegal p `{}` should be in the open interval (0, 1)",
-                p
-            ))
+            Err(DistributionError::InvalidParameter {
+                distribution: "Binomial",
+                name: "p",
+                value: p,
+                constraint: "in the open interval (0, 1)",
+            })
         } else {
-            Ok(Binomial { n, p })
+            Ok(Binomial {
+                n,
+                p,
+                sampler: Binomial2::new(n, p).expect("p is in (0, 1)"),
+                log_p: p.ln(),
+                log_q: (1.0 - p).ln(),
+                log_n_factorial: ln_gamma(n as f64 + 1.0),
+            })
         }
     }
 }
@@ -58,12 +79,12 @@ impl<R: Rng + ?Sized> Distribution<R> for Binomial {
     type Domain = u64;
 
     fn sample(&self, rng: &mut R) -> u64 {
-        Binomial2::new(self.n, self.p).unwrap().sample(rng)
+        self.sampler.sample(rng)
     }
 
     /// Returns
-    /// $\ln\Gamma(n+1) - \ln\Gamma(k+1) - \ln\Gamma(n-k+1)
-    ///  + k\ln p + (n-k)\ln(1-p)$,
+    /// $\ln\Gamma(n+1) - \ln\Gamma(k+1) - \ln\Gamma(n-k+1) +
+    ///  k\ln p + (n-k)\ln(1-p)$,
     /// or $-\infty$ if $k > n$.
     fn log_prob(&self, k: &u64) -> f64 {
         if *k > self.n {
@@ -71,14 +92,62 @@ impl<R: Rng + ?Sized> Distribution<R> for Binomial {
         }
         let k_f = *k as f64;
         let n_f = self.n as f64;
-        let log_binom =
-            libm::lgamma(n_f + 1.0) - libm::lgamma(k_f + 1.0) - libm::lgamma(n_f - k_f + 1.0);
-        log_binom + k_f * self.p.ln() + (n_f - k_f) * (1.0 - self.p).ln()
+        let log_binom = self.log_n_factorial - ln_gamma(k_f + 1.0) - ln_gamma(n_f - k_f + 1.0);
+        log_binom + k_f * self.log_p + (n_f - k_f) * self.log_q
     }
 
     fn is_discrete(&self) -> bool {
         true
     }
+
+    fn finite_support(&self) -> Option<Vec<u64>> {
+        Some((0..=self.n).collect())
+    }
+}
+
+impl Moments for Binomial {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = u64;
+
+    fn mean(&self) -> Option<f64> {
+        Some(self.n as f64 * self.p)
+    }
+
+    fn variance(&self) -> Option<f64> {
+        Some(self.n as f64 * self.p * (1.0 - self.p))
+    }
+
+    fn mode(&self) -> Option<u64> {
+        Some((((self.n + 1) as f64 * self.p).floor() as u64).min(self.n))
+    }
+}
+
+impl Univariate for Binomial {
+    /// Returns $I_{1-p}(n - k, k + 1)$.
+    fn cdf(&self, x: &u64) -> f64 {
+        if *x >= self.n {
+            1.0
+        } else {
+            beta_inc((self.n - x) as f64, (x + 1) as f64, 1.0 - self.p)
+        }
+    }
+
+    fn sf(&self, x: &u64) -> f64 {
+        if *x >= self.n {
+            0.0
+        } else {
+            beta_inc((x + 1) as f64, (self.n - x) as f64, self.p)
+        }
+    }
+
+    fn quantile(&self, p: f64) -> u64 {
+        discrete_quantile(p, (0, self.n), |k| self.cdf(&k))
+    }
+
+    fn quantile_sf(&self, q: f64) -> u64 {
+        discrete_quantile_sf(q, (0, self.n), |k| self.sf(&k))
+    }
 }
 
 impl std::fmt::Display for Binomial {
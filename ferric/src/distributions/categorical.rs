@@ -2,7 +2,9 @@
 
 use rand::Rng;
 
-use crate::distributions::Distribution;
+use crate::distributions::alias::AliasTable;
+use crate::distributions::univariate::check_probability;
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
 
 /// Categorical distribution over $\{0, 1, \ldots, k-1\}$.
 ///
@@ -16,6 +18,9 @@ use crate::distributions::Distribution;
 /// See [Categorical distribution](https://en.wikipedia.org/wiki/Categorical_distribution)
 /// on Wikipedia for further details.
 ///
+/// Sampling uses an alias table built in [`Categorical::new`], so each draw
+/// takes constant time however many categories there are.
+///
 /// # Examples
 ///
 /// ```
@@ -29,6 +34,9 @@ use crate::distributions::Distribution;
 pub struct Categorical {
     probs: Vec<f64>,
     cumulative: Vec<f64>,
+    /// $\ln p_i$ for each category.
+    log_probs: Vec<f64>,
+    alias: AliasTable,
 }
 
 impl Categorical {
@@ -41,16 +49,27 @@ impl Categorical {
     ///
     /// Returns `Err` if `probs` is empty, any weight is negative, or the
     /// total weight is zero.
-    pub fn new(probs: Vec<f64>) -> Result<Categorical, String> {
+    pub fn new(probs: Vec<f64>) -> Result<Categorical, DistributionError> {
         if probs.is_empty() {
-            return Err("Categorical: probs must not be empty".into());
+            return Err(DistributionError::EmptySupport {
+                distribution: "Categorical",
+                reason: "probs is empty",
+            });
         }
-        if probs.iter().any(|&p| p < 0.0) {
-            return Err("Categorical: all probabilities must be non-negative".into());
+        if let Some(&p) = probs.iter().find(|&&p| p < 0.0) {
+            return Err(DistributionError::InvalidParameter {
+                distribution: "Categorical",
+                name: "probability",
+                value: p,
+                constraint: "non-negative",
+            });
         }
         let total: f64 = probs.iter().sum();
         if total <= 0.0 {
-            return Err("Categorical: probabilities must sum to a positive value".into());
+            return Err(DistributionError::EmptySupport {
+                distribution: "Categorical",
+                reason: "all probabilities are zero",
+            });
         }
         let normalised: Vec<f64> = probs.iter().map(|&p| p / total).collect();
         let mut cumulative = Vec::with_capacity(normalised.len());
@@ -59,9 +78,13 @@ impl Categorical {
             acc += p;
             cumulative.push(acc);
         }
+        let log_probs = normalised.iter().map(|&p| p.ln()).collect();
+        let alias = AliasTable::new(&normalised);
         Ok(Categorical {
             probs: normalised,
             cumulative,
+            log_probs,
+            alias,
         })
     }
 }
@@ -70,24 +93,82 @@ impl<R: Rng + ?Sized> Distribution<R> for Categorical {
     type Domain = usize;
 
     fn sample(&self, rng: &mut R) -> usize {
-        let u: f64 = rng.r#gen();
-        self.cumulative
-            .iter()
-            .position(|&c| u < c)
-            .unwrap_or(self.probs.len() - 1)
+        self.alias.sample(rng)
     }
 
     /// Returns $\ln p_i$, or $-\infty$ if $i$ is out of range.
     fn log_prob(&self, x: &usize) -> f64 {
-        self.probs
-            .get(*x)
-            .map(|&p| if p > 0.0 { p.ln() } else { f64::NEG_INFINITY })
-            .unwrap_or(f64::NEG_INFINITY)
+        self.log_probs.get(*x).copied().unwrap_or(f64::NEG_INFINITY)
     }
 
     fn is_discrete(&self) -> bool {
         true
     }
+
+    fn finite_support(&self) -> Option<Vec<usize>> {
+        Some((0..self.probs.len()).collect())
+    }
+}
+
+impl Moments for Categorical {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = usize;
+
+    fn mean(&self) -> Option<f64> {
+        Some(
+            self.probs
+                .iter()
+                .enumerate()
+                .map(|(k, p)| k as f64 * p)
+                .sum(),
+        )
+    }
+
+    fn variance(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        Some(
+            self.probs
+                .iter()
+                .enumerate()
+                .map(|(k, p)| (k as f64 - mean).powi(2) * p)
+                .sum(),
+        )
+    }
+
+    fn mode(&self) -> Option<usize> {
+        let max = self.probs.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        self.probs.iter().position(|&p| p == max)
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        Some(
+            -self
+                .probs
+                .iter()
+                .filter(|&&p| p > 0.0)
+                .map(|p| p * p.ln())
+                .sum::<f64>(),
+        )
+    }
+}
+
+impl Univariate for Categorical {
+    fn cdf(&self, x: &usize) -> f64 {
+        if *x + 1 >= self.cumulative.len() {
+            1.0
+        } else {
+            self.cumulative[*x]
+        }
+    }
+
+    fn quantile(&self, p: f64) -> usize {
+        check_probability(p);
+        self.cumulative
+            .iter()
+            .position(|&c| c >= p)
+            .unwrap_or(self.cumulative.len() - 1)
+    }
 }
 
 impl std::fmt::Display for Categorical {
@@ -109,7 +190,7 @@ mod tests {
         let dist = Categorical::new(probs.clone()).unwrap();
         println!("dist = {}", dist);
         let trials = 100_000;
-        let mut counts = vec![0u64; 3];
+        let mut counts = [0u64; 3];
         for _ in 0..trials {
             counts[dist.sample(&mut rng)] += 1;
         }
@@ -156,6 +237,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn categorical_zero_weight_never_sampled() {
+        let mut rng = thread_rng();
+        let dist = Categorical::new(vec![1.0, 0.0, 2.0, 0.0]).unwrap();
+        for _ in 0..10_000 {
+            let x = dist.sample(&mut rng);
+            assert!(x == 0 || x == 2, "sampled category {}", x);
+        }
+    }
+
     #[test]
     fn categorical_display() {
         let dist = Categorical::new(vec![0.5, 0.5]).unwrap();
@@ -165,16 +256,25 @@ mod tests {
 
     #[test]
     fn categorical_empty() {
-        assert!(Categorical::new(vec![]).is_err());
+        assert!(matches!(
+            Categorical::new(vec![]),
+            Err(DistributionError::EmptySupport { .. })
+        ));
     }
 
     #[test]
     fn categorical_negative_prob() {
-        assert!(Categorical::new(vec![-1.0, 2.0]).is_err());
+        assert!(matches!(
+            Categorical::new(vec![-1.0, 2.0]),
+            Err(DistributionError::InvalidParameter { value, .. }) if value == -1.0
+        ));
     }
 
     #[test]
     fn categorical_zero_total() {
-        assert!(Categorical::new(vec![0.0, 0.0]).is_err());
+        assert!(matches!(
+            Categorical::new(vec![0.0, 0.0]),
+            Err(DistributionError::EmptySupport { .. })
+        ));
     }
 }
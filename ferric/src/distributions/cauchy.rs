@@ -3,7 +3,8 @@
 use rand_distr::Cauchy as Cauchy2;
 use rand_distr::Distribution as Distribution2;
 
-use crate::distributions::Distribution;
+use crate::distributions::univariate::check_probability;
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
 use rand::Rng;
 
 /// Cauchy (Lorentz) distribution over the real line.
@@ -32,6 +33,9 @@ use rand::Rng;
 pub struct Cauchy {
     median: f64,
     scale: f64,
+    sampler: Cauchy2<f64>,
+    /// $-\ln\pi - \ln\gamma$.
+    log_norm: f64,
 }
 
 impl Cauchy {
@@ -40,15 +44,20 @@ impl Cauchy {
     /// # Errors
     ///
     /// Returns `Err` if `scale` is not strictly positive.
-    pub fn new(median: f64, scale: f64) -> Result<Cauchy, String> {
-        if scale <= 0.0 {
-            Err(format!(
-                "Cauchy: illegal scale `{}` should be greater than 0",
-                scale
-            ))
-        } else {
-            Ok(Cauchy { median, scale })
-        }
+    pub fn new(median: f64, scale: f64) -> Result<Cauchy, DistributionError> {
+        Cauchy2::new(median, scale)
+            .map(|sampler| Cauchy {
+                median,
+                scale,
+                sampler,
+                log_norm: -(std::f64::consts::PI).ln() - scale.ln(),
+            })
+            .map_err(|_| DistributionError::InvalidParameter {
+                distribution: "Cauchy",
+                name: "scale",
+                value: scale,
+                constraint: "greater than 0",
+            })
     }
 }
 
@@ -56,13 +65,13 @@ impl<R: Rng + ?Sized> Distribution<R> for Cauchy {
     type Domain = f64;
 
     fn sample(&self, rng: &mut R) -> f64 {
-        Cauchy2::new(self.median, self.scale).unwrap().sample(rng)
+        self.sampler.sample(rng)
     }
 
     /// Returns $-\ln\pi - \ln\gamma - \ln\!\left[1 + \left(\tfrac{x-x_0}{\gamma}\right)^{2}\right]$.
     fn log_prob(&self, x: &f64) -> f64 {
         let z = (x - self.median) / self.scale;
-        -(std::f64::consts::PI).ln() - self.scale.ln() - (1.0 + z * z).ln()
+        self.log_norm - (1.0 + z * z).ln()
     }
 
     fn is_discrete(&self) -> bool {
@@ -70,6 +79,49 @@ impl<R: Rng + ?Sized> Distribution<R> for Cauchy {
     }
 }
 
+impl Moments for Cauchy {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = f64;
+
+    /// The mean of a Cauchy distribution is undefined.
+    fn mean(&self) -> Option<f64> {
+        None
+    }
+
+    /// The variance of a Cauchy distribution is undefined.
+    fn variance(&self) -> Option<f64> {
+        None
+    }
+
+    fn mode(&self) -> Option<f64> {
+        Some(self.median)
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        Some((4.0 * std::f64::consts::PI * self.scale).ln())
+    }
+}
+
+impl Univariate for Cauchy {
+    fn cdf(&self, x: &f64) -> f64 {
+        0.5 + ((x - self.median) / self.scale).atan() / std::f64::consts::PI
+    }
+
+    fn sf(&self, x: &f64) -> f64 {
+        0.5 - ((x - self.median) / self.scale).atan() / std::f64::consts::PI
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        check_probability(p);
+        match p {
+            0.0 => f64::NEG_INFINITY,
+            1.0 => f64::INFINITY,
+            _ => self.median + self.scale * (std::f64::consts::PI * (p - 0.5)).tan(),
+        }
+    }
+}
+
 impl std::fmt::Display for Cauchy {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -2,7 +2,9 @@
 
 use rand::Rng;
 
-use crate::distributions::{ChiSquared, Distribution};
+use crate::distributions::univariate::{continuous_quantile, continuous_quantile_sf};
+use crate::distributions::{ChiSquared, Distribution, DistributionError, Moments, Univariate};
+use crate::special::{digamma, gamma_p, gamma_q, ln_gamma};
 
 /// Chi distribution over non-negative real numbers.
 ///
@@ -11,6 +13,8 @@ use crate::distributions::{ChiSquared, Distribution};
 pub struct Chi {
     k: f64,
     chi_squared: ChiSquared,
+    /// $(1 - k/2)\ln 2 - \ln\Gamma(k/2)$.
+    log_norm: f64,
 }
 
 impl Chi {
@@ -19,10 +23,19 @@ impl Chi {
     /// # Errors
     ///
     /// Returns `Err` if `k <= 0`.
-    pub fn new(k: f64) -> Result<Chi, String> {
+    pub fn new(k: f64) -> Result<Chi, DistributionError> {
+        if k <= 0.0 {
+            return Err(DistributionError::InvalidParameter {
+                distribution: "Chi",
+                name: "degrees of freedom",
+                value: k,
+                constraint: "greater than 0",
+            });
+        }
         Ok(Chi {
             k,
             chi_squared: ChiSquared::new(k)?,
+            log_norm: (1.0 - k / 2.0) * 2.0_f64.ln() - ln_gamma(k / 2.0),
         })
     }
 }
@@ -38,13 +51,60 @@ impl<R: Rng + ?Sized> Distribution<R> for Chi {
         if *x <= 0.0 {
             return f64::NEG_INFINITY;
         }
-        (1.0 - self.k / 2.0) * 2.0_f64.ln() - libm::lgamma(self.k / 2.0) + (self.k - 1.0) * x.ln()
-            - 0.5 * x * x
+        self.log_norm + (self.k - 1.0) * x.ln() - 0.5 * x * x
     }
 
     fn is_discrete(&self) -> bool {
         false
     }
+
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+impl Moments for Chi {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = f64;
+
+    /// Returns $\sqrt{2}\,\Gamma((k+1)/2) / \Gamma(k/2)$.
+    fn mean(&self) -> Option<f64> {
+        let ln_ratio = ln_gamma((self.k + 1.0) / 2.0) - ln_gamma(self.k / 2.0);
+        Some(2.0_f64.sqrt() * ln_ratio.exp())
+    }
+
+    fn variance(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        Some(self.k - mean * mean)
+    }
+
+    fn mode(&self) -> Option<f64> {
+        Some((self.k - 1.0).max(0.0).sqrt())
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        let k = self.k;
+        Some(ln_gamma(k / 2.0) + 0.5 * (k - 2.0_f64.ln() - (k - 1.0) * digamma(k / 2.0)))
+    }
+}
+
+impl Univariate for Chi {
+    fn cdf(&self, x: &f64) -> f64 {
+        gamma_p(self.k / 2.0, x.max(0.0).powi(2) / 2.0)
+    }
+
+    fn sf(&self, x: &f64) -> f64 {
+        gamma_q(self.k / 2.0, x.max(0.0).powi(2) / 2.0)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        continuous_quantile(p, (0.0, f64::INFINITY), |x| self.cdf(&x))
+    }
+
+    fn quantile_sf(&self, q: f64) -> f64 {
+        continuous_quantile_sf(q, (0.0, f64::INFINITY), |x| self.sf(&x))
+    }
 }
 
 impl std::fmt::Display for Chi {
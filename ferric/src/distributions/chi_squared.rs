@@ -4,7 +4,9 @@ use rand::Rng;
 use rand_distr::ChiSquared as ChiSquared2;
 use rand_distr::Distribution as Distribution2;
 
-use crate::distributions::Distribution;
+use crate::distributions::univariate::{continuous_quantile, continuous_quantile_sf};
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
+use crate::special::{digamma, gamma_p, gamma_q, ln_gamma};
 
 /// Chi-squared distribution over the positive reals.
 ///
@@ -31,6 +33,9 @@ use crate::distributions::Distribution;
 /// ```
 pub struct ChiSquared {
     k: f64,
+    sampler: ChiSquared2<f64>,
+    /// $(k/2)\ln 2 + \ln\Gamma(k/2)$.
+    log_norm: f64,
 }
 
 impl ChiSquared {
@@ -39,15 +44,19 @@ impl ChiSquared {
     /// # Errors
     ///
     /// Returns `Err` if `k` is not strictly positive.
-    pub fn new(k: f64) -> Result<ChiSquared, String> {
-        if k <= 0.0 {
-            Err(format!(
-                "ChiSquared: illegal degrees of freedom `{}` should be greater than 0",
-                k
-            ))
-        } else {
-            Ok(ChiSquared { k })
-        }
+    pub fn new(k: f64) -> Result<ChiSquared, DistributionError> {
+        ChiSquared2::new(k)
+            .map(|sampler| ChiSquared {
+                k,
+                sampler,
+                log_norm: k / 2.0 * 2.0f64.ln() + ln_gamma(k / 2.0),
+            })
+            .map_err(|_| DistributionError::InvalidParameter {
+                distribution: "ChiSquared",
+                name: "degrees of freedom",
+                value: k,
+                constraint: "greater than 0",
+            })
     }
 }
 
@@ -55,7 +64,7 @@ impl<R: Rng + ?Sized> Distribution<R> for ChiSquared {
     type Domain = f64;
 
     fn sample(&self, rng: &mut R) -> f64 {
-        ChiSquared2::new(self.k).unwrap().sample(rng)
+        self.sampler.sample(rng)
     }
 
     /// Returns $(k/2-1)\ln x - x/2 - (k/2)\ln 2 - \ln\Gamma(k/2)$, or
@@ -65,12 +74,57 @@ impl<R: Rng + ?Sized> Distribution<R> for ChiSquared {
             return f64::NEG_INFINITY;
         }
         let half_k = self.k / 2.0;
-        (half_k - 1.0) * x.ln() - x / 2.0 - half_k * 2.0f64.ln() - libm::lgamma(half_k)
+        (half_k - 1.0) * x.ln() - x / 2.0 - self.log_norm
     }
 
     fn is_discrete(&self) -> bool {
         false
     }
+
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+impl Moments for ChiSquared {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = f64;
+
+    fn mean(&self) -> Option<f64> {
+        Some(self.k)
+    }
+
+    fn variance(&self) -> Option<f64> {
+        Some(2.0 * self.k)
+    }
+
+    fn mode(&self) -> Option<f64> {
+        Some((self.k - 2.0).max(0.0))
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        let half_k = self.k / 2.0;
+        Some(half_k + 2.0_f64.ln() + ln_gamma(half_k) + (1.0 - half_k) * digamma(half_k))
+    }
+}
+
+impl Univariate for ChiSquared {
+    fn cdf(&self, x: &f64) -> f64 {
+        gamma_p(self.k / 2.0, x / 2.0)
+    }
+
+    fn sf(&self, x: &f64) -> f64 {
+        gamma_q(self.k / 2.0, x / 2.0)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        continuous_quantile(p, (0.0, f64::INFINITY), |x| self.cdf(&x))
+    }
+
+    fn quantile_sf(&self, q: f64) -> f64 {
+        continuous_quantile_sf(q, (0.0, f64::INFINITY), |x| self.sf(&x))
+    }
 }
 
 impl std::fmt::Display for ChiSquared {
@@ -2,7 +2,7 @@
 
 use rand::Rng;
 
-use crate::distributions::Distribution;
+use crate::distributions::{Distribution, DistributionError};
 
 /// Deterministic point-mass distribution at a single value.
 ///
@@ -14,7 +14,7 @@ pub struct Dirac<T> {
 
 impl<T> Dirac<T> {
     /// Construct a point mass at `value`.
-    pub fn new(value: T) -> Result<Dirac<T>, String> {
+    pub fn new(value: T) -> Result<Dirac<T>, DistributionError> {
         Ok(Dirac { value })
     }
 }
@@ -41,6 +41,10 @@ where
     fn is_discrete(&self) -> bool {
         true
     }
+
+    fn finite_support(&self) -> Option<Vec<T>> {
+        Some(vec![self.value.clone()])
+    }
 }
 
 impl<T: std::fmt::Debug> std::fmt::Display for Dirac<T> {
@@ -3,7 +3,9 @@
 use rand_distr::Dirichlet as Dirichlet2;
 use rand_distr::Distribution as Distribution2;
 
-use crate::distributions::Distribution;
+use crate::distributions::{Distribution, DistributionError, Moments};
+use crate::special::{digamma, ln_gamma};
+use nalgebra::{DMatrix, DVector};
 use rand::Rng;
 
 /// Dirichlet distribution over the probability simplex $\Delta^{K-1}$.
@@ -37,6 +39,9 @@ use rand::Rng;
 /// ```
 pub struct Dirichlet {
     alphas: Vec<f64>,
+    sampler: Dirichlet2<f64>,
+    /// $\ln\Gamma(\alpha_0) - \sum_i \ln\Gamma(\alpha_i)$.
+    log_norm: f64,
 }
 
 impl Dirichlet {
@@ -47,21 +52,33 @@ impl Dirichlet {
     ///
     /// Returns `Err` if fewer than two concentrations are given or any
     /// concentration is not strictly positive.
-    pub fn new(alphas: Vec<f64>) -> Result<Dirichlet, String> {
+    pub fn new(alphas: Vec<f64>) -> Result<Dirichlet, DistributionError> {
         if alphas.len() < 2 {
-            return Err(
-                "Dirichlet: concentration vector must contain at least 2 elements".to_string(),
-            );
+            return Err(DistributionError::DimensionMismatch {
+                distribution: "Dirichlet",
+                name: "alphas",
+                expected: "at least 2".to_string(),
+                found: alphas.len().to_string(),
+            });
         }
         for &a in &alphas {
-            if a <= 0.0 {
-                return Err(format!(
-                    "Dirichlet: all concentrations must be > 0, got {}",
-                    a
-                ));
+            if a.is_nan() || a <= 0.0 {
+                return Err(DistributionError::InvalidParameter {
+                    distribution: "Dirichlet",
+                    name: "concentration",
+                    value: a,
+                    constraint: "greater than 0",
+                });
             }
         }
-        Ok(Dirichlet { alphas })
+        let sampler = Dirichlet2::new(&alphas).expect("alphas are validated above");
+        let sum_alpha: f64 = alphas.iter().sum();
+        let log_norm = ln_gamma(sum_alpha) - alphas.iter().map(|&a| ln_gamma(a)).sum::<f64>();
+        Ok(Dirichlet {
+            alphas,
+            sampler,
+            log_norm,
+        })
     }
 }
 
@@ -69,12 +86,12 @@ impl<R: Rng + ?Sized> Distribution<R> for Dirichlet {
     type Domain = Vec<f64>;
 
     fn sample(&self, rng: &mut R) -> Vec<f64> {
-        Dirichlet2::new(&self.alphas).unwrap().sample(rng)
+        self.sampler.sample(rng)
     }
 
     /// Returns
-    /// $\ln\Gamma(\alpha_0) - \sum_i \ln\Gamma(\alpha_i)
-    ///  + \sum_i (\alpha_i - 1)\ln x_i$
+    /// $\ln\Gamma(\alpha_0) - \sum_i \ln\Gamma(\alpha_i) +
+    ///  \sum_i (\alpha_i - 1)\ln x_i$
     /// where $\alpha_0 = \sum_i \alpha_i$.
     ///
     /// Returns $-\infty$ if `x` has the wrong length, any component is
@@ -91,16 +108,13 @@ impl<R: Rng + ?Sized> Distribution<R> for Dirichlet {
         if (x.iter().sum::<f64>() - 1.0).abs() > 1e-9 {
             return f64::NEG_INFINITY;
         }
-        let sum_alpha: f64 = self.alphas.iter().sum();
-        let log_z: f64 =
-            libm::lgamma(sum_alpha) - self.alphas.iter().map(|&a| libm::lgamma(a)).sum::<f64>();
         let log_kernel: f64 = self
             .alphas
             .iter()
             .zip(x.iter())
             .map(|(&a, &xi)| (a - 1.0) * xi.ln())
             .sum();
-        log_z + log_kernel
+        self.log_norm + log_kernel
     }
 
     fn is_discrete(&self) -> bool {
@@ -108,6 +122,59 @@ impl<R: Rng + ?Sized> Distribution<R> for Dirichlet {
     }
 }
 
+impl Moments for Dirichlet {
+    type Mean = DVector<f64>;
+    type Variance = DMatrix<f64>;
+    type Mode = Vec<f64>;
+
+    fn mean(&self) -> Option<DVector<f64>> {
+        let total: f64 = self.alphas.iter().sum();
+        Some(DVector::from_iterator(
+            self.alphas.len(),
+            self.alphas.iter().map(|a| a / total),
+        ))
+    }
+
+    /// Returns the covariance matrix
+    /// $(\delta_{ij}\tilde\alpha_i - \tilde\alpha_i\tilde\alpha_j) / (\alpha_0 + 1)$
+    /// where $\tilde\alpha = \alpha / \alpha_0$.
+    fn variance(&self) -> Option<DMatrix<f64>> {
+        let total: f64 = self.alphas.iter().sum();
+        let mean = self.mean()?;
+        let cov = (DMatrix::from_diagonal(&mean) - &mean * mean.transpose()) / (total + 1.0);
+        Some(cov)
+    }
+
+    /// Defined when every concentration is greater than 1.
+    fn mode(&self) -> Option<Vec<f64>> {
+        if self.alphas.iter().any(|&a| a <= 1.0) {
+            return None;
+        }
+        let total: f64 = self.alphas.iter().sum();
+        let k = self.alphas.len() as f64;
+        Some(
+            self.alphas
+                .iter()
+                .map(|a| (a - 1.0) / (total - k))
+                .collect(),
+        )
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        let total: f64 = self.alphas.iter().sum();
+        let k = self.alphas.len() as f64;
+        let ln_beta = self.alphas.iter().map(|&a| ln_gamma(a)).sum::<f64>() - ln_gamma(total);
+        Some(
+            ln_beta + (total - k) * digamma(total)
+                - self
+                    .alphas
+                    .iter()
+                    .map(|&a| (a - 1.0) * digamma(a))
+                    .sum::<f64>(),
+        )
+    }
+}
+
 impl std::fmt::Display for Dirichlet {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Dirichlet {{ alphas = {:?} }}", self.alphas)
@@ -127,7 +194,7 @@ mod tests {
         let dist = Dirichlet::new(alphas.clone()).unwrap();
         println!("dist = {}", dist);
         let trials = 10000;
-        let mut sums = vec![0.0f64; 3];
+        let mut sums = [0.0f64; 3];
         for _ in 0..trials {
             let x = dist.sample(&mut rng);
             // each sample must lie in the simplex
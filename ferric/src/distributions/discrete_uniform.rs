@@ -4,7 +4,8 @@ use rand::Rng;
 use rand::distributions::Uniform as RandUniform;
 use rand_distr::Distribution as Distribution2;
 
-use crate::distributions::Distribution;
+use crate::distributions::univariate::check_probability;
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
 
 /// Discrete uniform distribution over $\{a, a+1, \ldots, b\}$.
 ///
@@ -30,6 +31,9 @@ use crate::distributions::Distribution;
 pub struct DiscreteUniform {
     a: i64,
     b: i64,
+    sampler: RandUniform<i64>,
+    /// $-\ln(b - a + 1)$.
+    log_p: f64,
 }
 
 impl DiscreteUniform {
@@ -38,14 +42,21 @@ impl DiscreteUniform {
     /// # Errors
     ///
     /// Returns `Err` if `a > b`.
-    pub fn new(a: i64, b: i64) -> Result<DiscreteUniform, String> {
+    pub fn new(a: i64, b: i64) -> Result<DiscreteUniform, DistributionError> {
         if a > b {
-            Err(format!(
-                "DiscreteUniform: lower bound `{}` must be ≤ upper bound `{}`",
-                a, b
-            ))
+            Err(DistributionError::InvalidParameter {
+                distribution: "DiscreteUniform",
+                name: "upper bound",
+                value: b as f64,
+                constraint: "at least the lower bound",
+            })
         } else {
-            Ok(DiscreteUniform { a, b })
+            Ok(DiscreteUniform {
+                a,
+                b,
+                sampler: RandUniform::new_inclusive(a, b),
+                log_p: -((b as i128 - a as i128 + 1) as f64).ln(),
+            })
         }
     }
 }
@@ -54,7 +65,7 @@ impl<R: Rng + ?Sized> Distribution<R> for DiscreteUniform {
     type Domain = i64;
 
     fn sample(&self, rng: &mut R) -> i64 {
-        RandUniform::new_inclusive(self.a, self.b).sample(rng)
+        self.sampler.sample(rng)
     }
 
     /// Returns $-\ln(b - a + 1)$, or $-\infty$ outside $[a, b]$.
@@ -62,12 +73,48 @@ impl<R: Rng + ?Sized> Distribution<R> for DiscreteUniform {
         if *x < self.a || *x > self.b {
             return f64::NEG_INFINITY;
         }
-        -((self.b - self.a + 1) as f64).ln()
+        self.log_p
     }
 
     fn is_discrete(&self) -> bool {
         true
     }
+
+    fn finite_support(&self) -> Option<Vec<i64>> {
+        Some((self.a..=self.b).collect())
+    }
+}
+
+impl Moments for DiscreteUniform {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = i64;
+
+    fn mean(&self) -> Option<f64> {
+        Some((self.a as f64 + self.b as f64) / 2.0)
+    }
+
+    fn variance(&self) -> Option<f64> {
+        let n = (self.b - self.a + 1) as f64;
+        Some((n * n - 1.0) / 12.0)
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        Some(((self.b - self.a + 1) as f64).ln())
+    }
+}
+
+impl Univariate for DiscreteUniform {
+    fn cdf(&self, x: &i64) -> f64 {
+        let n = (self.b - self.a + 1) as f64;
+        ((x.saturating_sub(self.a) + 1) as f64 / n).clamp(0.0, 1.0)
+    }
+
+    fn quantile(&self, p: f64) -> i64 {
+        check_probability(p);
+        let n = (self.b - self.a + 1) as f64;
+        self.a + ((p * n).ceil() as i64 - 1).max(0)
+    }
 }
 
 impl std::fmt::Display for DiscreteUniform {
@@ -30,6 +30,28 @@ where
     /// data.
     fn log_prob(&self, x: &Self::Domain) -> f64;
 
+    /// The sum of [`log_prob`](Distribution::log_prob) over independent
+    /// observations `xs`, that is the log-likelihood of an i.i.d. sample.
+    ///
+    /// Distributions with a normalizing constant override this to compute
+    /// the constant once for the whole batch rather than once per element.
+    fn log_prob_batch(&self, xs: &[Self::Domain]) -> f64 {
+        xs.iter().map(|x| self.log_prob(x)).sum()
+    }
+
+    /// Write the [`log_prob`](Distribution::log_prob) of each element of
+    /// `xs` into the element of `out` at the same position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `xs` and `out` have different lengths.
+    fn log_prob_batch_into(&self, xs: &[Self::Domain], out: &mut [f64]) {
+        check_batch_len(xs.len(), out.len());
+        for (x, log_prob) in xs.iter().zip(out.iter_mut()) {
+            *log_prob = self.log_prob(x);
+        }
+    }
+
     /// Returns `true` if this distribution is discrete (e.g. [`Bernoulli`],
     /// [`Poisson`]), or `false` if it is continuous (e.g. [`Normal`]).
     ///
@@ -41,4 +63,72 @@ where
     /// [`Poisson`]: crate::distributions::Poisson
     /// [`Normal`]: crate::distributions::Normal
     fn is_discrete(&self) -> bool;
+
+    /// Lower and upper bounds of the support of a real-valued distribution.
+    ///
+    /// Gradient-based samplers use these bounds to map latent variables
+    /// onto the whole real line.  Defaults to $(-\infty, \infty)$, which is
+    /// also returned by distributions over non-real domains.
+    fn support(&self) -> (f64, f64) {
+        (f64::NEG_INFINITY, f64::INFINITY)
+    }
+
+    /// Every distinct value in the support of a discrete distribution, or
+    /// `None` if the support is infinite or cannot be listed.
+    ///
+    /// Exact enumeration (`Model::enumerate`) requires every latent
+    /// variable to be drawn from a distribution that returns `Some` here.
+    /// Defaults to `None`.
+    fn finite_support(&self) -> Option<Vec<Self::Domain>> {
+        None
+    }
+}
+
+/// A boxed distribution is a distribution, so that components of different
+/// types can share a container such as a
+/// [`BoxedMixture`](crate::distributions::BoxedMixture).
+impl<R, D> Distribution<R> for Box<D>
+where
+    R: Rng + ?Sized,
+    D: Distribution<R> + ?Sized,
+{
+    type Domain = D::Domain;
+
+    fn sample(&self, rng: &mut R) -> Self::Domain {
+        (**self).sample(rng)
+    }
+
+    fn log_prob(&self, x: &Self::Domain) -> f64 {
+        (**self).log_prob(x)
+    }
+
+    fn log_prob_batch(&self, xs: &[Self::Domain]) -> f64 {
+        (**self).log_prob_batch(xs)
+    }
+
+    fn log_prob_batch_into(&self, xs: &[Self::Domain], out: &mut [f64]) {
+        (**self).log_prob_batch_into(xs, out)
+    }
+
+    fn is_discrete(&self) -> bool {
+        (**self).is_discrete()
+    }
+
+    fn support(&self) -> (f64, f64) {
+        (**self).support()
+    }
+
+    fn finite_support(&self) -> Option<Vec<Self::Domain>> {
+        (**self).finite_support()
+    }
+}
+
+/// Panic unless `xs` and `out` have the same length, as documented on
+/// [`Distribution::log_prob_batch_into`].
+pub(crate) fn check_batch_len(xs: usize, out: usize) {
+    assert_eq!(
+        xs, out,
+        "log_prob_batch_into: {} observations but {} outputs",
+        xs, out
+    );
 }
@@ -1,48 +1,158 @@
 // Copyright 2022 The Ferric AI Project Developers
 
+use std::collections::HashMap;
+use std::hash::Hash;
+
 use rand::Rng;
-use rand_distr::Distribution as Distribution2;
-use rand_distr::WeightedIndex;
 
-use crate::distributions::Distribution;
+use crate::distributions::alias::AliasTable;
+use crate::distributions::{Distribution, DistributionError};
 
 /// Weighted empirical distribution over a finite set of values.
 ///
-/// Duplicate values are allowed; their weights are added when evaluating
-/// `log_prob`.
+/// Duplicate values are allowed; their weights are added together.  The
+/// distinct values are drawn from an alias table, so `sample` takes constant
+/// time.  `log_prob` compares a value with every distinct value, except for a
+/// distribution built by [`Empirical::new_hashed`], which finds values that
+/// are `Hash + Eq` in constant time.
 pub struct Empirical<T> {
-    values: Vec<T>,
-    probs: Vec<f64>,
+    /// The distinct values in order of first appearance.
+    support: Vec<T>,
+    /// $\ln p$ of each value in `support`.
+    log_probs: Vec<f64>,
+    /// Position of each value in `support`; empty unless built by `new_hashed`.
+    index: HashMap<T, usize>,
+    /// Finds a value in `support`, through `index` if it was built.
+    position: fn(&Empirical<T>, &T) -> Option<usize>,
+    alias: AliasTable,
 }
 
-impl<T> Empirical<T> {
+impl<T: Clone + PartialEq> Empirical<T> {
     /// Construct an empirical distribution from `(value, weight)` pairs.
     ///
-    /// The weights are normalised automatically.
+    /// The weights are normalised automatically.  Merging duplicate values
+    /// compares every value with the distinct ones found so far; prefer
+    /// [`Empirical::new_hashed`] for many distinct values that are
+    /// `Hash + Eq`.
     ///
     /// # Errors
     ///
     /// Returns `Err` if no pairs are supplied, a weight is negative, or all
     /// weights are zero.
-    pub fn new(weighted_values: Vec<(T, f64)>) -> Result<Empirical<T>, String> {
-        if weighted_values.is_empty() {
-            return Err("Empirical: weighted_values must not be empty".into());
+    pub fn new(weighted_values: Vec<(T, f64)>) -> Result<Empirical<T>, DistributionError> {
+        let total = total_weight(&weighted_values)?;
+        let mut support: Vec<T> = Vec::new();
+        let mut probs: Vec<f64> = Vec::new();
+        for (value, weight) in weighted_values {
+            match support.iter().position(|x| *x == value) {
+                Some(i) => probs[i] += weight / total,
+                None => {
+                    support.push(value);
+                    probs.push(weight / total);
+                }
+            }
         }
-        if weighted_values.iter().any(|(_, w)| *w < 0.0) {
-            return Err("Empirical: weights must be non-negative".into());
+        Ok(Empirical::from_support(
+            support,
+            &probs,
+            HashMap::new(),
+            linear_position,
+        ))
+    }
+}
+
+impl<T: Clone + Hash + Eq> Empirical<T> {
+    /// Like [`Empirical::new`], but merges duplicate values and scores values
+    /// through a `HashMap`, so that `log_prob` takes constant time.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if no pairs are supplied, a weight is negative, or all
+    /// weights are zero.
+    pub fn new_hashed(weighted_values: Vec<(T, f64)>) -> Result<Empirical<T>, DistributionError> {
+        let total = total_weight(&weighted_values)?;
+        let mut support = Vec::new();
+        let mut probs: Vec<f64> = Vec::new();
+        let mut index = HashMap::new();
+        for (value, weight) in weighted_values {
+            match index.get(&value) {
+                Some(&i) => probs[i] += weight / total,
+                None => {
+                    index.insert(value.clone(), support.len());
+                    support.push(value);
+                    probs.push(weight / total);
+                }
+            }
         }
-        let total: f64 = weighted_values.iter().map(|(_, w)| *w).sum();
-        if total <= 0.0 {
-            return Err("Empirical: weights must sum to a positive value".into());
+        Ok(Empirical::from_support(
+            support,
+            &probs,
+            index,
+            hashed_position,
+        ))
+    }
+}
+
+impl<T> Empirical<T> {
+    fn from_support(
+        support: Vec<T>,
+        probs: &[f64],
+        index: HashMap<T, usize>,
+        position: fn(&Empirical<T>, &T) -> Option<usize>,
+    ) -> Empirical<T> {
+        Empirical {
+            support,
+            log_probs: probs.iter().map(|p| p.ln()).collect(),
+            index,
+            position,
+            alias: AliasTable::new(probs),
         }
-        let (values, weights): (Vec<T>, Vec<f64>) = weighted_values.into_iter().unzip();
-        Ok(Empirical {
-            values,
-            probs: weights.into_iter().map(|w| w / total).collect(),
-        })
     }
 }
 
+fn linear_position<T: PartialEq>(dist: &Empirical<T>, x: &T) -> Option<usize> {
+    dist.support.iter().position(|value| value == x)
+}
+
+fn hashed_position<T: Hash + Eq>(dist: &Empirical<T>, x: &T) -> Option<usize> {
+    dist.index.get(x).copied()
+}
+
+/// The sum of the weights, or `Err` if they do not define a distribution.
+fn total_weight<T>(weighted_values: &[(T, f64)]) -> Result<f64, DistributionError> {
+    if weighted_values.is_empty() {
+        return Err(DistributionError::EmptySupport {
+            distribution: "Empirical",
+            reason: "weighted_values is empty",
+        });
+    }
+    if let Some((_, w)) = weighted_values.iter().find(|(_, w)| *w < 0.0) {
+        return Err(DistributionError::InvalidParameter {
+            distribution: "Empirical",
+            name: "weight",
+            value: *w,
+            constraint: "non-negative",
+        });
+    }
+    let total: f64 = weighted_values.iter().map(|(_, w)| *w).sum();
+    if total <= 0.0 {
+        return Err(DistributionError::EmptySupport {
+            distribution: "Empirical",
+            reason: "all weights are zero",
+        });
+    }
+    // a NaN or infinite weight makes the total NaN or infinite
+    if !total.is_finite() {
+        return Err(DistributionError::InvalidParameter {
+            distribution: "Empirical",
+            name: "total weight",
+            value: total,
+            constraint: "finite",
+        });
+    }
+    Ok(total)
+}
+
 impl<R, T> Distribution<R> for Empirical<T>
 where
     R: Rng + ?Sized,
@@ -51,28 +161,26 @@ where
     type Domain = T;
 
     fn sample(&self, rng: &mut R) -> T {
-        let index = WeightedIndex::new(&self.probs).unwrap().sample(rng);
-        self.values[index].clone()
+        self.support[self.alias.sample(rng)].clone()
     }
 
     fn log_prob(&self, x: &T) -> f64 {
-        let mut p = 0.0;
-        for (value, prob) in self.values.iter().zip(self.probs.iter()) {
-            if value == x {
-                p += *prob;
-            }
-        }
-        if p > 0.0 { p.ln() } else { f64::NEG_INFINITY }
+        (self.position)(self, x).map_or(f64::NEG_INFINITY, |i| self.log_probs[i])
     }
 
     fn is_discrete(&self) -> bool {
         true
     }
+
+    /// Returns the distinct values in order of first appearance.
+    fn finite_support(&self) -> Option<Vec<T>> {
+        Some(self.support.clone())
+    }
 }
 
 impl<T: std::fmt::Debug> std::fmt::Display for Empirical<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Empirical {{ values = {:?} }}", self.values)
+        write!(f, "Empirical {{ values = {:?} }}", self.support)
     }
 }
 
@@ -99,6 +207,10 @@ mod tests {
             &dist
         ));
         assert!(format!("{}", dist).contains("Empirical"));
+        assert_eq!(
+            <Empirical<&str> as Distribution<ThreadRng>>::finite_support(&dist),
+            Some(vec!["a", "b"])
+        );
     }
 
     #[test]
@@ -121,4 +233,52 @@ mod tests {
         );
         assert!(format!("{}", dist).contains("Empirical"));
     }
+
+    #[test]
+    fn empirical_float_support() {
+        let dist = Empirical::new(vec![(0.5, 1.0), (1.5, 2.0), (0.5, 1.0)]).unwrap();
+        let x = dist.sample(&mut thread_rng());
+        assert!(x == 0.5 || x == 1.5);
+        let lp = <Empirical<f64> as Distribution<ThreadRng>>::log_prob(&dist, &0.5);
+        assert!((lp - 0.5_f64.ln()).abs() < 1e-10);
+        assert_eq!(
+            <Empirical<f64> as Distribution<ThreadRng>>::log_prob(&dist, &1.0),
+            f64::NEG_INFINITY
+        );
+    }
+
+    #[test]
+    fn empirical_hashed_matches_linear() {
+        let weighted_values = vec![("a", 1.0), ("b", 3.0), ("a", 1.0), ("c", 0.0)];
+        let linear = Empirical::new(weighted_values.clone()).unwrap();
+        let hashed = Empirical::new_hashed(weighted_values).unwrap();
+        for x in ["a", "b", "c", "d"] {
+            assert_eq!(
+                <Empirical<&str> as Distribution<ThreadRng>>::log_prob(&linear, &x),
+                <Empirical<&str> as Distribution<ThreadRng>>::log_prob(&hashed, &x)
+            );
+        }
+        assert_eq!(
+            <Empirical<&str> as Distribution<ThreadRng>>::finite_support(&hashed),
+            Some(vec!["a", "b", "c"])
+        );
+        assert!(Empirical::<i32>::new_hashed(vec![]).is_err());
+    }
+
+    #[test]
+    fn empirical_zero_weight_value() {
+        let mut rng = thread_rng();
+        let dist = Empirical::new(vec![('x', 0.0), ('y', 1.0), ('z', 3.0)]).unwrap();
+        for _ in 0..10_000 {
+            assert_ne!(dist.sample(&mut rng), 'x');
+        }
+        assert_eq!(
+            <Empirical<char> as Distribution<ThreadRng>>::log_prob(&dist, &'x'),
+            f64::NEG_INFINITY
+        );
+        assert_eq!(
+            <Empirical<char> as Distribution<ThreadRng>>::finite_support(&dist),
+            Some(vec!['x', 'y', 'z'])
+        );
+    }
 }
@@ -4,7 +4,9 @@ use rand::Rng;
 use rand_distr::Distribution as Distribution2;
 use rand_distr::Gamma as Gamma2;
 
-use crate::distributions::Distribution;
+use crate::distributions::univariate::{continuous_quantile, continuous_quantile_sf};
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
+use crate::special::{digamma, gamma_p, gamma_q, ln_gamma};
 
 /// Erlang distribution over non-negative real numbers.
 ///
@@ -12,6 +14,9 @@ use crate::distributions::Distribution;
 pub struct Erlang {
     k: u64,
     lambda: f64,
+    sampler: Gamma2<f64>,
+    /// $k\ln\lambda - \ln\Gamma(k)$.
+    log_norm: f64,
 }
 
 impl Erlang {
@@ -21,17 +26,39 @@ impl Erlang {
     /// # Errors
     ///
     /// Returns `Err` if `k == 0` or `lambda <= 0`.
-    pub fn new(k: u64, lambda: f64) -> Result<Erlang, String> {
+    pub fn new(k: u64, lambda: f64) -> Result<Erlang, DistributionError> {
         if k == 0 {
-            return Err("Erlang: k must be at least 1".into());
+            return Err(DistributionError::InvalidParameter {
+                distribution: "Erlang",
+                name: "k",
+                value: k as f64,
+                constraint: "at least 1",
+            });
         }
         if lambda <= 0.0 {
-            return Err(format!(
-                "Erlang: illegal lambda `{}` should be greater than 0",
-                lambda
-            ));
+            return Err(DistributionError::InvalidParameter {
+                distribution: "Erlang",
+                name: "lambda",
+                value: lambda,
+                constraint: "greater than 0",
+            });
         }
-        Ok(Erlang { k, lambda })
+        // an infinite rate has a zero scale
+        let sampler = Gamma2::new(k as f64, 1.0 / lambda).map_err(|_| {
+            DistributionError::InvalidParameter {
+                distribution: "Erlang",
+                name: "lambda",
+                value: lambda,
+                constraint: "finite",
+            }
+        })?;
+        let log_norm = k as f64 * lambda.ln() - ln_gamma(k as f64);
+        Ok(Erlang {
+            k,
+            lambda,
+            sampler,
+            log_norm,
+        })
     }
 }
 
@@ -39,9 +66,7 @@ impl<R: Rng + ?Sized> Distribution<R> for Erlang {
     type Domain = f64;
 
     fn sample(&self, rng: &mut R) -> f64 {
-        Gamma2::new(self.k as f64, 1.0 / self.lambda)
-            .unwrap()
-            .sample(rng)
+        self.sampler.sample(rng)
     }
 
     fn log_prob(&self, x: &f64) -> f64 {
@@ -49,12 +74,57 @@ impl<R: Rng + ?Sized> Distribution<R> for Erlang {
             return f64::NEG_INFINITY;
         }
         let k = self.k as f64;
-        k * self.lambda.ln() + (k - 1.0) * x.ln() - self.lambda * x - libm::lgamma(k)
+        self.log_norm + (k - 1.0) * x.ln() - self.lambda * x
     }
 
     fn is_discrete(&self) -> bool {
         false
     }
+
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+impl Moments for Erlang {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = f64;
+
+    fn mean(&self) -> Option<f64> {
+        Some(self.k as f64 / self.lambda)
+    }
+
+    fn variance(&self) -> Option<f64> {
+        Some(self.k as f64 / (self.lambda * self.lambda))
+    }
+
+    fn mode(&self) -> Option<f64> {
+        Some((self.k - 1) as f64 / self.lambda)
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        let k = self.k as f64;
+        Some((1.0 - k) * digamma(k) + ln_gamma(k) - self.lambda.ln() + k)
+    }
+}
+
+impl Univariate for Erlang {
+    fn cdf(&self, x: &f64) -> f64 {
+        gamma_p(self.k as f64, self.lambda * x)
+    }
+
+    fn sf(&self, x: &f64) -> f64 {
+        gamma_q(self.k as f64, self.lambda * x)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        continuous_quantile(p, (0.0, f64::INFINITY), |x| self.cdf(&x))
+    }
+
+    fn quantile_sf(&self, q: f64) -> f64 {
+        continuous_quantile_sf(q, (0.0, f64::INFINITY), |x| self.sf(&x))
+    }
 }
 
 impl std::fmt::Display for Erlang {
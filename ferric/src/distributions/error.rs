@@ -0,0 +1,166 @@
+// Copyright 2022 The Ferric AI Project Developers
+
+/// The reason a distribution could not be constructed from its parameters.
+///
+/// Every distribution constructor returns a `DistributionError` when its
+/// parameters are illegal, so that callers can match on the kind of failure
+/// rather than parse an error message.
+///
+/// # Examples
+///
+/// ```
+/// use ferric::distributions::{DistributionError, Normal};
+///
+/// match Normal::new(0.0, -1.0) {
+///     Err(DistributionError::InvalidParameter { name, value, .. }) => {
+///         assert_eq!(name, "std_dev");
+///         assert_eq!(value, -1.0);
+///     }
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum DistributionError {
+    /// A scalar parameter lies outside its legal range.
+    InvalidParameter {
+        /// The name of the distribution, for example `"Normal"`.
+        distribution: &'static str,
+        /// The name of the offending parameter.
+        name: &'static str,
+        /// The illegal value of the parameter.
+        value: f64,
+        /// The constraint the parameter violates, for example
+        /// `"greater than 0"`.
+        constraint: &'static str,
+    },
+    /// The dimensions of a vector or matrix parameter do not agree with the
+    /// other parameters.
+    DimensionMismatch {
+        /// The name of the distribution.
+        distribution: &'static str,
+        /// The name of the offending parameter.
+        name: &'static str,
+        /// The dimensions the parameter should have, for example `"3×3"`.
+        expected: String,
+        /// The dimensions the parameter has.
+        found: String,
+    },
+    /// A covariance or scale matrix is not symmetric positive definite.
+    NotPositiveDefinite {
+        /// The name of the distribution.
+        distribution: &'static str,
+        /// The name of the offending matrix parameter.
+        name: &'static str,
+    },
+    /// The parameters leave no value with positive probability, for example
+    /// an empty list of categories.
+    EmptySupport {
+        /// The name of the distribution.
+        distribution: &'static str,
+        /// Why the support is empty.
+        reason: &'static str,
+    },
+}
+
+impl DistributionError {
+    /// The name of the distribution that could not be constructed.
+    pub fn distribution(&self) -> &'static str {
+        match self {
+            DistributionError::InvalidParameter { distribution, .. }
+            | DistributionError::DimensionMismatch { distribution, .. }
+            | DistributionError::NotPositiveDefinite { distribution, .. }
+            | DistributionError::EmptySupport { distribution, .. } => distribution,
+        }
+    }
+}
+
+impl std::fmt::Display for DistributionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DistributionError::InvalidParameter {
+                distribution,
+                name,
+                value,
+                constraint,
+            } => write!(
+                f,
+                "{}: illegal {} `{}` should be {}",
+                distribution, name, value, constraint
+            ),
+            DistributionError::DimensionMismatch {
+                distribution,
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{}: illegal dimension of {} `{}` should be {}",
+                distribution, name, found, expected
+            ),
+            DistributionError::NotPositiveDefinite { distribution, name } => {
+                write!(f, "{}: {} is not positive definite", distribution, name)
+            }
+            DistributionError::EmptySupport {
+                distribution,
+                reason,
+            } => write!(f, "{}: empty support, {}", distribution, reason),
+        }
+    }
+}
+
+impl std::error::Error for DistributionError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let err = DistributionError::InvalidParameter {
+            distribution: "Normal",
+            name: "std_dev",
+            value: -1.0,
+            constraint: "greater than 0",
+        };
+        assert_eq!(
+            err.to_string(),
+            "Normal: illegal std_dev `-1` should be greater than 0"
+        );
+        assert_eq!(err.distribution(), "Normal");
+
+        let err = DistributionError::DimensionMismatch {
+            distribution: "MultivariateNormal",
+            name: "cov",
+            expected: "3×3".into(),
+            found: "2×2".into(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "MultivariateNormal: illegal dimension of cov `2×2` should be 3×3"
+        );
+
+        let err = DistributionError::NotPositiveDefinite {
+            distribution: "Wishart",
+            name: "scale",
+        };
+        assert_eq!(err.to_string(), "Wishart: scale is not positive definite");
+
+        let err = DistributionError::EmptySupport {
+            distribution: "Categorical",
+            reason: "probs is empty",
+        };
+        assert_eq!(
+            err.to_string(),
+            "Categorical: empty support, probs is empty"
+        );
+    }
+
+    #[test]
+    fn is_std_error() {
+        let err: Box<dyn std::error::Error> = Box::new(DistributionError::EmptySupport {
+            distribution: "Empirical",
+            reason: "weighted_values is empty",
+        });
+        assert!(err.to_string().starts_with("Empirical"));
+    }
+}
@@ -3,7 +3,9 @@
 use rand_distr::Distribution as Distribution2;
 use rand_distr::Exp as Exp2;
 
-use crate::distributions::Distribution;
+use crate::distributions::distribution::check_batch_len;
+use crate::distributions::univariate::check_probability;
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
 use rand::Rng;
 
 /// Exponential distribution over non-negative reals.
@@ -29,6 +31,9 @@ use rand::Rng;
 /// ```
 pub struct Exponential {
     rate: f64,
+    sampler: Exp2<f64>,
+    /// $\ln\lambda$.
+    log_rate: f64,
 }
 
 impl Exponential {
@@ -37,14 +42,20 @@ impl Exponential {
     /// # Errors
     ///
     /// Returns `Err` if `rate` is not strictly positive.
-    pub fn new(rate: f64) -> Result<Exponential, String> {
-        if rate <= 0.0 {
-            Err(format!(
-                "Exponential: illegal rate `{}` should be greater than 0",
-                rate
-            ))
+    pub fn new(rate: f64) -> Result<Exponential, DistributionError> {
+        if rate.is_nan() || rate <= 0.0 {
+            Err(DistributionError::InvalidParameter {
+                distribution: "Exponential",
+                name: "rate",
+                value: rate,
+                constraint: "greater than 0",
+            })
         } else {
-            Ok(Exponential { rate })
+            Ok(Exponential {
+                rate,
+                sampler: Exp2::new(rate).expect("rate is positive"),
+                log_rate: rate.ln(),
+            })
         }
     }
 }
@@ -53,7 +64,7 @@ impl<R: Rng + ?Sized> Distribution<R> for Exponential {
     type Domain = f64;
 
     fn sample(&self, rng: &mut R) -> f64 {
-        Exp2::new(self.rate).unwrap().sample(rng)
+        self.sampler.sample(rng)
     }
 
     /// Returns $\ln\lambda - \lambda x$ for $x \geq 0$, or $-\infty$ otherwise.
@@ -61,13 +72,81 @@ impl<R: Rng + ?Sized> Distribution<R> for Exponential {
         if *x < 0.0 {
             f64::NEG_INFINITY
         } else {
-            self.rate.ln() - self.rate * x
+            self.log_rate - self.rate * x
+        }
+    }
+
+    fn log_prob_batch(&self, xs: &[f64]) -> f64 {
+        if xs.iter().any(|x| *x < 0.0) {
+            return f64::NEG_INFINITY;
+        }
+        xs.len() as f64 * self.log_rate - self.rate * xs.iter().sum::<f64>()
+    }
+
+    fn log_prob_batch_into(&self, xs: &[f64], out: &mut [f64]) {
+        check_batch_len(xs.len(), out.len());
+        for (x, log_prob) in xs.iter().zip(out.iter_mut()) {
+            *log_prob = if *x < 0.0 {
+                f64::NEG_INFINITY
+            } else {
+                self.log_rate - self.rate * x
+            };
         }
     }
 
     fn is_discrete(&self) -> bool {
         false
     }
+
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+impl Moments for Exponential {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = f64;
+
+    fn mean(&self) -> Option<f64> {
+        Some(1.0 / self.rate)
+    }
+
+    fn variance(&self) -> Option<f64> {
+        Some(1.0 / (self.rate * self.rate))
+    }
+
+    fn mode(&self) -> Option<f64> {
+        Some(0.0)
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        Some(1.0 - self.rate.ln())
+    }
+}
+
+impl Univariate for Exponential {
+    fn cdf(&self, x: &f64) -> f64 {
+        -(-self.rate * x.max(0.0)).exp_m1()
+    }
+
+    fn sf(&self, x: &f64) -> f64 {
+        self.log_sf(x).exp()
+    }
+
+    fn log_sf(&self, x: &f64) -> f64 {
+        -self.rate * x.max(0.0)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        check_probability(p);
+        -(-p).ln_1p() / self.rate
+    }
+
+    fn quantile_sf(&self, q: f64) -> f64 {
+        check_probability(q);
+        -q.ln() / self.rate
+    }
 }
 
 impl std::fmt::Display for Exponential {
@@ -125,4 +204,24 @@ mod tests {
     fn exponential_negative_rate() {
         Exponential::new(-1.0).unwrap();
     }
+
+    #[test]
+    fn exponential_log_prob_batch() {
+        let dist = Exponential::new(2.0).unwrap();
+        let xs = [0.0, 0.3, 1.7];
+        let expected: Vec<f64> = xs
+            .iter()
+            .map(|x| <Exponential as Distribution<ThreadRng>>::log_prob(&dist, x))
+            .collect();
+        let total = <Exponential as Distribution<ThreadRng>>::log_prob_batch(&dist, &xs);
+        assert!((total - expected.iter().sum::<f64>()).abs() < 1e-10);
+        let mut out = [0.0; 3];
+        <Exponential as Distribution<ThreadRng>>::log_prob_batch_into(&dist, &xs, &mut out);
+        assert_eq!(out.to_vec(), expected);
+        // one observation outside the support makes the batch impossible
+        assert_eq!(
+            <Exponential as Distribution<ThreadRng>>::log_prob_batch(&dist, &[1.0, -0.5]),
+            f64::NEG_INFINITY
+        );
+    }
 }
@@ -3,8 +3,11 @@
 use rand::Rng;
 use rand_distr::Distribution as Distribution2;
 use rand_distr::FisherF as FisherF2;
+use rand_distr::FisherFError;
 
-use crate::distributions::Distribution;
+use crate::distributions::univariate::{continuous_quantile, continuous_quantile_sf};
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
+use crate::special::{beta_inc, digamma, ln_beta};
 
 /// Fisher's F-distribution over the positive reals.
 ///
@@ -35,6 +38,9 @@ use crate::distributions::Distribution;
 pub struct FisherF {
     d1: f64,
     d2: f64,
+    sampler: FisherF2<f64>,
+    /// $\tfrac{d_1}{2}\ln\tfrac{d_1}{d_2} - \ln B(d_1/2, d_2/2)$.
+    log_norm: f64,
 }
 
 impl FisherF {
@@ -44,20 +50,28 @@ impl FisherF {
     /// # Errors
     ///
     /// Returns `Err` if `d1` or `d2` is not strictly positive.
-    pub fn new(d1: f64, d2: f64) -> Result<FisherF, String> {
-        if d1 <= 0.0 {
-            Err(format!(
-                "FisherF: illegal d1 `{}` should be greater than 0",
-                d1
-            ))
-        } else if d2 <= 0.0 {
-            Err(format!(
-                "FisherF: illegal d2 `{}` should be greater than 0",
-                d2
-            ))
-        } else {
-            Ok(FisherF { d1, d2 })
-        }
+    pub fn new(d1: f64, d2: f64) -> Result<FisherF, DistributionError> {
+        FisherF2::new(d1, d2)
+            .map(|sampler| FisherF {
+                d1,
+                d2,
+                sampler,
+                log_norm: (d1 / 2.0) * (d1 / d2).ln() - ln_beta(d1 / 2.0, d2 / 2.0),
+            })
+            .map_err(|e| match e {
+                FisherFError::MTooSmall => DistributionError::InvalidParameter {
+                    distribution: "FisherF",
+                    name: "d1",
+                    value: d1,
+                    constraint: "greater than 0",
+                },
+                FisherFError::NTooSmall => DistributionError::InvalidParameter {
+                    distribution: "FisherF",
+                    name: "d2",
+                    value: d2,
+                    constraint: "greater than 0",
+                },
+            })
     }
 }
 
@@ -65,7 +79,7 @@ impl<R: Rng + ?Sized> Distribution<R> for FisherF {
     type Domain = f64;
 
     fn sample(&self, rng: &mut R) -> f64 {
-        FisherF2::new(self.d1, self.d2).unwrap().sample(rng)
+        self.sampler.sample(rng)
     }
 
     /// Returns the log-PDF evaluated at `x`.  Returns $-\infty$ for
@@ -76,16 +90,74 @@ impl<R: Rng + ?Sized> Distribution<R> for FisherF {
         }
         let d1 = self.d1;
         let d2 = self.d2;
-        let log_beta =
-            libm::lgamma(d1 / 2.0) + libm::lgamma(d2 / 2.0) - libm::lgamma((d1 + d2) / 2.0);
-        (d1 / 2.0) * (d1 / d2).ln() + (d1 / 2.0 - 1.0) * x.ln()
-            - ((d1 + d2) / 2.0) * (1.0 + d1 / d2 * x).ln()
-            - log_beta
+        self.log_norm + (d1 / 2.0 - 1.0) * x.ln() - ((d1 + d2) / 2.0) * (1.0 + d1 / d2 * x).ln()
     }
 
     fn is_discrete(&self) -> bool {
         false
     }
+
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+impl Moments for FisherF {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = f64;
+
+    /// Defined for $d_2 > 2$.
+    fn mean(&self) -> Option<f64> {
+        (self.d2 > 2.0).then(|| self.d2 / (self.d2 - 2.0))
+    }
+
+    /// Defined for $d_2 > 4$.
+    fn variance(&self) -> Option<f64> {
+        let (d1, d2) = (self.d1, self.d2);
+        (d2 > 4.0).then(|| 2.0 * d2 * d2 * (d1 + d2 - 2.0) / (d1 * (d2 - 2.0).powi(2) * (d2 - 4.0)))
+    }
+
+    fn mode(&self) -> Option<f64> {
+        Some(((self.d1 - 2.0) / self.d1 * self.d2 / (self.d2 + 2.0)).max(0.0))
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        let (h1, h2) = (self.d1 / 2.0, self.d2 / 2.0);
+        Some(
+            ln_beta(h1, h2) + (1.0 - h1) * digamma(h1) - (1.0 + h2) * digamma(h2)
+                + (h1 + h2) * digamma(h1 + h2)
+                + (self.d2 / self.d1).ln(),
+        )
+    }
+}
+
+impl Univariate for FisherF {
+    fn cdf(&self, x: &f64) -> f64 {
+        let x = x.max(0.0);
+        beta_inc(
+            self.d1 / 2.0,
+            self.d2 / 2.0,
+            self.d1 * x / (self.d1 * x + self.d2),
+        )
+    }
+
+    fn sf(&self, x: &f64) -> f64 {
+        let x = x.max(0.0);
+        beta_inc(
+            self.d2 / 2.0,
+            self.d1 / 2.0,
+            self.d2 / (self.d1 * x + self.d2),
+        )
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        continuous_quantile(p, (0.0, f64::INFINITY), |x| self.cdf(&x))
+    }
+
+    fn quantile_sf(&self, q: f64) -> f64 {
+        continuous_quantile_sf(q, (0.0, f64::INFINITY), |x| self.sf(&x))
+    }
 }
 
 impl std::fmt::Display for FisherF {
@@ -3,8 +3,11 @@
 use rand::Rng;
 use rand_distr::Distribution as Distribution2;
 use rand_distr::Frechet as Frechet2;
+use rand_distr::FrechetError;
 
-use crate::distributions::Distribution;
+use crate::distributions::univariate::check_probability;
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
+use crate::special::{EULER_GAMMA, gamma};
 
 /// Fréchet (type-II extreme value) distribution.
 ///
@@ -35,6 +38,9 @@ pub struct Frechet {
     location: f64,
     scale: f64,
     shape: f64,
+    sampler: Frechet2<f64>,
+    /// $\ln\alpha - \ln s$.
+    log_norm: f64,
 }
 
 impl Frechet {
@@ -44,24 +50,35 @@ impl Frechet {
     /// # Errors
     ///
     /// Returns `Err` if `scale` or `shape` is not strictly positive.
-    pub fn new(location: f64, scale: f64, shape: f64) -> Result<Frechet, String> {
-        if scale <= 0.0 {
-            Err(format!(
-                "Frechet: illegal scale `{}` should be greater than 0",
-                scale
-            ))
-        } else if shape <= 0.0 {
-            Err(format!(
-                "Frechet: illegal shape `{}` should be greater than 0",
-                shape
-            ))
-        } else {
-            Ok(Frechet {
+    pub fn new(location: f64, scale: f64, shape: f64) -> Result<Frechet, DistributionError> {
+        Frechet2::new(location, scale, shape)
+            .map(|sampler| Frechet {
                 location,
                 scale,
                 shape,
+                sampler,
+                log_norm: shape.ln() - scale.ln(),
+            })
+            .map_err(|e| match e {
+                FrechetError::ScaleNotPositive => DistributionError::InvalidParameter {
+                    distribution: "Frechet",
+                    name: "scale",
+                    value: scale,
+                    constraint: "greater than 0 and finite",
+                },
+                FrechetError::ShapeNotPositive => DistributionError::InvalidParameter {
+                    distribution: "Frechet",
+                    name: "shape",
+                    value: shape,
+                    constraint: "greater than 0 and finite",
+                },
+                FrechetError::LocationNotFinite => DistributionError::InvalidParameter {
+                    distribution: "Frechet",
+                    name: "location",
+                    value: location,
+                    constraint: "finite",
+                },
             })
-        }
     }
 }
 
@@ -69,9 +86,7 @@ impl<R: Rng + ?Sized> Distribution<R> for Frechet {
     type Domain = f64;
 
     fn sample(&self, rng: &mut R) -> f64 {
-        Frechet2::new(self.location, self.scale, self.shape)
-            .unwrap()
-            .sample(rng)
+        self.sampler.sample(rng)
     }
 
     /// Returns $\ln\alpha - \ln s - (\alpha+1)\ln z - z^{-\alpha}$ where
@@ -81,12 +96,64 @@ impl<R: Rng + ?Sized> Distribution<R> for Frechet {
             return f64::NEG_INFINITY;
         }
         let z = (x - self.location) / self.scale;
-        self.shape.ln() - self.scale.ln() - (self.shape + 1.0) * z.ln() - z.powf(-self.shape)
+        self.log_norm - (self.shape + 1.0) * z.ln() - z.powf(-self.shape)
     }
 
     fn is_discrete(&self) -> bool {
         false
     }
+
+    fn support(&self) -> (f64, f64) {
+        (self.location, f64::INFINITY)
+    }
+}
+
+impl Moments for Frechet {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = f64;
+
+    /// Defined for shape $\alpha > 1$.
+    fn mean(&self) -> Option<f64> {
+        (self.shape > 1.0).then(|| self.location + self.scale * gamma(1.0 - 1.0 / self.shape))
+    }
+
+    /// Defined for shape $\alpha > 2$.
+    fn variance(&self) -> Option<f64> {
+        let g1 = gamma(1.0 - 1.0 / self.shape);
+        let g2 = gamma(1.0 - 2.0 / self.shape);
+        (self.shape > 2.0).then_some(self.scale * self.scale * (g2 - g1 * g1))
+    }
+
+    fn mode(&self) -> Option<f64> {
+        Some(self.location + self.scale * (self.shape / (1.0 + self.shape)).powf(1.0 / self.shape))
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        Some(1.0 + EULER_GAMMA / self.shape + EULER_GAMMA + (self.scale / self.shape).ln())
+    }
+}
+
+impl Univariate for Frechet {
+    fn cdf(&self, x: &f64) -> f64 {
+        self.log_cdf(x).exp()
+    }
+
+    fn log_cdf(&self, x: &f64) -> f64 {
+        if *x <= self.location {
+            return f64::NEG_INFINITY;
+        }
+        -((x - self.location) / self.scale).powf(-self.shape)
+    }
+
+    fn sf(&self, x: &f64) -> f64 {
+        -self.log_cdf(x).exp_m1()
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        check_probability(p);
+        self.location + self.scale * (-p.ln()).powf(-1.0 / self.shape)
+    }
 }
 
 impl std::fmt::Display for Frechet {
@@ -118,8 +185,8 @@ mod tests {
         }
         let empirical_mean = total / trials as f64;
         // Mean = Gamma(1 - 1/alpha) for alpha > 1
-        let expected_mean = libm::tgamma(1.0 - 1.0 / 3.0);
-        let variance = libm::tgamma(1.0 - 2.0 / 3.0) - expected_mean.powi(2);
+        let expected_mean = gamma(1.0 - 1.0 / 3.0);
+        let variance = gamma(1.0 - 2.0 / 3.0) - expected_mean.powi(2);
         let std = variance.sqrt();
         let err = 5.0 * std / (trials as f64).sqrt();
         assert!((empirical_mean - expected_mean).abs() < err);
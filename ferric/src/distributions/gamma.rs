@@ -2,8 +2,12 @@
 
 use rand_distr::Distribution as Distribution2;
 use rand_distr::Gamma as Gamma2;
+use rand_distr::GammaError;
 
-use crate::distributions::Distribution;
+use crate::distributions::distribution::check_batch_len;
+use crate::distributions::univariate::{continuous_quantile, continuous_quantile_sf};
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
+use crate::special::{digamma, gamma_p, gamma_q, ln_gamma};
 use rand::Rng;
 
 /// Gamma distribution over positive reals.
@@ -33,6 +37,9 @@ use rand::Rng;
 pub struct Gamma {
     shape: f64,
     scale: f64,
+    sampler: Gamma2<f64>,
+    /// $k\ln\theta + \ln\Gamma(k)$.
+    log_norm: f64,
 }
 
 impl Gamma {
@@ -41,20 +48,34 @@ impl Gamma {
     /// # Errors
     ///
     /// Returns `Err` if `shape` or `scale` is not strictly positive.
-    pub fn new(shape: f64, scale: f64) -> Result<Gamma, String> {
-        if shape <= 0.0 {
-            Err(format!(
-                "Gamma: illegal shape `{}` should be greater than 0",
-                shape
-            ))
-        } else if scale <= 0.0 {
-            Err(format!(
-                "Gamma: illegal scale `{}` should be greater than 0",
-                scale
-            ))
-        } else {
-            Ok(Gamma { shape, scale })
-        }
+    pub fn new(shape: f64, scale: f64) -> Result<Gamma, DistributionError> {
+        Gamma2::new(shape, scale)
+            .map(|sampler| Gamma {
+                shape,
+                scale,
+                sampler,
+                log_norm: shape * scale.ln() + ln_gamma(shape),
+            })
+            .map_err(|e| match e {
+                GammaError::ShapeTooSmall => DistributionError::InvalidParameter {
+                    distribution: "Gamma",
+                    name: "shape",
+                    value: shape,
+                    constraint: "greater than 0",
+                },
+                GammaError::ScaleTooSmall => DistributionError::InvalidParameter {
+                    distribution: "Gamma",
+                    name: "scale",
+                    value: scale,
+                    constraint: "greater than 0",
+                },
+                GammaError::ScaleTooLarge => DistributionError::InvalidParameter {
+                    distribution: "Gamma",
+                    name: "scale",
+                    value: scale,
+                    constraint: "finite",
+                },
+            })
     }
 }
 
@@ -62,7 +83,7 @@ impl<R: Rng + ?Sized> Distribution<R> for Gamma {
     type Domain = f64;
 
     fn sample(&self, rng: &mut R) -> f64 {
-        Gamma2::new(self.shape, self.scale).unwrap().sample(rng)
+        self.sampler.sample(rng)
     }
 
     /// Returns $(k-1)\ln x - x/\theta - k\ln\theta - \ln\Gamma(k)$.
@@ -70,15 +91,78 @@ impl<R: Rng + ?Sized> Distribution<R> for Gamma {
         if *x <= 0.0 {
             return f64::NEG_INFINITY;
         }
-        (self.shape - 1.0) * x.ln()
-            - x / self.scale
-            - self.shape * self.scale.ln()
-            - libm::lgamma(self.shape)
+        (self.shape - 1.0) * x.ln() - x / self.scale - self.log_norm
+    }
+
+    fn log_prob_batch(&self, xs: &[f64]) -> f64 {
+        if xs.iter().any(|x| *x <= 0.0) {
+            return f64::NEG_INFINITY;
+        }
+        let sum_ln_x: f64 = xs.iter().map(|x| x.ln()).sum();
+        let sum_x: f64 = xs.iter().sum();
+        (self.shape - 1.0) * sum_ln_x - sum_x / self.scale - xs.len() as f64 * self.log_norm
+    }
+
+    fn log_prob_batch_into(&self, xs: &[f64], out: &mut [f64]) {
+        check_batch_len(xs.len(), out.len());
+        let inv_scale = 1.0 / self.scale;
+        for (x, log_prob) in xs.iter().zip(out.iter_mut()) {
+            *log_prob = if *x <= 0.0 {
+                f64::NEG_INFINITY
+            } else {
+                (self.shape - 1.0) * x.ln() - x * inv_scale - self.log_norm
+            };
+        }
     }
 
     fn is_discrete(&self) -> bool {
         false
     }
+
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+impl Moments for Gamma {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = f64;
+
+    fn mean(&self) -> Option<f64> {
+        Some(self.shape * self.scale)
+    }
+
+    fn variance(&self) -> Option<f64> {
+        Some(self.shape * self.scale * self.scale)
+    }
+
+    fn mode(&self) -> Option<f64> {
+        Some((self.shape - 1.0).max(0.0) * self.scale)
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        let k = self.shape;
+        Some(k + self.scale.ln() + ln_gamma(k) + (1.0 - k) * digamma(k))
+    }
+}
+
+impl Univariate for Gamma {
+    fn cdf(&self, x: &f64) -> f64 {
+        gamma_p(self.shape, x / self.scale)
+    }
+
+    fn sf(&self, x: &f64) -> f64 {
+        gamma_q(self.shape, x / self.scale)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        continuous_quantile(p, (0.0, f64::INFINITY), |x| self.cdf(&x))
+    }
+
+    fn quantile_sf(&self, q: f64) -> f64 {
+        continuous_quantile_sf(q, (0.0, f64::INFINITY), |x| self.sf(&x))
+    }
 }
 
 impl std::fmt::Display for Gamma {
@@ -139,4 +223,25 @@ mod tests {
     fn gamma_zero_scale() {
         Gamma::new(1.0, 0.0).unwrap();
     }
+
+    #[test]
+    fn gamma_log_prob_batch() {
+        let dist = Gamma::new(3.0, 2.0).unwrap();
+        let xs = [0.5, 2.0, 7.5];
+        let expected: Vec<f64> = xs
+            .iter()
+            .map(|x| <Gamma as Distribution<ThreadRng>>::log_prob(&dist, x))
+            .collect();
+        let total = <Gamma as Distribution<ThreadRng>>::log_prob_batch(&dist, &xs);
+        assert!((total - expected.iter().sum::<f64>()).abs() < 1e-10);
+        let mut out = [0.0; 3];
+        <Gamma as Distribution<ThreadRng>>::log_prob_batch_into(&dist, &xs, &mut out);
+        for (lp, expected) in out.iter().zip(expected) {
+            assert!((lp - expected).abs() < 1e-12);
+        }
+        assert_eq!(
+            <Gamma as Distribution<ThreadRng>>::log_prob_batch(&dist, &[1.0, 0.0]),
+            f64::NEG_INFINITY
+        );
+    }
 }
@@ -3,7 +3,8 @@
 use rand_distr::Distribution as Distribution2;
 use rand_distr::Geometric as Geometric2;
 
-use crate::distributions::Distribution;
+use crate::distributions::univariate::{discrete_quantile, discrete_quantile_sf};
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
 use rand::Rng;
 
 /// Geometric distribution over $\{0, 1, 2, \ldots\}$.
@@ -30,6 +31,10 @@ use rand::Rng;
 /// ```
 pub struct Geometric {
     p: f64,
+    sampler: Geometric2,
+    /// $\ln p$ and $\ln(1-p)$.
+    log_p: f64,
+    log_q: f64,
 }
 
 impl Geometric {
@@ -38,14 +43,21 @@ impl Geometric {
     /// # Errors
     ///
     /// Returns `Err` if `p` is not in the half-open interval $(0, 1]$.
-    pub fn new(p: f64) -> Result<Geometric, String> {
+    pub fn new(p: f64) -> Result<Geometric, DistributionError> {
         if !(p > 0.0 && p <= 1.0) {
-            Err(format!(
-                "Geometric: illegal p `{}` should be in the interval (0, 1]",
-                p
-            ))
+            Err(DistributionError::InvalidParameter {
+                distribution: "Geometric",
+                name: "p",
+                value: p,
+                constraint: "in the interval (0, 1]",
+            })
         } else {
-            Ok(Geometric { p })
+            Ok(Geometric {
+                p,
+                sampler: Geometric2::new(p).expect("p is in (0, 1]"),
+                log_p: p.ln(),
+                log_q: (1.0 - p).ln(),
+            })
         }
     }
 }
@@ -54,7 +66,7 @@ impl<R: Rng + ?Sized> Distribution<R> for Geometric {
     type Domain = u64;
 
     fn sample(&self, rng: &mut R) -> u64 {
-        Geometric2::new(self.p).unwrap().sample(rng)
+        self.sampler.sample(rng)
     }
 
     /// Returns $\ln p + k\,\ln(1-p)$, handling $p = 1$ exactly.
@@ -63,7 +75,7 @@ impl<R: Rng + ?Sized> Distribution<R> for Geometric {
             // P(X=0) = 1, P(X>0) = 0
             if *k == 0 { 0.0 } else { f64::NEG_INFINITY }
         } else {
-            self.p.ln() + (*k as f64) * (1.0 - self.p).ln()
+            self.log_p + (*k as f64) * self.log_q
         }
     }
 
@@ -72,6 +84,58 @@ impl<R: Rng + ?Sized> Distribution<R> for Geometric {
     }
 }
 
+impl Moments for Geometric {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = u64;
+
+    fn mean(&self) -> Option<f64> {
+        Some((1.0 - self.p) / self.p)
+    }
+
+    fn variance(&self) -> Option<f64> {
+        Some((1.0 - self.p) / (self.p * self.p))
+    }
+
+    fn mode(&self) -> Option<u64> {
+        Some(0)
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        let q = 1.0 - self.p;
+        let xlnx = |x: f64| if x > 0.0 { x * x.ln() } else { 0.0 };
+        Some(-(xlnx(q) + xlnx(self.p)) / self.p)
+    }
+}
+
+impl Univariate for Geometric {
+    /// Returns $1 - (1-p)^{k+1}$.
+    fn cdf(&self, x: &u64) -> f64 {
+        -self.log_sf(x).exp_m1()
+    }
+
+    fn sf(&self, x: &u64) -> f64 {
+        self.log_sf(x).exp()
+    }
+
+    fn log_sf(&self, x: &u64) -> f64 {
+        if self.p == 1.0 {
+            return f64::NEG_INFINITY;
+        }
+        (*x as f64 + 1.0) * (-self.p).ln_1p()
+    }
+
+    fn quantile(&self, p: f64) -> u64 {
+        let upper = if self.p == 1.0 { 0 } else { u64::MAX };
+        discrete_quantile(p, (0, upper), |k| self.cdf(&k))
+    }
+
+    fn quantile_sf(&self, q: f64) -> u64 {
+        let upper = if self.p == 1.0 { 0 } else { u64::MAX };
+        discrete_quantile_sf(q, (0, upper), |k| self.sf(&k))
+    }
+}
+
 impl std::fmt::Display for Geometric {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Geometric {{ p = {} }}", self.p)
@@ -3,8 +3,11 @@
 use rand::Rng;
 use rand_distr::Distribution as Distribution2;
 use rand_distr::Gumbel as Gumbel2;
+use rand_distr::GumbelError;
 
-use crate::distributions::Distribution;
+use crate::distributions::univariate::check_probability;
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
+use crate::special::EULER_GAMMA;
 
 /// Gumbel (type-I extreme value) distribution over the reals.
 ///
@@ -33,6 +36,9 @@ use crate::distributions::Distribution;
 pub struct Gumbel {
     mu: f64,
     beta: f64,
+    sampler: Gumbel2<f64>,
+    /// $\ln\beta$.
+    log_beta: f64,
 }
 
 impl Gumbel {
@@ -42,15 +48,28 @@ impl Gumbel {
     /// # Errors
     ///
     /// Returns `Err` if `beta` is not strictly positive.
-    pub fn new(mu: f64, beta: f64) -> Result<Gumbel, String> {
-        if beta <= 0.0 {
-            Err(format!(
-                "Gumbel: illegal scale `{}` should be greater than 0",
-                beta
-            ))
-        } else {
-            Ok(Gumbel { mu, beta })
-        }
+    pub fn new(mu: f64, beta: f64) -> Result<Gumbel, DistributionError> {
+        Gumbel2::new(mu, beta)
+            .map(|sampler| Gumbel {
+                mu,
+                beta,
+                sampler,
+                log_beta: beta.ln(),
+            })
+            .map_err(|e| match e {
+                GumbelError::ScaleNotPositive => DistributionError::InvalidParameter {
+                    distribution: "Gumbel",
+                    name: "scale",
+                    value: beta,
+                    constraint: "greater than 0 and finite",
+                },
+                GumbelError::LocationNotFinite => DistributionError::InvalidParameter {
+                    distribution: "Gumbel",
+                    name: "location",
+                    value: mu,
+                    constraint: "finite",
+                },
+            })
     }
 }
 
@@ -58,13 +77,13 @@ impl<R: Rng + ?Sized> Distribution<R> for Gumbel {
     type Domain = f64;
 
     fn sample(&self, rng: &mut R) -> f64 {
-        Gumbel2::new(self.mu, self.beta).unwrap().sample(rng)
+        self.sampler.sample(rng)
     }
 
     /// Returns $-(x-\mu)/\beta - e^{-(x-\mu)/\beta} - \ln\beta$.
     fn log_prob(&self, x: &f64) -> f64 {
         let z = (x - self.mu) / self.beta;
-        -z - (-z).exp() - self.beta.ln()
+        -z - (-z).exp() - self.log_beta
     }
 
     fn is_discrete(&self) -> bool {
@@ -72,6 +91,47 @@ impl<R: Rng + ?Sized> Distribution<R> for Gumbel {
     }
 }
 
+impl Moments for Gumbel {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = f64;
+
+    fn mean(&self) -> Option<f64> {
+        Some(self.mu + self.beta * EULER_GAMMA)
+    }
+
+    fn variance(&self) -> Option<f64> {
+        Some(std::f64::consts::PI.powi(2) * self.beta * self.beta / 6.0)
+    }
+
+    fn mode(&self) -> Option<f64> {
+        Some(self.mu)
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        Some(self.beta.ln() + EULER_GAMMA + 1.0)
+    }
+}
+
+impl Univariate for Gumbel {
+    fn cdf(&self, x: &f64) -> f64 {
+        self.log_cdf(x).exp()
+    }
+
+    fn log_cdf(&self, x: &f64) -> f64 {
+        -(-(x - self.mu) / self.beta).exp()
+    }
+
+    fn sf(&self, x: &f64) -> f64 {
+        -self.log_cdf(x).exp_m1()
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        check_probability(p);
+        self.mu - self.beta * (-p.ln()).ln()
+    }
+}
+
 impl std::fmt::Display for Gumbel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Gumbel {{ mu = {}, beta = {} }}", self.mu, self.beta)
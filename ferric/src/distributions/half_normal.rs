@@ -4,7 +4,9 @@ use rand::Rng;
 use rand_distr::Distribution as Distribution2;
 use rand_distr::Normal;
 
-use crate::distributions::Distribution;
+use crate::distributions::univariate::check_probability;
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
+use crate::special::{erf, erfc, normal_quantile};
 
 /// Half-normal distribution over the non-negative reals.
 ///
@@ -32,6 +34,9 @@ use crate::distributions::Distribution;
 /// ```
 pub struct HalfNormal {
     sigma: f64,
+    sampler: Normal<f64>,
+    /// $\ln\sqrt{2/\pi} - \ln\sigma$.
+    log_norm: f64,
 }
 
 impl HalfNormal {
@@ -40,14 +45,27 @@ impl HalfNormal {
     /// # Errors
     ///
     /// Returns `Err` if `sigma` is not strictly positive.
-    pub fn new(sigma: f64) -> Result<HalfNormal, String> {
+    pub fn new(sigma: f64) -> Result<HalfNormal, DistributionError> {
         if sigma <= 0.0 {
-            Err(format!(
-                "HalfNormal: illegal scale `{}` should be greater than 0",
-                sigma
-            ))
+            Err(DistributionError::InvalidParameter {
+                distribution: "HalfNormal",
+                name: "scale",
+                value: sigma,
+                constraint: "greater than 0",
+            })
         } else {
-            Ok(HalfNormal { sigma })
+            Normal::new(0.0, sigma)
+                .map(|sampler| HalfNormal {
+                    sigma,
+                    sampler,
+                    log_norm: (2.0 / std::f64::consts::PI).sqrt().ln() - sigma.ln(),
+                })
+                .map_err(|_| DistributionError::InvalidParameter {
+                    distribution: "HalfNormal",
+                    name: "scale",
+                    value: sigma,
+                    constraint: "finite",
+                })
         }
     }
 }
@@ -57,7 +75,7 @@ impl<R: Rng + ?Sized> Distribution<R> for HalfNormal {
 
     /// Draw a sample as $|Z|$ where $Z \sim \mathrm{Normal}(0, \sigma^2)$.
     fn sample(&self, rng: &mut R) -> f64 {
-        Normal::new(0.0, self.sigma).unwrap().sample(rng).abs()
+        self.sampler.sample(rng).abs()
     }
 
     /// Returns $\ln\sqrt{2/\pi} - \ln\sigma - x^2/(2\sigma^2)$, or
@@ -66,14 +84,58 @@ impl<R: Rng + ?Sized> Distribution<R> for HalfNormal {
         if *x < 0.0 {
             return f64::NEG_INFINITY;
         }
-        (2.0 / std::f64::consts::PI).sqrt().ln()
-            - self.sigma.ln()
-            - x * x / (2.0 * self.sigma * self.sigma)
+        self.log_norm - x * x / (2.0 * self.sigma * self.sigma)
     }
 
     fn is_discrete(&self) -> bool {
         false
     }
+
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+impl Moments for HalfNormal {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = f64;
+
+    fn mean(&self) -> Option<f64> {
+        Some(self.sigma * (2.0 / std::f64::consts::PI).sqrt())
+    }
+
+    fn variance(&self) -> Option<f64> {
+        Some(self.sigma * self.sigma * (1.0 - 2.0 / std::f64::consts::PI))
+    }
+
+    fn mode(&self) -> Option<f64> {
+        Some(0.0)
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        Some(0.5 * (std::f64::consts::PI * self.sigma * self.sigma / 2.0).ln() + 0.5)
+    }
+}
+
+impl Univariate for HalfNormal {
+    fn cdf(&self, x: &f64) -> f64 {
+        erf(x.max(0.0) / (self.sigma * std::f64::consts::SQRT_2))
+    }
+
+    fn sf(&self, x: &f64) -> f64 {
+        erfc(x.max(0.0) / (self.sigma * std::f64::consts::SQRT_2))
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        check_probability(p);
+        self.sigma * normal_quantile(0.5 + 0.5 * p)
+    }
+
+    fn quantile_sf(&self, q: f64) -> f64 {
+        check_probability(q);
+        -self.sigma * normal_quantile(0.5 * q)
+    }
 }
 
 impl std::fmt::Display for HalfNormal {
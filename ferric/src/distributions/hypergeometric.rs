@@ -4,7 +4,9 @@ use rand::Rng;
 use rand_distr::Distribution as Distribution2;
 use rand_distr::Hypergeometric as Hypergeometric2;
 
-use crate::distributions::Distribution;
+use crate::distributions::univariate::{discrete_quantile, discrete_quantile_sf};
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
+use crate::special::ln_gamma;
 
 /// Hypergeometric distribution.
 ///
@@ -34,6 +36,9 @@ pub struct Hypergeometric {
     n_total: u64,
     k_success: u64,
     n_draws: u64,
+    sampler: Hypergeometric2,
+    /// $\ln\binom{N}{n}$.
+    log_norm: f64,
 }
 
 impl Hypergeometric {
@@ -48,23 +53,34 @@ impl Hypergeometric {
     /// # Errors
     ///
     /// Returns `Err` if parameters are invalid.
-    pub fn new(n_total: u64, k_success: u64, n_draws: u64) -> Result<Hypergeometric, String> {
+    pub fn new(
+        n_total: u64,
+        k_success: u64,
+        n_draws: u64,
+    ) -> Result<Hypergeometric, DistributionError> {
         if k_success > n_total {
-            return Err(format!(
-                "Hypergeometric: k_success `{}` must be ≤ n_total `{}`",
-                k_success, n_total
-            ));
+            return Err(DistributionError::InvalidParameter {
+                distribution: "Hypergeometric",
+                name: "k_success",
+                value: k_success as f64,
+                constraint: "at most n_total",
+            });
         }
         if n_draws > n_total {
-            return Err(format!(
-                "Hypergeometric: n_draws `{}` must be ≤ n_total `{}`",
-                n_draws, n_total
-            ));
+            return Err(DistributionError::InvalidParameter {
+                distribution: "Hypergeometric",
+                name: "n_draws",
+                value: n_draws as f64,
+                constraint: "at most n_total",
+            });
         }
         Ok(Hypergeometric {
             n_total,
             k_success,
             n_draws,
+            sampler: Hypergeometric2::new(n_total, k_success, n_draws)
+                .expect("parameters are validated above"),
+            log_norm: log_binom(n_total as f64, n_draws as f64),
         })
     }
 }
@@ -73,9 +89,7 @@ impl<R: Rng + ?Sized> Distribution<R> for Hypergeometric {
     type Domain = u64;
 
     fn sample(&self, rng: &mut R) -> u64 {
-        Hypergeometric2::new(self.n_total, self.k_success, self.n_draws)
-            .unwrap()
-            .sample(rng)
+        self.sampler.sample(rng)
     }
 
     /// Returns the log-PMF.  Uses log-factorials via `lgamma` to avoid
@@ -93,16 +107,90 @@ impl<R: Rng + ?Sized> Distribution<R> for Hypergeometric {
             return f64::NEG_INFINITY;
         }
 
-        log_binom(big_k, k) + log_binom(n - big_k, n_draws - k) - log_binom(n, n_draws)
+        log_binom(big_k, k) + log_binom(n - big_k, n_draws - k) - self.log_norm
     }
 
     fn is_discrete(&self) -> bool {
         true
     }
+
+    fn finite_support(&self) -> Option<Vec<u64>> {
+        let k_min = (self.n_draws + self.k_success).saturating_sub(self.n_total);
+        let k_max = self.k_success.min(self.n_draws);
+        Some((k_min..=k_max).collect())
+    }
 }
 
 fn log_binom(n: f64, k: f64) -> f64 {
-    libm::lgamma(n + 1.0) - libm::lgamma(k + 1.0) - libm::lgamma(n - k + 1.0)
+    ln_gamma(n + 1.0) - ln_gamma(k + 1.0) - ln_gamma(n - k + 1.0)
+}
+
+impl Moments for Hypergeometric {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = u64;
+
+    fn mean(&self) -> Option<f64> {
+        if self.n_total == 0 {
+            return Some(0.0);
+        }
+        Some(self.n_draws as f64 * self.k_success as f64 / self.n_total as f64)
+    }
+
+    fn variance(&self) -> Option<f64> {
+        if self.n_total <= 1 {
+            return Some(0.0);
+        }
+        let (total, success, draws) = (
+            self.n_total as f64,
+            self.k_success as f64,
+            self.n_draws as f64,
+        );
+        Some(
+            draws
+                * (success / total)
+                * ((total - success) / total)
+                * ((total - draws) / (total - 1.0)),
+        )
+    }
+
+    fn mode(&self) -> Option<u64> {
+        Some((self.n_draws + 1) * (self.k_success + 1) / (self.n_total + 2))
+    }
+}
+
+impl Hypergeometric {
+    /// The smallest and largest number of successes with positive
+    /// probability.
+    fn support_bounds(&self) -> (u64, u64) {
+        let lower = (self.n_draws + self.k_success).saturating_sub(self.n_total);
+        (lower, self.k_success.min(self.n_draws))
+    }
+}
+
+impl Univariate for Hypergeometric {
+    fn cdf(&self, x: &u64) -> f64 {
+        let (lower, upper) = self.support_bounds();
+        let prob = |k| <Self as Distribution>::log_prob(self, &k).exp();
+        (lower..=(*x).min(upper)).map(prob).sum::<f64>().min(1.0)
+    }
+
+    fn sf(&self, x: &u64) -> f64 {
+        let (lower, upper) = self.support_bounds();
+        let prob = |k| <Self as Distribution>::log_prob(self, &k).exp();
+        (x.saturating_add(1).max(lower)..=upper)
+            .map(prob)
+            .sum::<f64>()
+            .min(1.0)
+    }
+
+    fn quantile(&self, p: f64) -> u64 {
+        discrete_quantile(p, self.support_bounds(), |k| self.cdf(&k))
+    }
+
+    fn quantile_sf(&self, q: f64) -> u64 {
+        discrete_quantile_sf(q, self.support_bounds(), |k| self.sf(&k))
+    }
 }
 
 impl std::fmt::Display for Hypergeometric {
@@ -163,6 +251,19 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn hypergeometric_finite_support() {
+        // Hyp(10, 7, 5): at least 2 and at most 5 successes
+        let dist = Hypergeometric::new(10, 7, 5).unwrap();
+        let support = <Hypergeometric as Distribution<ThreadRng>>::finite_support(&dist).unwrap();
+        assert_eq!(support, vec![2, 3, 4, 5]);
+        let total: f64 = support
+            .iter()
+            .map(|k| <Hypergeometric as Distribution<ThreadRng>>::log_prob(&dist, k).exp())
+            .sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
     #[test]
     fn hypergeometric_display() {
         let dist = Hypergeometric::new(50, 10, 5).unwrap();
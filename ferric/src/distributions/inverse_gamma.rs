@@ -4,7 +4,9 @@ use rand::Rng;
 use rand_distr::Distribution as Distribution2;
 use rand_distr::Gamma as Gamma2;
 
-use crate::distributions::Distribution;
+use crate::distributions::univariate::{continuous_quantile, continuous_quantile_sf};
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
+use crate::special::{digamma, gamma_p, gamma_q, ln_gamma};
 
 /// Inverse-gamma distribution over the positive reals.
 ///
@@ -34,6 +36,10 @@ use crate::distributions::Distribution;
 pub struct InverseGamma {
     alpha: f64,
     beta: f64,
+    /// The distribution of $1/X$.
+    reciprocal: Gamma2<f64>,
+    /// $\alpha\ln\beta - \ln\Gamma(\alpha)$.
+    log_norm: f64,
 }
 
 impl InverseGamma {
@@ -43,19 +49,36 @@ impl InverseGamma {
     /// # Errors
     ///
     /// Returns `Err` if `alpha` or `beta` is not strictly positive.
-    pub fn new(alpha: f64, beta: f64) -> Result<InverseGamma, String> {
-        if alpha <= 0.0 {
-            Err(format!(
-                "InverseGamma: illegal shape `{}` should be greater than 0",
-                alpha
-            ))
+    pub fn new(alpha: f64, beta: f64) -> Result<InverseGamma, DistributionError> {
+        if alpha.is_nan() || alpha <= 0.0 {
+            Err(DistributionError::InvalidParameter {
+                distribution: "InverseGamma",
+                name: "shape",
+                value: alpha,
+                constraint: "greater than 0",
+            })
         } else if beta <= 0.0 {
-            Err(format!(
-                "InverseGamma: illegal scale `{}` should be greater than 0",
-                beta
-            ))
+            Err(DistributionError::InvalidParameter {
+                distribution: "InverseGamma",
+                name: "scale",
+                value: beta,
+                constraint: "greater than 0",
+            })
         } else {
-            Ok(InverseGamma { alpha, beta })
+            // Gamma2 uses shape/scale parameterisation, and the scale of 1/X is 1/beta
+            Gamma2::new(alpha, 1.0 / beta)
+                .map(|reciprocal| InverseGamma {
+                    alpha,
+                    beta,
+                    reciprocal,
+                    log_norm: alpha * beta.ln() - ln_gamma(alpha),
+                })
+                .map_err(|_| DistributionError::InvalidParameter {
+                    distribution: "InverseGamma",
+                    name: "scale",
+                    value: beta,
+                    constraint: "finite",
+                })
         }
     }
 }
@@ -65,11 +88,7 @@ impl<R: Rng + ?Sized> Distribution<R> for InverseGamma {
 
     /// Draw a sample as $1/\mathrm{Gamma}(\alpha,\,1/\beta)$.
     fn sample(&self, rng: &mut R) -> f64 {
-        // Gamma2 uses shape/rate parameterisation: Gamma2::new(shape, scale=1/rate)
-        let g = Gamma2::new(self.alpha, 1.0 / self.beta)
-            .unwrap()
-            .sample(rng);
-        1.0 / g
+        1.0 / self.reciprocal.sample(rng)
     }
 
     /// Returns $\alpha\ln\beta - \ln\Gamma(\alpha) - (\alpha+1)\ln x - \beta/x$,
@@ -78,15 +97,66 @@ impl<R: Rng + ?Sized> Distribution<R> for InverseGamma {
         if *x <= 0.0 {
             return f64::NEG_INFINITY;
         }
-        self.alpha * self.beta.ln()
-            - libm::lgamma(self.alpha)
-            - (self.alpha + 1.0) * x.ln()
-            - self.beta / x
+        self.log_norm - (self.alpha + 1.0) * x.ln() - self.beta / x
     }
 
     fn is_discrete(&self) -> bool {
         false
     }
+
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+impl Moments for InverseGamma {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = f64;
+
+    /// Defined for shape $\alpha > 1$.
+    fn mean(&self) -> Option<f64> {
+        (self.alpha > 1.0).then(|| self.beta / (self.alpha - 1.0))
+    }
+
+    /// Defined for shape $\alpha > 2$.
+    fn variance(&self) -> Option<f64> {
+        (self.alpha > 2.0)
+            .then(|| self.beta * self.beta / ((self.alpha - 1.0).powi(2) * (self.alpha - 2.0)))
+    }
+
+    fn mode(&self) -> Option<f64> {
+        Some(self.beta / (self.alpha + 1.0))
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        let a = self.alpha;
+        Some(a + self.beta.ln() + ln_gamma(a) - (1.0 + a) * digamma(a))
+    }
+}
+
+impl Univariate for InverseGamma {
+    fn cdf(&self, x: &f64) -> f64 {
+        if *x <= 0.0 {
+            return 0.0;
+        }
+        gamma_q(self.alpha, self.beta / x)
+    }
+
+    fn sf(&self, x: &f64) -> f64 {
+        if *x <= 0.0 {
+            return 1.0;
+        }
+        gamma_p(self.alpha, self.beta / x)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        continuous_quantile(p, (0.0, f64::INFINITY), |x| self.cdf(&x))
+    }
+
+    fn quantile_sf(&self, q: f64) -> f64 {
+        continuous_quantile_sf(q, (0.0, f64::INFINITY), |x| self.sf(&x))
+    }
 }
 
 impl std::fmt::Display for InverseGamma {
@@ -3,8 +3,11 @@
 use rand::Rng;
 use rand_distr::Distribution as Distribution2;
 use rand_distr::InverseGaussian as InverseGaussian2;
+use rand_distr::InverseGaussianError;
 
-use crate::distributions::Distribution;
+use crate::distributions::univariate::{continuous_quantile, continuous_quantile_sf};
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
+use crate::special::{log_normal_cdf, normal_cdf};
 
 /// Inverse Gaussian (Wald) distribution over the positive reals.
 ///
@@ -32,6 +35,9 @@ use crate::distributions::Distribution;
 pub struct InverseGaussian {
     mu: f64,
     lambda: f64,
+    sampler: InverseGaussian2<f64>,
+    /// $\tfrac{1}{2}\ln(\lambda/2\pi)$.
+    log_norm: f64,
 }
 
 impl InverseGaussian {
@@ -41,20 +47,28 @@ impl InverseGaussian {
     /// # Errors
     ///
     /// Returns `Err` if `mu` or `lambda` is not strictly positive.
-    pub fn new(mu: f64, lambda: f64) -> Result<InverseGaussian, String> {
-        if mu <= 0.0 {
-            Err(format!(
-                "InverseGaussian: illegal mean `{}` should be greater than 0",
-                mu
-            ))
-        } else if lambda <= 0.0 {
-            Err(format!(
-                "InverseGaussian: illegal shape `{}` should be greater than 0",
-                lambda
-            ))
-        } else {
-            Ok(InverseGaussian { mu, lambda })
-        }
+    pub fn new(mu: f64, lambda: f64) -> Result<InverseGaussian, DistributionError> {
+        InverseGaussian2::new(mu, lambda)
+            .map(|sampler| InverseGaussian {
+                mu,
+                lambda,
+                sampler,
+                log_norm: 0.5 * (lambda / (2.0 * std::f64::consts::PI)).ln(),
+            })
+            .map_err(|e| match e {
+                InverseGaussianError::MeanNegativeOrNull => DistributionError::InvalidParameter {
+                    distribution: "InverseGaussian",
+                    name: "mean",
+                    value: mu,
+                    constraint: "greater than 0",
+                },
+                InverseGaussianError::ShapeNegativeOrNull => DistributionError::InvalidParameter {
+                    distribution: "InverseGaussian",
+                    name: "shape",
+                    value: lambda,
+                    constraint: "greater than 0",
+                },
+            })
     }
 }
 
@@ -62,9 +76,7 @@ impl<R: Rng + ?Sized> Distribution<R> for InverseGaussian {
     type Domain = f64;
 
     fn sample(&self, rng: &mut R) -> f64 {
-        InverseGaussian2::new(self.mu, self.lambda)
-            .unwrap()
-            .sample(rng)
+        self.sampler.sample(rng)
     }
 
     /// Returns $\tfrac{1}{2}\ln(\lambda/(2\pi x^3))
@@ -74,13 +86,67 @@ impl<R: Rng + ?Sized> Distribution<R> for InverseGaussian {
         if *x <= 0.0 {
             return f64::NEG_INFINITY;
         }
-        0.5 * (self.lambda / (2.0 * std::f64::consts::PI * x * x * x)).ln()
+        self.log_norm
+            - 1.5 * x.ln()
             - self.lambda * (x - self.mu).powi(2) / (2.0 * self.mu * self.mu * x)
     }
 
     fn is_discrete(&self) -> bool {
         false
     }
+
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+impl Moments for InverseGaussian {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = f64;
+
+    fn mean(&self) -> Option<f64> {
+        Some(self.mu)
+    }
+
+    fn variance(&self) -> Option<f64> {
+        Some(self.mu.powi(3) / self.lambda)
+    }
+
+    fn mode(&self) -> Option<f64> {
+        let ratio = self.mu / self.lambda;
+        Some(self.mu * ((1.0 + 2.25 * ratio * ratio).sqrt() - 1.5 * ratio))
+    }
+}
+
+impl Univariate for InverseGaussian {
+    /// Returns $\Phi\left(\sqrt{\lambda/x}\,(x/\mu - 1)\right)
+    /// + e^{2\lambda/\mu}\,\Phi\left(-\sqrt{\lambda/x}\,(x/\mu + 1)\right)$.
+    fn cdf(&self, x: &f64) -> f64 {
+        if *x <= 0.0 {
+            return 0.0;
+        }
+        let a = (self.lambda / x).sqrt();
+        let second = 2.0 * self.lambda / self.mu + log_normal_cdf(-a * (x / self.mu + 1.0));
+        (normal_cdf(a * (x / self.mu - 1.0)) + second.exp()).min(1.0)
+    }
+
+    fn sf(&self, x: &f64) -> f64 {
+        if *x <= 0.0 {
+            return 1.0;
+        }
+        let a = (self.lambda / x).sqrt();
+        let second = 2.0 * self.lambda / self.mu + log_normal_cdf(-a * (x / self.mu + 1.0));
+        (normal_cdf(-a * (x / self.mu - 1.0)) - second.exp()).max(0.0)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        continuous_quantile(p, (0.0, f64::INFINITY), |x| self.cdf(&x))
+    }
+
+    fn quantile_sf(&self, q: f64) -> f64 {
+        continuous_quantile_sf(q, (0.0, f64::INFINITY), |x| self.sf(&x))
+    }
 }
 
 impl std::fmt::Display for InverseGaussian {
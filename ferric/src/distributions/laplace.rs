@@ -2,9 +2,10 @@
 
 use rand::Rng;
 use rand_distr::Distribution as Distribution2;
-use rand_distr::Exp;
+use rand_distr::Exp1;
 
-use crate::distributions::Distribution;
+use crate::distributions::univariate::check_probability;
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
 
 /// Laplace (double-exponential) distribution over the reals.
 ///
@@ -32,6 +33,8 @@ use crate::distributions::Distribution;
 pub struct Laplace {
     mu: f64,
     b: f64,
+    /// $-\ln(2b)$.
+    log_norm: f64,
 }
 
 impl Laplace {
@@ -41,14 +44,20 @@ impl Laplace {
     /// # Errors
     ///
     /// Returns `Err` if `b` is not strictly positive.
-    pub fn new(mu: f64, b: f64) -> Result<Laplace, String> {
+    pub fn new(mu: f64, b: f64) -> Result<Laplace, DistributionError> {
         if b <= 0.0 {
-            Err(format!(
-                "Laplace: illegal scale `{}` should be greater than 0",
-                b
-            ))
+            Err(DistributionError::InvalidParameter {
+                distribution: "Laplace",
+                name: "scale",
+                value: b,
+                constraint: "greater than 0",
+            })
         } else {
-            Ok(Laplace { mu, b })
+            Ok(Laplace {
+                mu,
+                b,
+                log_norm: -(2.0 * b).ln(),
+            })
         }
     }
 }
@@ -59,15 +68,14 @@ impl<R: Rng + ?Sized> Distribution<R> for Laplace {
     /// Draw a sample via the difference of two $\mathrm{Exp}(1)$ variates:
     /// $X = \mu + b(E_1 - E_2)$ where $E_1, E_2 \sim \mathrm{Exp}(1)$.
     fn sample(&self, rng: &mut R) -> f64 {
-        let exp = Exp::new(1.0).unwrap();
-        let e1 = exp.sample(rng);
-        let e2 = exp.sample(rng);
+        let e1: f64 = Exp1.sample(rng);
+        let e2: f64 = Exp1.sample(rng);
         self.mu + self.b * (e1 - e2)
     }
 
     /// Returns $-\ln(2b) - |x - \mu| / b$.
     fn log_prob(&self, x: &f64) -> f64 {
-        -(2.0 * self.b).ln() - (x - self.mu).abs() / self.b
+        self.log_norm - (x - self.mu).abs() / self.b
     }
 
     fn is_discrete(&self) -> bool {
@@ -75,6 +83,75 @@ impl<R: Rng + ?Sized> Distribution<R> for Laplace {
     }
 }
 
+impl Moments for Laplace {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = f64;
+
+    fn mean(&self) -> Option<f64> {
+        Some(self.mu)
+    }
+
+    fn variance(&self) -> Option<f64> {
+        Some(2.0 * self.b * self.b)
+    }
+
+    fn mode(&self) -> Option<f64> {
+        Some(self.mu)
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        Some((2.0 * self.b).ln() + 1.0)
+    }
+}
+
+impl Univariate for Laplace {
+    fn cdf(&self, x: &f64) -> f64 {
+        let z = (x - self.mu) / self.b;
+        if z < 0.0 {
+            0.5 * z.exp()
+        } else {
+            1.0 - 0.5 * (-z).exp()
+        }
+    }
+
+    fn log_cdf(&self, x: &f64) -> f64 {
+        let z = (x - self.mu) / self.b;
+        if z < 0.0 {
+            z - std::f64::consts::LN_2
+        } else {
+            (-0.5 * (-z).exp()).ln_1p()
+        }
+    }
+
+    fn sf(&self, x: &f64) -> f64 {
+        let z = (x - self.mu) / self.b;
+        if z > 0.0 {
+            0.5 * (-z).exp()
+        } else {
+            1.0 - 0.5 * z.exp()
+        }
+    }
+
+    fn log_sf(&self, x: &f64) -> f64 {
+        let z = (x - self.mu) / self.b;
+        if z > 0.0 {
+            -z - std::f64::consts::LN_2
+        } else {
+            (-0.5 * z.exp()).ln_1p()
+        }
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        check_probability(p);
+        if p < 0.5 {
+            self.mu + self.b * (2.0 * p).ln()
+        } else {
+            self.mu - self.b * (2.0 * (1.0 - p)).ln()
+        }
+    }
+}
+
 impl std::fmt::Display for Laplace {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Laplace {{ mu = {}, b = {} }}", self.mu, self.b)
@@ -3,7 +3,9 @@
 use rand_distr::Distribution as Distribution2;
 use rand_distr::LogNormal as LogNormal2;
 
-use crate::distributions::Distribution;
+use crate::distributions::univariate::check_probability;
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
+use crate::special::{log_normal_cdf, normal_cdf, normal_quantile};
 use rand::Rng;
 
 /// Log-normal distribution over positive reals.
@@ -35,6 +37,9 @@ use rand::Rng;
 pub struct LogNormal {
     mu: f64,
     sigma: f64,
+    sampler: LogNormal2<f64>,
+    /// $-\ln\sigma - \tfrac{1}{2}\ln(2\pi)$.
+    log_norm: f64,
 }
 
 impl LogNormal {
@@ -44,14 +49,28 @@ impl LogNormal {
     /// # Errors
     ///
     /// Returns `Err` if `sigma` is not strictly positive.
-    pub fn new(mu: f64, sigma: f64) -> Result<LogNormal, String> {
+    pub fn new(mu: f64, sigma: f64) -> Result<LogNormal, DistributionError> {
         if sigma <= 0.0 {
-            Err(format!(
-                "LogNormal: illegal sigma `{}` should be greater than 0",
-                sigma
-            ))
+            Err(DistributionError::InvalidParameter {
+                distribution: "LogNormal",
+                name: "sigma",
+                value: sigma,
+                constraint: "greater than 0",
+            })
         } else {
-            Ok(LogNormal { mu, sigma })
+            LogNormal2::new(mu, sigma)
+                .map(|sampler| LogNormal {
+                    mu,
+                    sigma,
+                    sampler,
+                    log_norm: -sigma.ln() - 0.5 * (2.0 * std::f64::consts::PI).ln(),
+                })
+                .map_err(|_| DistributionError::InvalidParameter {
+                    distribution: "LogNormal",
+                    name: "sigma",
+                    value: sigma,
+                    constraint: "finite",
+                })
         }
     }
 }
@@ -60,24 +79,85 @@ impl<R: Rng + ?Sized> Distribution<R> for LogNormal {
     type Domain = f64;
 
     fn sample(&self, rng: &mut R) -> f64 {
-        LogNormal2::new(self.mu, self.sigma).unwrap().sample(rng)
+        self.sampler.sample(rng)
     }
 
     /// Returns
-    /// $-\tfrac{1}{2}\!\left(\tfrac{\ln x - \mu}{\sigma}\right)^{\!2}
-    ///   - \ln x - \ln\sigma - \tfrac{1}{2}\ln(2\pi)$
+    /// $-\tfrac{1}{2}\!\left(\tfrac{\ln x - \mu}{\sigma}\right)^{\!2} -
+    ///   \ln x - \ln\sigma - \tfrac{1}{2}\ln(2\pi)$
     /// for $x > 0$, or $-\infty$ otherwise.
     fn log_prob(&self, x: &f64) -> f64 {
         if *x <= 0.0 {
             return f64::NEG_INFINITY;
         }
-        let z = (x.ln() - self.mu) / self.sigma;
-        -0.5 * z * z - x.ln() - self.sigma.ln() - 0.5 * (2.0 * std::f64::consts::PI).ln()
+        let ln_x = x.ln();
+        let z = (ln_x - self.mu) / self.sigma;
+        -0.5 * z * z - ln_x + self.log_norm
     }
 
     fn is_discrete(&self) -> bool {
         false
     }
+
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+impl Moments for LogNormal {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = f64;
+
+    fn mean(&self) -> Option<f64> {
+        Some((self.mu + self.sigma * self.sigma / 2.0).exp())
+    }
+
+    fn variance(&self) -> Option<f64> {
+        let s2 = self.sigma * self.sigma;
+        Some(s2.exp_m1() * (2.0 * self.mu + s2).exp())
+    }
+
+    fn mode(&self) -> Option<f64> {
+        Some((self.mu - self.sigma * self.sigma).exp())
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        Some(
+            self.mu
+                + 0.5
+                    * (2.0 * std::f64::consts::PI * std::f64::consts::E * self.sigma * self.sigma)
+                        .ln(),
+        )
+    }
+}
+
+impl Univariate for LogNormal {
+    fn cdf(&self, x: &f64) -> f64 {
+        normal_cdf((x.ln() - self.mu) / self.sigma)
+    }
+
+    fn log_cdf(&self, x: &f64) -> f64 {
+        log_normal_cdf((x.ln() - self.mu) / self.sigma)
+    }
+
+    fn sf(&self, x: &f64) -> f64 {
+        normal_cdf((self.mu - x.ln()) / self.sigma)
+    }
+
+    fn log_sf(&self, x: &f64) -> f64 {
+        log_normal_cdf((self.mu - x.ln()) / self.sigma)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        check_probability(p);
+        (self.mu + self.sigma * normal_quantile(p)).exp()
+    }
+
+    fn quantile_sf(&self, q: f64) -> f64 {
+        check_probability(q);
+        (self.mu - self.sigma * normal_quantile(q)).exp()
+    }
 }
 
 impl std::fmt::Display for LogNormal {
@@ -1,10 +1,10 @@
 // Copyright 2022 The Ferric AI Project Developers
 
 use rand::Rng;
-use rand_distr::Distribution as Distribution2;
-use rand_distr::Uniform;
 
-use crate::distributions::Distribution;
+use crate::distributions::univariate::check_probability;
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
+use crate::special::log1p_exp;
 
 /// Logistic distribution over the reals.
 ///
@@ -32,6 +32,8 @@ use crate::distributions::Distribution;
 pub struct Logistic {
     mu: f64,
     s: f64,
+    /// $\ln s$.
+    log_s: f64,
 }
 
 impl Logistic {
@@ -41,14 +43,20 @@ impl Logistic {
     /// # Errors
     ///
     /// Returns `Err` if `s` is not strictly positive.
-    pub fn new(mu: f64, s: f64) -> Result<Logistic, String> {
+    pub fn new(mu: f64, s: f64) -> Result<Logistic, DistributionError> {
         if s <= 0.0 {
-            Err(format!(
-                "Logistic: illegal scale `{}` should be greater than 0",
-                s
-            ))
+            Err(DistributionError::InvalidParameter {
+                distribution: "Logistic",
+                name: "scale",
+                value: s,
+                constraint: "greater than 0",
+            })
         } else {
-            Ok(Logistic { mu, s })
+            Ok(Logistic {
+                mu,
+                s,
+                log_s: s.ln(),
+            })
         }
     }
 }
@@ -60,7 +68,7 @@ impl<R: Rng + ?Sized> Distribution<R> for Logistic {
     /// $X = \mu + s \ln\!\left(\frac{u}{1-u}\right)$ where $u \sim U(0,1)$.
     fn sample(&self, rng: &mut R) -> f64 {
         // Use open unit interval to avoid ln(0)
-        let u = Uniform::new(0.0f64, 1.0).sample(rng);
+        let u: f64 = rng.r#gen();
         // Clamp away from 0 and 1 for numerical safety
         let u = u.clamp(1e-15, 1.0 - 1e-15);
         self.mu + self.s * (u / (1.0 - u)).ln()
@@ -71,7 +79,7 @@ impl<R: Rng + ?Sized> Distribution<R> for Logistic {
         let z = (x - self.mu) / self.s;
         // Use numerically stable softplus: ln(1 + e^{-|z|}) + max(z, 0)
         let softplus_neg_z = (-z.abs()).exp().ln_1p() + z.max(0.0);
-        -z - self.s.ln() - 2.0 * softplus_neg_z
+        -z - self.log_s - 2.0 * softplus_neg_z
     }
 
     fn is_discrete(&self) -> bool {
@@ -79,6 +87,52 @@ impl<R: Rng + ?Sized> Distribution<R> for Logistic {
     }
 }
 
+impl Moments for Logistic {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = f64;
+
+    fn mean(&self) -> Option<f64> {
+        Some(self.mu)
+    }
+
+    fn variance(&self) -> Option<f64> {
+        Some((self.s * std::f64::consts::PI).powi(2) / 3.0)
+    }
+
+    fn mode(&self) -> Option<f64> {
+        Some(self.mu)
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        Some(self.s.ln() + 2.0)
+    }
+}
+
+impl Univariate for Logistic {
+    fn cdf(&self, x: &f64) -> f64 {
+        self.log_cdf(x).exp()
+    }
+
+    /// Returns $-\ln(1 + e^{-z})$ with $z = (x - \mu)/s$.
+    fn log_cdf(&self, x: &f64) -> f64 {
+        -log1p_exp(-(x - self.mu) / self.s)
+    }
+
+    fn sf(&self, x: &f64) -> f64 {
+        self.log_sf(x).exp()
+    }
+
+    fn log_sf(&self, x: &f64) -> f64 {
+        -log1p_exp((x - self.mu) / self.s)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        check_probability(p);
+        self.mu + self.s * (p / (1.0 - p)).ln()
+    }
+}
+
 impl std::fmt::Display for Logistic {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Logistic {{ mu = {}, s = {} }}", self.mu, self.s)
@@ -2,16 +2,22 @@
 
 use nalgebra::{Cholesky, DMatrix, Dyn};
 use rand::Rng;
-use rand_distr::Distribution as Distribution2;
-use rand_distr::Normal as Normal2;
+use rand_distr::StandardNormal;
 
-use crate::distributions::Distribution;
+use crate::distributions::{Distribution, DistributionError};
 
 /// Matrix normal distribution over real matrices.
 pub struct MatrixNormal {
     mean: DMatrix<f64>,
     row_chol: Cholesky<f64, Dyn>,
-    col_chol: Cholesky<f64, Dyn>,
+    /// The lower Cholesky factor of the row covariance.
+    row_l: DMatrix<f64>,
+    /// The transposed lower Cholesky factor of the column covariance.
+    col_l_t: DMatrix<f64>,
+    /// The inverse of the column covariance.
+    col_precision: DMatrix<f64>,
+    /// $-\tfrac{1}{2}(np\ln(2\pi) + p\ln|U| + n\ln|V|)$.
+    log_norm: f64,
 }
 
 impl MatrixNormal {
@@ -26,22 +32,44 @@ impl MatrixNormal {
         mean: DMatrix<f64>,
         row_cov: DMatrix<f64>,
         col_cov: DMatrix<f64>,
-    ) -> Result<MatrixNormal, String> {
+    ) -> Result<MatrixNormal, DistributionError> {
         if row_cov.nrows() != mean.nrows() || row_cov.ncols() != mean.nrows() {
-            return Err("MatrixNormal: row covariance must be nrows(mean) × nrows(mean)".into());
+            return Err(DistributionError::DimensionMismatch {
+                distribution: "MatrixNormal",
+                name: "row covariance",
+                expected: format!("{}×{}", mean.nrows(), mean.nrows()),
+                found: format!("{}×{}", row_cov.nrows(), row_cov.ncols()),
+            });
         }
         if col_cov.nrows() != mean.ncols() || col_cov.ncols() != mean.ncols() {
-            return Err("MatrixNormal: column covariance must be ncols(mean) × ncols(mean)".into());
+            return Err(DistributionError::DimensionMismatch {
+                distribution: "MatrixNormal",
+                name: "column covariance",
+                expected: format!("{}×{}", mean.ncols(), mean.ncols()),
+                found: format!("{}×{}", col_cov.nrows(), col_cov.ncols()),
+            });
         }
-        let row_chol = Cholesky::new(row_cov)
-            .ok_or_else(|| "MatrixNormal: row covariance is not positive definite".to_string())?;
-        let col_chol = Cholesky::new(col_cov).ok_or_else(|| {
-            "MatrixNormal: column covariance is not positive definite".to_string()
+        let row_chol = Cholesky::new(row_cov).ok_or(DistributionError::NotPositiveDefinite {
+            distribution: "MatrixNormal",
+            name: "row covariance",
         })?;
+        let col_chol = Cholesky::new(col_cov).ok_or(DistributionError::NotPositiveDefinite {
+            distribution: "MatrixNormal",
+            name: "column covariance",
+        })?;
+        let n = mean.nrows() as f64;
+        let p = mean.ncols() as f64;
+        let log_norm = -0.5
+            * (n * p * (2.0 * std::f64::consts::PI).ln()
+                + p * log_det_from_chol(&row_chol)
+                + n * log_det_from_chol(&col_chol));
         Ok(MatrixNormal {
             mean,
+            row_l: row_chol.l(),
+            col_l_t: col_chol.l().transpose(),
+            col_precision: col_chol.inverse(),
             row_chol,
-            col_chol,
+            log_norm,
         })
     }
 }
@@ -50,28 +78,20 @@ impl<R: Rng + ?Sized> Distribution<R> for MatrixNormal {
     type Domain = DMatrix<f64>;
 
     fn sample(&self, rng: &mut R) -> DMatrix<f64> {
-        let normal = Normal2::new(0.0, 1.0).unwrap();
         let z = DMatrix::from_fn(self.mean.nrows(), self.mean.ncols(), |_, _| {
-            normal.sample(rng)
+            rng.sample::<f64, _>(StandardNormal)
         });
-        &self.mean + self.row_chol.l() * z * self.col_chol.l().transpose()
+        &self.mean + &self.row_l * z * &self.col_l_t
     }
 
     fn log_prob(&self, x: &DMatrix<f64>) -> f64 {
         if x.shape() != self.mean.shape() {
             return f64::NEG_INFINITY;
         }
-        let n = self.mean.nrows() as f64;
-        let p = self.mean.ncols() as f64;
         let diff = x - &self.mean;
         let row_solved = self.row_chol.solve(&diff);
-        let trace_term = (row_solved.transpose() * diff * self.col_chol.inverse()).trace();
-        let row_log_det = log_det_from_chol(&self.row_chol);
-        let col_log_det = log_det_from_chol(&self.col_chol);
-        -0.5 * (n * p * (2.0 * std::f64::consts::PI).ln()
-            + p * row_log_det
-            + n * col_log_det
-            + trace_term)
+        let trace_term = (row_solved.transpose() * diff * &self.col_precision).trace();
+        self.log_norm - 0.5 * trace_term
     }
 
     fn is_discrete(&self) -> bool {
@@ -0,0 +1,367 @@
+// Copyright 2022 The Ferric AI Project Developers
+
+use rand::Rng;
+use rand::rngs::ThreadRng;
+
+use crate::distributions::{Distribution, DistributionError, Moments};
+use crate::special::log_sum_exp;
+
+/// Finite mixture of component distributions over a common domain.
+///
+/// The density (or mass) is
+///
+/// $$p(x) = \sum_{i=1}^{k} w_i\, p_i(x)$$
+///
+/// where $w_i \ge 0$ are the normalised component weights and $p_i$ are the
+/// component densities.  Sampling first picks a component with probability
+/// $w_i$ and then draws from it, and `log_prob` combines the components with
+/// [`log_sum_exp`].  Scoring an observation against the whole mixture
+/// marginalises out the component it came from, which is much more
+/// efficient for importance sampling than an explicit
+/// [`Categorical`](crate::distributions::Categorical) assignment per data
+/// point.
+///
+/// All components of a `Mixture<D>` have the same type `D`.  Use
+/// [`BoxedMixture`] to mix components of different types.
+///
+/// See [Mixture distribution](https://en.wikipedia.org/wiki/Mixture_distribution)
+/// on Wikipedia for further details.
+///
+/// # Examples
+///
+/// ```
+/// use ferric::distributions::{Distribution, Mixture, Normal};
+/// use rand::thread_rng;
+///
+/// let dist = Mixture::new(
+///     vec![0.3, 0.7],
+///     vec![Normal::new(-2.0, 1.0).unwrap(), Normal::new(2.0, 1.0).unwrap()],
+/// )
+/// .unwrap();
+/// let x: f64 = dist.sample(&mut thread_rng());
+/// println!("sample = {:.4}", x);
+/// ```
+pub struct Mixture<D> {
+    weights: Vec<f64>,
+    cumulative: Vec<f64>,
+    /// $\ln w_i$ for each component.
+    log_weights: Vec<f64>,
+    components: Vec<D>,
+}
+
+/// A [`Mixture`] of boxed components, which may be of different types.
+///
+/// # Examples
+///
+/// ```
+/// use ferric::distributions::{BoxedMixture, Distribution, Laplace, Normal};
+///
+/// let dist: BoxedMixture<f64> = BoxedMixture::new(
+///     vec![0.5, 0.5],
+///     vec![
+///         Box::new(Normal::new(0.0, 1.0).unwrap()),
+///         Box::new(Laplace::new(0.0, 3.0).unwrap()),
+///     ],
+/// )
+/// .unwrap();
+/// assert!(dist.log_prob(&0.0) < 0.0);
+/// ```
+pub type BoxedMixture<T, R = ThreadRng> = Mixture<Box<dyn Distribution<R, Domain = T> + Send>>;
+
+impl<D> Mixture<D> {
+    /// Construct a mixture of `components` with the given `weights`.
+    ///
+    /// The weights are automatically normalised, so any non-negative vector
+    /// with a positive sum is accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if `weights` is empty, if its length differs from the
+    /// number of components, if any weight is negative or not finite, or if
+    /// the total weight is zero.
+    pub fn new(weights: Vec<f64>, components: Vec<D>) -> Result<Mixture<D>, DistributionError> {
+        if weights.is_empty() {
+            return Err(DistributionError::EmptySupport {
+                distribution: "Mixture",
+                reason: "weights is empty",
+            });
+        }
+        if weights.len() != components.len() {
+            return Err(DistributionError::DimensionMismatch {
+                distribution: "Mixture",
+                name: "components",
+                expected: weights.len().to_string(),
+                found: components.len().to_string(),
+            });
+        }
+        if let Some(&w) = weights
+            .iter()
+            .find(|&&w| !(0.0..f64::INFINITY).contains(&w))
+        {
+            return Err(DistributionError::InvalidParameter {
+                distribution: "Mixture",
+                name: "weight",
+                value: w,
+                constraint: "non-negative and finite",
+            });
+        }
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            return Err(DistributionError::EmptySupport {
+                distribution: "Mixture",
+                reason: "all weights are zero",
+            });
+        }
+        let weights: Vec<f64> = weights.iter().map(|&w| w / total).collect();
+        let cumulative = weights
+            .iter()
+            .scan(0.0, |acc, &w| {
+                *acc += w;
+                Some(*acc)
+            })
+            .collect();
+        let log_weights = weights.iter().map(|w| w.ln()).collect();
+        Ok(Mixture {
+            weights,
+            cumulative,
+            log_weights,
+            components,
+        })
+    }
+
+    /// The normalised component weights.
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// The component distributions.
+    pub fn components(&self) -> &[D] {
+        &self.components
+    }
+
+    /// The weights and components whose weight is positive.
+    fn active(&self) -> impl Iterator<Item = (f64, &D)> {
+        self.weights
+            .iter()
+            .zip(self.components.iter())
+            .filter(|(w, _)| **w > 0.0)
+            .map(|(&w, d)| (w, d))
+    }
+}
+
+impl<R, D> Distribution<R> for Mixture<D>
+where
+    R: Rng + ?Sized,
+    D: Distribution<R>,
+    D::Domain: PartialEq,
+{
+    type Domain = D::Domain;
+
+    fn sample(&self, rng: &mut R) -> Self::Domain {
+        let u: f64 = rng.r#gen();
+        let index = self
+            .cumulative
+            .iter()
+            .position(|&c| u < c)
+            .unwrap_or(self.components.len() - 1);
+        self.components[index].sample(rng)
+    }
+
+    /// Returns $\ln \sum_i w_i\, p_i(x)$, computed with [`log_sum_exp`].
+    fn log_prob(&self, x: &Self::Domain) -> f64 {
+        let terms: Vec<f64> = self
+            .log_weights
+            .iter()
+            .zip(self.components.iter())
+            .filter(|(log_w, _)| **log_w > f64::NEG_INFINITY)
+            .map(|(log_w, d)| log_w + d.log_prob(x))
+            .collect();
+        log_sum_exp(&terms)
+    }
+
+    /// Returns `true` if every component is discrete.
+    fn is_discrete(&self) -> bool {
+        self.components.iter().all(|d| d.is_discrete())
+    }
+
+    /// Returns the smallest interval containing the support of every
+    /// component.
+    fn support(&self) -> (f64, f64) {
+        self.active()
+            .map(|(_, d)| d.support())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), (l, h)| {
+                (lo.min(l), hi.max(h))
+            })
+    }
+
+    /// Returns the distinct values in the supports of the components, or
+    /// `None` if any component has an infinite support.
+    fn finite_support(&self) -> Option<Vec<Self::Domain>> {
+        let mut support: Vec<Self::Domain> = Vec::new();
+        for (_, dist) in self.active() {
+            for value in dist.finite_support()? {
+                if !support.contains(&value) {
+                    support.push(value);
+                }
+            }
+        }
+        Some(support)
+    }
+}
+
+impl<D> Moments for Mixture<D>
+where
+    D: Moments<Mean = f64, Variance = f64>,
+{
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = f64;
+
+    /// Returns $\sum_i w_i \mu_i$.
+    fn mean(&self) -> Option<f64> {
+        self.active().map(|(w, d)| Some(w * d.mean()?)).sum()
+    }
+
+    /// Returns $\sum_i w_i (\sigma_i^2 + \mu_i^2) - \mu^2$.
+    fn variance(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        let second: Option<f64> = self
+            .active()
+            .map(|(w, d)| {
+                let m = d.mean()?;
+                Some(w * (d.variance()? + m * m))
+            })
+            .sum();
+        Some((second? - mean * mean).max(0.0))
+    }
+}
+
+impl<D: std::fmt::Display> std::fmt::Display for Mixture<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let components: Vec<String> = self.components.iter().map(|d| d.to_string()).collect();
+        write!(
+            f,
+            "Mixture {{ weights = {:?}, components = [{}] }}",
+            self.weights,
+            components.join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributions::{Bernoulli, Exponential, Normal, Poisson};
+    use rand::thread_rng;
+
+    fn two_normals() -> Mixture<Normal> {
+        Mixture::new(
+            vec![1.0, 3.0],
+            vec![
+                Normal::new(-2.0, 1.0).unwrap(),
+                Normal::new(2.0, 0.5).unwrap(),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn mixture_log_prob() {
+        let dist = two_normals();
+        assert_eq!(dist.weights(), &[0.25, 0.75]);
+        let density = |x: f64| {
+            dist.components()
+                .iter()
+                .zip(dist.weights())
+                .map(|(d, w)| w * <Normal as Distribution>::log_prob(d, &x).exp())
+                .sum::<f64>()
+        };
+        for &x in &[-3.0, 0.0, 1.5, 2.0] {
+            let lp = <Mixture<Normal> as Distribution>::log_prob(&dist, &x);
+            assert!((lp - density(x).ln()).abs() < 1e-12);
+        }
+        // far out the densities underflow, but log-sum-exp does not
+        let lp = <Mixture<Normal> as Distribution>::log_prob(&dist, &-60.0);
+        assert!(lp.is_finite());
+        assert!(format!("{}", dist).contains("Mixture"));
+    }
+
+    #[test]
+    fn mixture_sample() {
+        let dist = two_normals();
+        let mut rng = thread_rng();
+        let trials = 20_000;
+        let total: f64 = (0..trials).map(|_| dist.sample(&mut rng)).sum();
+        let empirical_mean = total / trials as f64;
+        let mean = dist.mean().unwrap();
+        assert!((mean - 1.0).abs() < 1e-12);
+        let err = 5.0 * (dist.variance().unwrap() / trials as f64).sqrt();
+        assert!((empirical_mean - mean).abs() < err);
+    }
+
+    #[test]
+    fn boxed_mixture_of_different_types() {
+        let dist: BoxedMixture<f64> = BoxedMixture::new(
+            vec![0.5, 0.5],
+            vec![
+                Box::new(Normal::new(0.0, 1.0).unwrap()),
+                Box::new(Exponential::new(1.0).unwrap()),
+            ],
+        )
+        .unwrap();
+        let normal = <Normal as Distribution>::log_prob(&Normal::new(0.0, 1.0).unwrap(), &-1.0);
+        assert!((dist.log_prob(&-1.0) - (0.5f64.ln() + normal)).abs() < 1e-12);
+        assert_eq!(dist.support(), (f64::NEG_INFINITY, f64::INFINITY));
+        assert!(!dist.is_discrete());
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            assert!(dist.sample(&mut rng).is_finite());
+        }
+    }
+
+    #[test]
+    fn discrete_mixture_support() {
+        let coins = Mixture::new(
+            vec![0.5, 0.5],
+            vec![Bernoulli::new(0.1).unwrap(), Bernoulli::new(0.9).unwrap()],
+        )
+        .unwrap();
+        assert!(<Mixture<Bernoulli> as Distribution>::is_discrete(&coins));
+        assert_eq!(
+            <Mixture<Bernoulli> as Distribution>::finite_support(&coins),
+            Some(vec![false, true])
+        );
+        let counts = Mixture::new(
+            vec![0.5, 0.5],
+            vec![Poisson::new(1.0).unwrap(), Poisson::new(10.0).unwrap()],
+        )
+        .unwrap();
+        assert_eq!(
+            <Mixture<Poisson> as Distribution>::finite_support(&counts),
+            None
+        );
+        let total: f64 = (0..100)
+            .map(|k| <Mixture<Poisson> as Distribution>::log_prob(&counts, &k).exp())
+            .sum();
+        assert!((total - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn mixture_invalid() {
+        let normal = || Normal::new(0.0, 1.0).unwrap();
+        assert!(matches!(
+            Mixture::<Normal>::new(vec![], vec![]),
+            Err(DistributionError::EmptySupport { .. })
+        ));
+        assert!(matches!(
+            Mixture::new(vec![1.0, 1.0], vec![normal()]),
+            Err(DistributionError::DimensionMismatch { .. })
+        ));
+        assert!(matches!(
+            Mixture::new(vec![1.0, -1.0], vec![normal(), normal()]),
+            Err(DistributionError::InvalidParameter { .. })
+        ));
+        assert!(Mixture::new(vec![f64::NAN], vec![normal()]).is_err());
+        assert!(Mixture::new(vec![0.0, 0.0], vec![normal(), normal()]).is_err());
+    }
+}
@@ -7,6 +7,7 @@
 //! values. Scalar distributions use native Rust numeric and boolean types,
 //! vector distributions use `Vec` or `nalgebra::DVector<f64>`, and matrix
 //! distributions use `nalgebra::DMatrix<f64>`.
+mod alias;
 mod bernoulli;
 mod beta;
 mod beta_binomial;
@@ -21,6 +22,7 @@ mod discrete_uniform;
 mod distribution;
 mod empirical;
 mod erlang;
+mod error;
 mod exponential;
 mod fisher_f;
 mod frechet;
@@ -35,6 +37,8 @@ mod laplace;
 mod log_normal;
 mod logistic;
 mod matrix_normal;
+mod mixture;
+mod moments;
 mod multinomial;
 mod multivariate_normal;
 mod multivariate_student_t;
@@ -45,7 +49,9 @@ mod poisson;
 mod rayleigh;
 mod student_t;
 mod triangular;
+mod truncated;
 mod uniform;
+mod univariate;
 mod weibull;
 mod wishart;
 
@@ -64,6 +70,7 @@ pub use self::discrete_uniform::DiscreteUniform;
 pub use self::distribution::Distribution;
 pub use self::empirical::Empirical;
 pub use self::erlang::Erlang;
+pub use self::error::DistributionError;
 pub use self::exponential::Exponential;
 pub use self::fisher_f::FisherF;
 pub use self::frechet::Frechet;
@@ -78,6 +85,8 @@ pub use self::laplace::Laplace;
 pub use self::log_normal::LogNormal;
 pub use self::logistic::Logistic;
 pub use self::matrix_normal::MatrixNormal;
+pub use self::mixture::{BoxedMixture, Mixture};
+pub use self::moments::Moments;
 pub use self::multinomial::Multinomial;
 pub use self::multivariate_normal::MultivariateNormal;
 pub use self::multivariate_student_t::MultivariateStudentT;
@@ -88,6 +97,8 @@ pub use self::poisson::Poisson;
 pub use self::rayleigh::Rayleigh;
 pub use self::student_t::StudentT;
 pub use self::triangular::Triangular;
+pub use self::truncated::Truncated;
 pub use self::uniform::Uniform;
+pub use self::univariate::Univariate;
 pub use self::weibull::Weibull;
 pub use self::wishart::Wishart;
@@ -0,0 +1,241 @@
+// Copyright 2022 The Ferric AI Project Developers
+
+/// Closed-form summary statistics of a distribution.
+///
+/// Scalar distributions report their mean and variance as `f64`.  Vector
+/// distributions report a mean vector and a covariance matrix, and matrix
+/// distributions report the mean matrix and the elementwise variances.
+/// Every statistic is `None` where it is undefined or infinite, for example
+/// the mean of a [`Cauchy`](crate::distributions::Cauchy).
+///
+/// # Examples
+///
+/// ```
+/// use ferric::distributions::{Gamma, Moments};
+///
+/// let dist = Gamma::new(2.0, 3.0).unwrap();
+/// assert_eq!(dist.mean(), Some(6.0));
+/// assert_eq!(dist.variance(), Some(18.0));
+/// assert_eq!(dist.mode(), Some(3.0));
+/// ```
+pub trait Moments {
+    /// The type of the mean: `f64`, `DVector<f64>` or `DMatrix<f64>`.
+    type Mean;
+
+    /// The type of the variance: `f64` for scalar distributions, the
+    /// covariance matrix for vector distributions.
+    type Variance;
+
+    /// The type of the mode, which is the domain of the distribution.
+    type Mode;
+
+    /// The expected value $\mathbb{E}[X]$.
+    fn mean(&self) -> Option<Self::Mean>;
+
+    /// The variance $\mathbb{E}[(X - \mathbb{E}[X])^2]$, or the covariance
+    /// matrix of a vector distribution.
+    fn variance(&self) -> Option<Self::Variance>;
+
+    /// A value at which the probability (density) is largest.  Defaults to
+    /// `None`, which is also returned when every value in an interval is a
+    /// mode.
+    fn mode(&self) -> Option<Self::Mode> {
+        None
+    }
+
+    /// The entropy in nats: the Shannon entropy of a discrete distribution
+    /// or the differential entropy of a continuous one.  Defaults to `None`
+    /// for distributions without a closed form.
+    fn entropy(&self) -> Option<f64> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::{DMatrix, DVector};
+    use rand::thread_rng;
+
+    use crate::distributions::*;
+
+    /// Compare the closed-form mean, variance and entropy against Monte
+    /// Carlo estimates from `num_samples` draws, mapping each draw to a real
+    /// number with `to_f64`.
+    fn check<D, T>(dist: D, to_f64: impl Fn(&T) -> f64, num_samples: usize)
+    where
+        D: Distribution<Domain = T> + Moments<Mean = f64, Variance = f64>,
+    {
+        let mut rng = thread_rng();
+        let draws: Vec<T> = (0..num_samples).map(|_| dist.sample(&mut rng)).collect();
+        let xs: Vec<f64> = draws.iter().map(&to_f64).collect();
+        let n = num_samples as f64;
+        let mean = xs.iter().sum::<f64>() / n;
+        let var = xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+        let entropy = -draws.iter().map(|x| dist.log_prob(x)).sum::<f64>() / n;
+
+        let true_var = dist.variance().unwrap();
+        let tol = 5.0 * (true_var / n).sqrt() + 1e-9;
+        assert!(
+            (mean - dist.mean().unwrap()).abs() < tol,
+            "mean {} != {:?}",
+            mean,
+            dist.mean()
+        );
+        assert!(
+            (var - true_var).abs() < 0.05 * true_var + 1e-9,
+            "variance {} != {}",
+            var,
+            true_var
+        );
+        if let Some(true_entropy) = dist.entropy() {
+            assert!(
+                (entropy - true_entropy).abs() < 0.02 * (1.0 + true_entropy.abs()),
+                "{}: entropy {} != {}",
+                std::any::type_name::<D>(),
+                entropy,
+                true_entropy
+            );
+        }
+    }
+
+    #[test]
+    fn continuous_moments_match_samples() {
+        let n = 200_000;
+        check(Beta::new(2.0, 5.0).unwrap(), |&x| x, n);
+        check(Chi::new(3.0).unwrap(), |&x| x, n);
+        check(ChiSquared::new(4.0).unwrap(), |&x| x, n);
+        check(Erlang::new(3, 2.0).unwrap(), |&x| x, n);
+        check(Exponential::new(1.5).unwrap(), |&x| x, n);
+        check(FisherF::new(5.0, 20.0).unwrap(), |&x| x, n);
+        check(Frechet::new(1.0, 2.0, 6.0).unwrap(), |&x| x, n);
+        check(Gamma::new(2.5, 1.5).unwrap(), |&x| x, n);
+        check(Gumbel::new(1.0, 2.0).unwrap(), |&x| x, n);
+        check(HalfNormal::new(2.0).unwrap(), |&x| x, n);
+        check(InverseGamma::new(6.0, 2.0).unwrap(), |&x| x, n);
+        check(InverseGaussian::new(1.0, 3.0).unwrap(), |&x| x, n);
+        check(Laplace::new(1.0, 0.5).unwrap(), |&x| x, n);
+        check(LogNormal::new(0.0, 0.25).unwrap(), |&x| x, n);
+        check(Logistic::new(1.0, 2.0).unwrap(), |&x| x, n);
+        check(Normal::new(-1.0, 3.0).unwrap(), |&x| x, n);
+        check(Pareto::new(1.0, 6.0).unwrap(), |&x| x, n);
+        check(Rayleigh::new(2.0).unwrap(), |&x| x, n);
+        check(StudentT::new(8.0).unwrap(), |&x| x, n);
+        check(Triangular::new(0.0, 4.0, 1.0).unwrap(), |&x| x, n);
+        check(Uniform::new(-2.0, 3.0).unwrap(), |&x| x, n);
+        check(Weibull::new(2.0, 3.0).unwrap(), |&x| x, n);
+    }
+
+    #[test]
+    fn discrete_moments_match_samples() {
+        let n = 200_000;
+        check(Bernoulli::new(0.3).unwrap(), |&x| x as u8 as f64, n);
+        check(BetaBinomial::new(10, 2.0, 3.0).unwrap(), |&x| x as f64, n);
+        check(Binomial::new(20, 0.3).unwrap(), |&x| x as f64, n);
+        check(
+            Categorical::new(vec![0.2, 0.5, 0.3]).unwrap(),
+            |&x| x as f64,
+            n,
+        );
+        check(DiscreteUniform::new(-3, 4).unwrap(), |&x| x as f64, n);
+        check(Geometric::new(0.3).unwrap(), |&x| x as f64, n);
+        check(Hypergeometric::new(20, 7, 5).unwrap(), |&x| x as f64, n);
+        check(NegativeBinomial::new(3.0, 0.4).unwrap(), |&x| x as f64, n);
+        check(Poisson::new(4.5).unwrap(), |&x| x as f64, n);
+    }
+
+    /// Compare a closed-form mean vector and covariance matrix against Monte
+    /// Carlo estimates.
+    fn check_vector<D, T>(dist: D, to_vector: impl Fn(&T) -> DVector<f64>, num_samples: usize)
+    where
+        D: Distribution<Domain = T> + Moments<Mean = DVector<f64>, Variance = DMatrix<f64>>,
+    {
+        let mut rng = thread_rng();
+        let xs: Vec<DVector<f64>> = (0..num_samples)
+            .map(|_| to_vector(&dist.sample(&mut rng)))
+            .collect();
+        let n = num_samples as f64;
+        let mean = xs
+            .iter()
+            .fold(DVector::zeros(xs[0].len()), |acc, x| acc + x)
+            / n;
+        let cov = xs
+            .iter()
+            .fold(DMatrix::zeros(mean.len(), mean.len()), |acc, x| {
+                let d = x - &mean;
+                acc + &d * d.transpose()
+            })
+            / n;
+        let true_mean = dist.mean().unwrap();
+        let true_cov = dist.variance().unwrap();
+        let scale = true_cov.diagonal().max();
+        assert!(
+            (mean - &true_mean).amax() < 5.0 * (scale / n).sqrt(),
+            "mean {}",
+            true_mean
+        );
+        assert!((cov - &true_cov).amax() < 0.05 * scale, "cov {}", true_cov);
+    }
+
+    #[test]
+    fn vector_moments_match_samples() {
+        let n = 100_000;
+        check_vector(
+            Dirichlet::new(vec![1.0, 2.0, 3.0]).unwrap(),
+            |x: &Vec<f64>| DVector::from_column_slice(x),
+            n,
+        );
+        check_vector(
+            Multinomial::new(10, vec![0.2, 0.3, 0.5]).unwrap(),
+            |x: &Vec<u64>| DVector::from_iterator(x.len(), x.iter().map(|&k| k as f64)),
+            n,
+        );
+        check_vector(
+            MultivariateNormal::new(
+                DVector::from_vec(vec![1.0, -1.0]),
+                DMatrix::from_vec(2, 2, vec![2.0, 0.5, 0.5, 1.0]),
+            )
+            .unwrap(),
+            |x: &DVector<f64>| x.clone(),
+            n,
+        );
+    }
+
+    #[test]
+    fn wishart_moments_match_samples() {
+        let scale = DMatrix::from_vec(2, 2, vec![1.0, 0.3, 0.3, 0.5]);
+        let dist = Wishart::new(5.0, scale.clone()).unwrap();
+        let n = 100_000;
+        let mut rng = thread_rng();
+        let xs: Vec<DMatrix<f64>> = (0..n).map(|_| dist.sample(&mut rng)).collect();
+        let mean = xs.iter().fold(DMatrix::zeros(2, 2), |acc, x| acc + x) / n as f64;
+        let var = xs.iter().fold(DMatrix::zeros(2, 2), |acc, x| {
+            acc + (x - &mean).map(|d| d * d)
+        }) / n as f64;
+        assert!((mean - dist.mean().unwrap()).amax() < 0.05);
+        assert!((var - dist.variance().unwrap()).amax() < 0.05 * 10.0);
+        assert_eq!(dist.mode(), Some(scale * 2.0));
+        let entropy = -xs
+            .iter()
+            .map(|x| <Wishart as Distribution>::log_prob(&dist, x))
+            .sum::<f64>()
+            / n as f64;
+        assert!((entropy - dist.entropy().unwrap()).abs() < 0.05);
+    }
+
+    #[test]
+    fn undefined_moments() {
+        let cauchy = Cauchy::new(0.0, 1.0).unwrap();
+        assert_eq!(cauchy.mean(), None);
+        assert_eq!(cauchy.variance(), None);
+        assert_eq!(cauchy.mode(), Some(0.0));
+
+        let t = StudentT::new(1.5).unwrap();
+        assert_eq!(t.mean(), Some(0.0));
+        assert_eq!(t.variance(), None);
+
+        let pareto = Pareto::new(1.0, 1.0).unwrap();
+        assert_eq!(pareto.mean(), None);
+        assert_eq!(Uniform::new(0.0, 1.0).unwrap().mode(), None);
+        assert_eq!(Dirichlet::new(vec![0.5, 2.0]).unwrap().mode(), None);
+    }
+}
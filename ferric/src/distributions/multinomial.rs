@@ -3,7 +3,9 @@
 use rand_distr::Distribution as Distribution2;
 use rand_distr::WeightedIndex;
 
-use crate::distributions::Distribution;
+use crate::distributions::{Distribution, DistributionError, Moments};
+use crate::special::ln_gamma;
+use nalgebra::{DMatrix, DVector};
 use rand::Rng;
 
 /// Multinomial distribution over non-negative integer count vectors.
@@ -36,6 +38,12 @@ use rand::Rng;
 pub struct Multinomial {
     n: u64,
     probs: Vec<f64>,
+    /// Draws the category of a single trial.
+    trial: WeightedIndex<f64>,
+    /// $\ln p_i$ for each category.
+    log_probs: Vec<f64>,
+    /// $\ln n!$.
+    log_n_factorial: f64,
 }
 
 impl Multinomial {
@@ -47,29 +55,49 @@ impl Multinomial {
     /// Returns `Err` if `n` is zero, `probs` has fewer than 2 categories,
     /// any probability is not strictly positive, or the probabilities do not
     /// sum to 1 within $10^{-9}$.
-    pub fn new(n: u64, probs: Vec<f64>) -> Result<Multinomial, String> {
+    pub fn new(n: u64, probs: Vec<f64>) -> Result<Multinomial, DistributionError> {
         if n == 0 {
-            return Err("Multinomial: n must be at least 1".to_string());
+            return Err(DistributionError::InvalidParameter {
+                distribution: "Multinomial",
+                name: "n",
+                value: n as f64,
+                constraint: "at least 1",
+            });
         }
         if probs.len() < 2 {
-            return Err("Multinomial: probs must have at least 2 categories".to_string());
+            return Err(DistributionError::DimensionMismatch {
+                distribution: "Multinomial",
+                name: "probs",
+                expected: "at least 2".to_string(),
+                found: probs.len().to_string(),
+            });
         }
         for &p in &probs {
-            if p <= 0.0 {
-                return Err(format!(
-                    "Multinomial: all probabilities must be > 0, got {}",
-                    p
-                ));
+            if p.is_nan() || p <= 0.0 {
+                return Err(DistributionError::InvalidParameter {
+                    distribution: "Multinomial",
+                    name: "probability",
+                    value: p,
+                    constraint: "greater than 0",
+                });
             }
         }
         let sum: f64 = probs.iter().sum();
         if (sum - 1.0).abs() > 1e-9 {
-            return Err(format!(
-                "Multinomial: probabilities must sum to 1, got {}",
-                sum
-            ));
+            return Err(DistributionError::InvalidParameter {
+                distribution: "Multinomial",
+                name: "sum of probabilities",
+                value: sum,
+                constraint: "equal to 1",
+            });
         }
-        Ok(Multinomial { n, probs })
+        Ok(Multinomial {
+            n,
+            trial: WeightedIndex::new(&probs).expect("probabilities are validated above"),
+            log_probs: probs.iter().map(|p| p.ln()).collect(),
+            log_n_factorial: ln_gamma(n as f64 + 1.0),
+            probs,
+        })
     }
 }
 
@@ -78,9 +106,8 @@ impl<R: Rng + ?Sized> Distribution<R> for Multinomial {
 
     fn sample(&self, rng: &mut R) -> Vec<u64> {
         let mut counts = vec![0u64; self.probs.len()];
-        let weighted = WeightedIndex::new(&self.probs).unwrap();
         for _ in 0..self.n {
-            counts[weighted.sample(rng)] += 1;
+            counts[self.trial.sample(rng)] += 1;
         }
         counts
     }
@@ -95,15 +122,12 @@ impl<R: Rng + ?Sized> Distribution<R> for Multinomial {
         if k.iter().sum::<u64>() != self.n {
             return f64::NEG_INFINITY;
         }
-        let n_f = self.n as f64;
-        let log_multinomial = libm::lgamma(n_f + 1.0)
-            - k.iter()
-                .map(|&ki| libm::lgamma(ki as f64 + 1.0))
-                .sum::<f64>();
+        let log_multinomial =
+            self.log_n_factorial - k.iter().map(|&ki| ln_gamma(ki as f64 + 1.0)).sum::<f64>();
         let log_kernel: f64 = k
             .iter()
-            .zip(self.probs.iter())
-            .map(|(&ki, &pi)| ki as f64 * pi.ln())
+            .zip(self.log_probs.iter())
+            .map(|(&ki, &log_pi)| ki as f64 * log_pi)
             .sum();
         log_multinomial + log_kernel
     }
@@ -113,6 +137,26 @@ impl<R: Rng + ?Sized> Distribution<R> for Multinomial {
     }
 }
 
+impl Moments for Multinomial {
+    type Mean = DVector<f64>;
+    type Variance = DMatrix<f64>;
+    type Mode = Vec<u64>;
+
+    fn mean(&self) -> Option<DVector<f64>> {
+        let n = self.n as f64;
+        Some(DVector::from_iterator(
+            self.probs.len(),
+            self.probs.iter().map(|p| n * p),
+        ))
+    }
+
+    /// Returns the covariance matrix $n(\operatorname{diag}(p) - p p^\top)$.
+    fn variance(&self) -> Option<DMatrix<f64>> {
+        let p = DVector::from_column_slice(&self.probs);
+        Some((DMatrix::from_diagonal(&p) - &p * p.transpose()) * self.n as f64)
+    }
+}
+
 impl std::fmt::Display for Multinomial {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -137,7 +181,7 @@ mod tests {
         let dist = Multinomial::new(n, probs.clone()).unwrap();
         println!("dist = {}", dist);
         let trials = 1000;
-        let mut sums = vec![0u64; 3];
+        let mut sums = [0u64; 3];
         for _ in 0..trials {
             let counts = dist.sample(&mut rng);
             assert_eq!(counts.iter().sum::<u64>(), n);
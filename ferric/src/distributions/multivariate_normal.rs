@@ -2,10 +2,9 @@
 
 use nalgebra::{Cholesky, DMatrix, DVector, Dyn};
 use rand::Rng;
-use rand_distr::Distribution as Distribution2;
-use rand_distr::Normal as Normal2;
+use rand_distr::StandardNormal;
 
-use crate::distributions::Distribution;
+use crate::distributions::{Distribution, DistributionError, Moments};
 
 /// Multivariate normal (Gaussian) distribution over $\mathbb{R}^k$.
 ///
@@ -40,6 +39,10 @@ use crate::distributions::Distribution;
 pub struct MultivariateNormal {
     mean: DVector<f64>,
     chol: Cholesky<f64, Dyn>,
+    /// The lower Cholesky factor $L$.
+    l: DMatrix<f64>,
+    /// $-\tfrac{k}{2}\ln(2\pi) - \tfrac{1}{2}\ln|\boldsymbol\Sigma|$.
+    log_norm: f64,
 }
 
 impl MultivariateNormal {
@@ -50,20 +53,32 @@ impl MultivariateNormal {
     ///
     /// Returns `Err` if the dimensions of `cov` do not match the length of
     /// `mean`, or if `cov` is not symmetric positive-definite.
-    pub fn new(mean: DVector<f64>, cov: DMatrix<f64>) -> Result<MultivariateNormal, String> {
+    pub fn new(
+        mean: DVector<f64>,
+        cov: DMatrix<f64>,
+    ) -> Result<MultivariateNormal, DistributionError> {
         let k = mean.len();
         if cov.nrows() != k || cov.ncols() != k {
-            return Err(format!(
-                "MultivariateNormal: covariance {}×{} must match mean length {}",
-                cov.nrows(),
-                cov.ncols(),
-                k
-            ));
-        }
-        match Cholesky::new(cov) {
-            Some(chol) => Ok(MultivariateNormal { mean, chol }),
-            None => Err("MultivariateNormal: covariance is not positive definite".to_string()),
+            return Err(DistributionError::DimensionMismatch {
+                distribution: "MultivariateNormal",
+                name: "covariance",
+                expected: format!("{}×{}", k, k),
+                found: format!("{}×{}", cov.nrows(), cov.ncols()),
+            });
         }
+        let chol = Cholesky::new(cov).ok_or(DistributionError::NotPositiveDefinite {
+            distribution: "MultivariateNormal",
+            name: "covariance",
+        })?;
+        let l = chol.l();
+        let log_det: f64 = (0..k).map(|i| l[(i, i)].ln()).sum::<f64>() * 2.0;
+        let log_norm = -0.5 * (k as f64 * (2.0 * std::f64::consts::PI).ln() + log_det);
+        Ok(MultivariateNormal {
+            mean,
+            chol,
+            l,
+            log_norm,
+        })
     }
 }
 
@@ -75,9 +90,8 @@ impl<R: Rng + ?Sized> Distribution<R> for MultivariateNormal {
     /// $\mathbf{z} \sim \mathcal{N}(\mathbf{0}, I_k)$.
     fn sample(&self, rng: &mut R) -> DVector<f64> {
         let k = self.mean.len();
-        let normal = Normal2::new(0.0, 1.0).unwrap();
-        let z = DVector::from_fn(k, |_, _| normal.sample(rng));
-        &self.mean + self.chol.l() * z
+        let z = DVector::from_fn(k, |_, _| rng.sample::<f64, _>(StandardNormal));
+        &self.mean + &self.l * z
     }
 
     /// Returns
@@ -87,16 +101,13 @@ impl<R: Rng + ?Sized> Distribution<R> for MultivariateNormal {
     ///     \boldsymbol\Sigma^{-1}(\mathbf{x}-\boldsymbol\mu)$.
     ///
     /// Uses the Cholesky factor $L$ stored at construction time:
-    /// $\ln|\boldsymbol\Sigma| = 2\sum_i \ln L_{ii}$ and the Mahalanobis
-    /// term is computed via back-substitution.
+    /// $\ln|\boldsymbol\Sigma| = 2\sum_i \ln L_{ii}$ is precomputed and the
+    /// Mahalanobis term is computed via back-substitution.
     fn log_prob(&self, x: &DVector<f64>) -> f64 {
-        let k = self.mean.len() as f64;
         let diff = x - &self.mean;
         let sol = self.chol.solve(&diff);
         let mahal_sq = diff.dot(&sol);
-        let l = self.chol.l();
-        let log_det: f64 = (0..l.nrows()).map(|i| l[(i, i)].ln()).sum::<f64>() * 2.0;
-        -0.5 * (k * (2.0 * std::f64::consts::PI).ln() + log_det + mahal_sq)
+        self.log_norm - 0.5 * mahal_sq
     }
 
     fn is_discrete(&self) -> bool {
@@ -104,6 +115,32 @@ impl<R: Rng + ?Sized> Distribution<R> for MultivariateNormal {
     }
 }
 
+impl Moments for MultivariateNormal {
+    type Mean = DVector<f64>;
+    type Variance = DMatrix<f64>;
+    type Mode = DVector<f64>;
+
+    fn mean(&self) -> Option<DVector<f64>> {
+        Some(self.mean.clone())
+    }
+
+    fn variance(&self) -> Option<DMatrix<f64>> {
+        Some(&self.l * self.l.transpose())
+    }
+
+    fn mode(&self) -> Option<DVector<f64>> {
+        Some(self.mean.clone())
+    }
+
+    /// Returns $\tfrac{k}{2}(1 + \ln 2\pi) + \tfrac{1}{2}\ln\det\Sigma$.
+    fn entropy(&self) -> Option<f64> {
+        let k = self.mean.len() as f64;
+        let l = self.chol.l_dirty();
+        let half_log_det: f64 = (0..l.nrows()).map(|i| l[(i, i)].ln()).sum();
+        Some(0.5 * k * (1.0 + (2.0 * std::f64::consts::PI).ln()) + half_log_det)
+    }
+}
+
 impl std::fmt::Display for MultivariateNormal {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -4,15 +4,23 @@ use nalgebra::{Cholesky, DMatrix, DVector, Dyn};
 use rand::Rng;
 use rand_distr::ChiSquared as ChiSquared2;
 use rand_distr::Distribution as Distribution2;
-use rand_distr::Normal as Normal2;
+use rand_distr::StandardNormal;
 
-use crate::distributions::Distribution;
+use crate::distributions::{Distribution, DistributionError};
+use crate::special::ln_gamma;
 
 /// Multivariate Student's t distribution over column vectors.
 pub struct MultivariateStudentT {
     mean: DVector<f64>,
     chol: Cholesky<f64, Dyn>,
     df: f64,
+    /// The lower Cholesky factor $L$.
+    l: DMatrix<f64>,
+    /// The chi-squared mixing distribution with `df` degrees of freedom.
+    mixing: ChiSquared2<f64>,
+    /// $\ln\Gamma(\tfrac{\nu+k}{2}) - \ln\Gamma(\tfrac{\nu}{2})
+    ///   - \tfrac{1}{2}(k\ln(\nu\pi) + \ln|\Sigma|)$.
+    log_norm: f64,
 }
 
 impl MultivariateStudentT {
@@ -26,26 +34,42 @@ impl MultivariateStudentT {
         mean: DVector<f64>,
         scale: DMatrix<f64>,
         df: f64,
-    ) -> Result<MultivariateStudentT, String> {
-        if df <= 0.0 {
-            return Err(format!(
-                "MultivariateStudentT: illegal df `{}` should be greater than 0",
-                df
-            ));
+    ) -> Result<MultivariateStudentT, DistributionError> {
+        if df.is_nan() || df <= 0.0 {
+            return Err(DistributionError::InvalidParameter {
+                distribution: "MultivariateStudentT",
+                name: "df",
+                value: df,
+                constraint: "greater than 0",
+            });
         }
         let k = mean.len();
         if scale.nrows() != k || scale.ncols() != k {
-            return Err(format!(
-                "MultivariateStudentT: scale {}×{} must match mean length {}",
-                scale.nrows(),
-                scale.ncols(),
-                k
-            ));
-        }
-        match Cholesky::new(scale.clone()) {
-            Some(chol) => Ok(MultivariateStudentT { mean, chol, df }),
-            None => Err("MultivariateStudentT: scale is not positive definite".to_string()),
+            return Err(DistributionError::DimensionMismatch {
+                distribution: "MultivariateStudentT",
+                name: "scale",
+                expected: format!("{}×{}", k, k),
+                found: format!("{}×{}", scale.nrows(), scale.ncols()),
+            });
         }
+        let chol = Cholesky::new(scale).ok_or(DistributionError::NotPositiveDefinite {
+            distribution: "MultivariateStudentT",
+            name: "scale",
+        })?;
+        let l = chol.l();
+        let log_det: f64 = (0..k).map(|i| l[(i, i)].ln()).sum::<f64>() * 2.0;
+        let k = k as f64;
+        let log_norm = ln_gamma((df + k) / 2.0)
+            - ln_gamma(df / 2.0)
+            - 0.5 * (k * (df * std::f64::consts::PI).ln() + log_det);
+        Ok(MultivariateStudentT {
+            mean,
+            chol,
+            df,
+            l,
+            mixing: ChiSquared2::new(df).expect("df is positive"),
+            log_norm,
+        })
     }
 }
 
@@ -54,10 +78,9 @@ impl<R: Rng + ?Sized> Distribution<R> for MultivariateStudentT {
 
     fn sample(&self, rng: &mut R) -> DVector<f64> {
         let k = self.mean.len();
-        let normal = Normal2::new(0.0, 1.0).unwrap();
-        let z = DVector::from_fn(k, |_, _| normal.sample(rng));
-        let g = ChiSquared2::new(self.df).unwrap().sample(rng);
-        &self.mean + self.chol.l() * z * (self.df / g).sqrt()
+        let z = DVector::from_fn(k, |_, _| rng.sample::<f64, _>(StandardNormal));
+        let g = self.mixing.sample(rng);
+        &self.mean + &self.l * z * (self.df / g).sqrt()
     }
 
     fn log_prob(&self, x: &DVector<f64>) -> f64 {
@@ -68,12 +91,7 @@ impl<R: Rng + ?Sized> Distribution<R> for MultivariateStudentT {
         let diff = x - &self.mean;
         let sol = self.chol.solve(&diff);
         let mahal_sq = diff.dot(&sol);
-        let l = self.chol.l();
-        let log_det: f64 = (0..l.nrows()).map(|i| l[(i, i)].ln()).sum::<f64>() * 2.0;
-        libm::lgamma((self.df + k) / 2.0)
-            - libm::lgamma(self.df / 2.0)
-            - 0.5 * (k * (self.df * std::f64::consts::PI).ln() + log_det)
-            - ((self.df + k) / 2.0) * (1.0 + mahal_sq / self.df).ln()
+        self.log_norm - ((self.df + k) / 2.0) * (1.0 + mahal_sq / self.df).ln()
     }
 
     fn is_discrete(&self) -> bool {
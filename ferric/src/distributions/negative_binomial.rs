@@ -5,7 +5,9 @@ use rand_distr::Distribution as Distribution2;
 use rand_distr::Gamma as Gamma2;
 use rand_distr::Poisson;
 
-use crate::distributions::Distribution;
+use crate::distributions::univariate::{discrete_quantile, discrete_quantile_sf};
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
+use crate::special::{beta_inc, ln_gamma};
 
 /// Negative binomial distribution over the non-negative integers.
 ///
@@ -34,6 +36,11 @@ use crate::distributions::Distribution;
 pub struct NegativeBinomial {
     r: f64,
     p: f64,
+    /// The Gamma distribution of the Poisson rate, or `None` when $p = 1$.
+    rate: Option<Gamma2<f64>>,
+    /// $r\ln p - \ln\Gamma(r)$ and $\ln(1-p)$.
+    log_norm: f64,
+    log_q: f64,
 }
 
 impl NegativeBinomial {
@@ -44,19 +51,31 @@ impl NegativeBinomial {
     ///
     /// Returns `Err` if `r` is not strictly positive or `p` is not in
     /// $(0, 1]$.
-    pub fn new(r: f64, p: f64) -> Result<NegativeBinomial, String> {
-        if r <= 0.0 {
-            Err(format!(
-                "NegativeBinomial: illegal r `{}` should be greater than 0",
-                r
-            ))
+    pub fn new(r: f64, p: f64) -> Result<NegativeBinomial, DistributionError> {
+        if r.is_nan() || r <= 0.0 {
+            Err(DistributionError::InvalidParameter {
+                distribution: "NegativeBinomial",
+                name: "r",
+                value: r,
+                constraint: "greater than 0",
+            })
         } else if !(p > 0.0 && p <= 1.0) {
-            Err(format!(
-                "NegativeBinomial: illegal p `{}` must be in (0, 1]",
-                p
-            ))
+            Err(DistributionError::InvalidParameter {
+                distribution: "NegativeBinomial",
+                name: "p",
+                value: p,
+                constraint: "in (0, 1]",
+            })
         } else {
-            Ok(NegativeBinomial { r, p })
+            let rate = (p < 1.0)
+                .then(|| Gamma2::new(r, (1.0 - p) / p).expect("r and the scale are positive"));
+            Ok(NegativeBinomial {
+                r,
+                p,
+                rate,
+                log_norm: r * p.ln() - ln_gamma(r),
+                log_q: (1.0 - p).ln(),
+            })
         }
     }
 }
@@ -67,11 +86,10 @@ impl<R: Rng + ?Sized> Distribution<R> for NegativeBinomial {
     /// Draw a sample via the Poisson-Gamma mixture:
     /// $\lambda \sim \mathrm{Gamma}(r, (1-p)/p)$ then $X \sim \mathrm{Poisson}(\lambda)$.
     fn sample(&self, rng: &mut R) -> u64 {
-        if self.p == 1.0 {
+        let Some(rate) = &self.rate else {
             return 0;
-        }
-        let scale = (1.0 - self.p) / self.p;
-        let lambda = Gamma2::new(self.r, scale).unwrap().sample(rng);
+        };
+        let lambda = rate.sample(rng);
         Poisson::new(lambda).unwrap().sample(rng) as u64
     }
 
@@ -81,9 +99,7 @@ impl<R: Rng + ?Sized> Distribution<R> for NegativeBinomial {
             return if *x == 0 { 0.0 } else { f64::NEG_INFINITY };
         }
         let k = *x as f64;
-        libm::lgamma(k + self.r) - libm::lgamma(self.r) - libm::lgamma(k + 1.0)
-            + self.r * self.p.ln()
-            + k * (1.0 - self.p).ln()
+        ln_gamma(k + self.r) - ln_gamma(k + 1.0) + self.log_norm + k * self.log_q
     }
 
     fn is_discrete(&self) -> bool {
@@ -91,6 +107,49 @@ impl<R: Rng + ?Sized> Distribution<R> for NegativeBinomial {
     }
 }
 
+impl Moments for NegativeBinomial {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = u64;
+
+    fn mean(&self) -> Option<f64> {
+        Some(self.r * (1.0 - self.p) / self.p)
+    }
+
+    fn variance(&self) -> Option<f64> {
+        Some(self.r * (1.0 - self.p) / (self.p * self.p))
+    }
+
+    fn mode(&self) -> Option<u64> {
+        if self.r <= 1.0 {
+            Some(0)
+        } else {
+            Some(((self.r - 1.0) * (1.0 - self.p) / self.p).floor() as u64)
+        }
+    }
+}
+
+impl Univariate for NegativeBinomial {
+    /// Returns $I_p(r, k + 1)$.
+    fn cdf(&self, x: &u64) -> f64 {
+        beta_inc(self.r, *x as f64 + 1.0, self.p)
+    }
+
+    fn sf(&self, x: &u64) -> f64 {
+        beta_inc(*x as f64 + 1.0, self.r, 1.0 - self.p)
+    }
+
+    fn quantile(&self, p: f64) -> u64 {
+        let upper = if self.p == 1.0 { 0 } else { u64::MAX };
+        discrete_quantile(p, (0, upper), |k| self.cdf(&k))
+    }
+
+    fn quantile_sf(&self, q: f64) -> u64 {
+        let upper = if self.p == 1.0 { 0 } else { u64::MAX };
+        discrete_quantile_sf(q, (0, upper), |k| self.sf(&k))
+    }
+}
+
 impl std::fmt::Display for NegativeBinomial {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "NegativeBinomial {{ r = {}, p = {} }}", self.r, self.p)
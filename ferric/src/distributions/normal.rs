@@ -3,7 +3,10 @@
 use rand_distr::Distribution as Distribution2;
 use rand_distr::Normal as Normal2;
 
-use crate::distributions::Distribution;
+use crate::distributions::distribution::check_batch_len;
+use crate::distributions::univariate::check_probability;
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
+use crate::special::{log_normal_cdf, normal_cdf, normal_quantile};
 use rand::Rng;
 
 /// Normal (Gaussian) distribution over the real line.
@@ -32,6 +35,9 @@ use rand::Rng;
 pub struct Normal {
     mean: f64,
     std_dev: f64,
+    sampler: Normal2<f64>,
+    /// $-\ln\sigma - \tfrac{1}{2}\ln(2\pi)$.
+    log_norm: f64,
 }
 
 impl Normal {
@@ -41,11 +47,28 @@ impl Normal {
     /// # Errors
     ///
     /// Returns `Err` if `std_dev` is not strictly positive.
-    pub fn new(mean: f64, std_dev: f64) -> Result<Normal, String> {
+    pub fn new(mean: f64, std_dev: f64) -> Result<Normal, DistributionError> {
         if std_dev <= 0f64 {
-            Err(format! {"Normal: illegal std_dev `{}` should be greater than 0", std_dev})
+            Err(DistributionError::InvalidParameter {
+                distribution: "Normal",
+                name: "std_dev",
+                value: std_dev,
+                constraint: "greater than 0",
+            })
         } else {
-            Ok(Normal { mean, std_dev })
+            Normal2::new(mean, std_dev)
+                .map(|sampler| Normal {
+                    mean,
+                    std_dev,
+                    sampler,
+                    log_norm: -std_dev.ln() - 0.5 * (2.0 * std::f64::consts::PI).ln(),
+                })
+                .map_err(|_| DistributionError::InvalidParameter {
+                    distribution: "Normal",
+                    name: "std_dev",
+                    value: std_dev,
+                    constraint: "finite",
+                })
         }
     }
 }
@@ -54,7 +77,7 @@ impl<R: Rng + ?Sized> Distribution<R> for Normal {
     type Domain = f64;
 
     fn sample(&self, rng: &mut R) -> f64 {
-        Normal2::new(self.mean, self.std_dev).unwrap().sample(rng)
+        self.sampler.sample(rng)
     }
 
     /// Returns the log-density
@@ -62,7 +85,28 @@ impl<R: Rng + ?Sized> Distribution<R> for Normal {
     ///   - \ln\sigma - \tfrac{1}{2}\ln(2\pi)$.
     fn log_prob(&self, x: &f64) -> f64 {
         let z = (x - self.mean) / self.std_dev;
-        -0.5 * z * z - self.std_dev.ln() - 0.5 * (2.0 * std::f64::consts::PI).ln()
+        -0.5 * z * z + self.log_norm
+    }
+
+    fn log_prob_batch(&self, xs: &[f64]) -> f64 {
+        let inv_std_dev = 1.0 / self.std_dev;
+        let sum_sq: f64 = xs
+            .iter()
+            .map(|x| {
+                let z = (x - self.mean) * inv_std_dev;
+                z * z
+            })
+            .sum();
+        -0.5 * sum_sq + xs.len() as f64 * self.log_norm
+    }
+
+    fn log_prob_batch_into(&self, xs: &[f64], out: &mut [f64]) {
+        check_batch_len(xs.len(), out.len());
+        let inv_std_dev = 1.0 / self.std_dev;
+        for (x, log_prob) in xs.iter().zip(out.iter_mut()) {
+            let z = (x - self.mean) * inv_std_dev;
+            *log_prob = -0.5 * z * z + self.log_norm;
+        }
     }
 
     fn is_discrete(&self) -> bool {
@@ -70,6 +114,59 @@ impl<R: Rng + ?Sized> Distribution<R> for Normal {
     }
 }
 
+impl Moments for Normal {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = f64;
+
+    fn mean(&self) -> Option<f64> {
+        Some(self.mean)
+    }
+
+    fn variance(&self) -> Option<f64> {
+        Some(self.std_dev * self.std_dev)
+    }
+
+    fn mode(&self) -> Option<f64> {
+        Some(self.mean)
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        Some(
+            0.5 * (2.0 * std::f64::consts::PI * std::f64::consts::E * self.std_dev * self.std_dev)
+                .ln(),
+        )
+    }
+}
+
+impl Univariate for Normal {
+    fn cdf(&self, x: &f64) -> f64 {
+        normal_cdf((x - self.mean) / self.std_dev)
+    }
+
+    fn log_cdf(&self, x: &f64) -> f64 {
+        log_normal_cdf((x - self.mean) / self.std_dev)
+    }
+
+    fn sf(&self, x: &f64) -> f64 {
+        normal_cdf((self.mean - x) / self.std_dev)
+    }
+
+    fn log_sf(&self, x: &f64) -> f64 {
+        log_normal_cdf((self.mean - x) / self.std_dev)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        check_probability(p);
+        self.mean + self.std_dev * normal_quantile(p)
+    }
+
+    fn quantile_sf(&self, q: f64) -> f64 {
+        check_probability(q);
+        self.mean - self.std_dev * normal_quantile(q)
+    }
+}
+
 impl std::fmt::Display for Normal {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -127,4 +224,32 @@ mod tests {
     fn normal_negative_std() {
         let _dist = Normal::new(0.0, -1.0).unwrap();
     }
+
+    #[test]
+    fn normal_log_prob_batch() {
+        let dist = Normal::new(1.5, 2.0).unwrap();
+        let xs = [-3.0, 0.0, 1.5, 4.2];
+        let expected: Vec<f64> = xs
+            .iter()
+            .map(|x| <Normal as Distribution<ThreadRng>>::log_prob(&dist, x))
+            .collect();
+        let total = <Normal as Distribution<ThreadRng>>::log_prob_batch(&dist, &xs);
+        assert!((total - expected.iter().sum::<f64>()).abs() < 1e-10);
+        let mut out = [0.0; 4];
+        <Normal as Distribution<ThreadRng>>::log_prob_batch_into(&dist, &xs, &mut out);
+        for (lp, expected) in out.iter().zip(expected) {
+            assert!((lp - expected).abs() < 1e-12);
+        }
+        assert_eq!(
+            <Normal as Distribution<ThreadRng>>::log_prob_batch(&dist, &[]),
+            0.0
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "3 observations but 2 outputs")]
+    fn normal_log_prob_batch_into_length_mismatch() {
+        let dist = Normal::new(0.0, 1.0).unwrap();
+        <Normal as Distribution<ThreadRng>>::log_prob_batch_into(&dist, &[0.0; 3], &mut [0.0; 2]);
+    }
 }
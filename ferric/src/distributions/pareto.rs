@@ -3,8 +3,10 @@
 use rand::Rng;
 use rand_distr::Distribution as Distribution2;
 use rand_distr::Pareto as Pareto2;
+use rand_distr::ParetoError;
 
-use crate::distributions::Distribution;
+use crate::distributions::univariate::check_probability;
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
 
 /// Pareto distribution over $[x_m, \infty)$.
 ///
@@ -32,6 +34,9 @@ use crate::distributions::Distribution;
 pub struct Pareto {
     x_m: f64,
     alpha: f64,
+    sampler: Pareto2<f64>,
+    /// $\ln\alpha + \alpha\ln x_m$.
+    log_norm: f64,
 }
 
 impl Pareto {
@@ -41,20 +46,28 @@ impl Pareto {
     /// # Errors
     ///
     /// Returns `Err` if `x_m` or `alpha` is not strictly positive.
-    pub fn new(x_m: f64, alpha: f64) -> Result<Pareto, String> {
-        if x_m <= 0.0 {
-            Err(format!(
-                "Pareto: illegal scale `{}` should be greater than 0",
-                x_m
-            ))
-        } else if alpha <= 0.0 {
-            Err(format!(
-                "Pareto: illegal shape `{}` should be greater than 0",
-                alpha
-            ))
-        } else {
-            Ok(Pareto { x_m, alpha })
-        }
+    pub fn new(x_m: f64, alpha: f64) -> Result<Pareto, DistributionError> {
+        Pareto2::new(x_m, alpha)
+            .map(|sampler| Pareto {
+                x_m,
+                alpha,
+                sampler,
+                log_norm: alpha.ln() + alpha * x_m.ln(),
+            })
+            .map_err(|e| match e {
+                ParetoError::ScaleTooSmall => DistributionError::InvalidParameter {
+                    distribution: "Pareto",
+                    name: "scale",
+                    value: x_m,
+                    constraint: "greater than 0",
+                },
+                ParetoError::ShapeTooSmall => DistributionError::InvalidParameter {
+                    distribution: "Pareto",
+                    name: "shape",
+                    value: alpha,
+                    constraint: "greater than 0",
+                },
+            })
     }
 }
 
@@ -62,7 +75,7 @@ impl<R: Rng + ?Sized> Distribution<R> for Pareto {
     type Domain = f64;
 
     fn sample(&self, rng: &mut R) -> f64 {
-        Pareto2::new(self.x_m, self.alpha).unwrap().sample(rng)
+        self.sampler.sample(rng)
     }
 
     /// Returns $\ln\alpha + \alpha\ln x_m - (\alpha+1)\ln x$, or
@@ -71,12 +84,64 @@ impl<R: Rng + ?Sized> Distribution<R> for Pareto {
         if *x < self.x_m {
             return f64::NEG_INFINITY;
         }
-        self.alpha.ln() + self.alpha * self.x_m.ln() - (self.alpha + 1.0) * x.ln()
+        self.log_norm - (self.alpha + 1.0) * x.ln()
     }
 
     fn is_discrete(&self) -> bool {
         false
     }
+
+    fn support(&self) -> (f64, f64) {
+        (self.x_m, f64::INFINITY)
+    }
+}
+
+impl Moments for Pareto {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = f64;
+
+    /// Defined for shape $\alpha > 1$.
+    fn mean(&self) -> Option<f64> {
+        (self.alpha > 1.0).then(|| self.alpha * self.x_m / (self.alpha - 1.0))
+    }
+
+    /// Defined for shape $\alpha > 2$.
+    fn variance(&self) -> Option<f64> {
+        (self.alpha > 2.0).then(|| {
+            self.x_m * self.x_m * self.alpha / ((self.alpha - 1.0).powi(2) * (self.alpha - 2.0))
+        })
+    }
+
+    fn mode(&self) -> Option<f64> {
+        Some(self.x_m)
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        Some((self.x_m / self.alpha).ln() + 1.0 + 1.0 / self.alpha)
+    }
+}
+
+impl Univariate for Pareto {
+    fn cdf(&self, x: &f64) -> f64 {
+        -self.log_sf(x).exp_m1()
+    }
+
+    fn sf(&self, x: &f64) -> f64 {
+        self.log_sf(x).exp()
+    }
+
+    fn log_sf(&self, x: &f64) -> f64 {
+        if *x <= self.x_m {
+            return 0.0;
+        }
+        self.alpha * (self.x_m / x).ln()
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        check_probability(p);
+        self.x_m * (1.0 - p).powf(-1.0 / self.alpha)
+    }
 }
 
 impl std::fmt::Display for Pareto {
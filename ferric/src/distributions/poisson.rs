@@ -3,7 +3,10 @@
 use rand_distr::Distribution as Distribution2;
 use rand_distr::Poisson as Poisson2;
 
-use crate::distributions::Distribution;
+use crate::distributions::distribution::check_batch_len;
+use crate::distributions::univariate::{discrete_quantile, discrete_quantile_sf};
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
+use crate::special::{gamma_p, gamma_q, ln_gamma};
 use rand::Rng;
 
 /// Poisson distribution over non-negative integers.
@@ -31,6 +34,9 @@ use rand::Rng;
 pub struct Poisson {
     /// Expected number of events (rate).
     rate: f64,
+    sampler: Poisson2<f64>,
+    /// $\ln\lambda$.
+    log_rate: f64,
 }
 
 impl Poisson {
@@ -39,11 +45,20 @@ impl Poisson {
     /// # Errors
     ///
     /// Returns `Err` if `rate` is not strictly positive.
-    pub fn new(rate: f64) -> Result<Poisson, String> {
-        if rate <= 0f64 {
-            Err(format! {"Poisson: illegal rate `{}` should be greater than 0", rate})
+    pub fn new(rate: f64) -> Result<Poisson, DistributionError> {
+        if rate.is_nan() || rate <= 0f64 {
+            Err(DistributionError::InvalidParameter {
+                distribution: "Poisson",
+                name: "rate",
+                value: rate,
+                constraint: "greater than 0",
+            })
         } else {
-            Ok(Poisson { rate })
+            Ok(Poisson {
+                rate,
+                sampler: Poisson2::new(rate).expect("rate is positive"),
+                log_rate: rate.ln(),
+            })
         }
     }
 }
@@ -52,14 +67,25 @@ impl<R: Rng + ?Sized> Distribution<R> for Poisson {
     type Domain = u64;
 
     fn sample(&self, rng: &mut R) -> u64 {
-        Poisson2::new(self.rate).unwrap().sample(rng) as u64
+        self.sampler.sample(rng) as u64
     }
 
     /// Returns $k \ln\lambda - \lambda - \ln(k!)$ where $k$ is `*x`.
     fn log_prob(&self, x: &u64) -> f64 {
-        let k = *x as f64;
-        let log_factorial: f64 = (1..=*x).map(|i| (i as f64).ln()).sum();
-        k * self.rate.ln() - self.rate - log_factorial
+        *x as f64 * self.log_rate - self.rate - ln_gamma(*x as f64 + 1.0)
+    }
+
+    fn log_prob_batch(&self, xs: &[u64]) -> f64 {
+        let sum_k: f64 = xs.iter().map(|&k| k as f64).sum();
+        let sum_log_factorial: f64 = xs.iter().map(|&k| ln_gamma(k as f64 + 1.0)).sum();
+        sum_k * self.log_rate - xs.len() as f64 * self.rate - sum_log_factorial
+    }
+
+    fn log_prob_batch_into(&self, xs: &[u64], out: &mut [f64]) {
+        check_batch_len(xs.len(), out.len());
+        for (&k, log_prob) in xs.iter().zip(out.iter_mut()) {
+            *log_prob = k as f64 * self.log_rate - self.rate - ln_gamma(k as f64 + 1.0);
+        }
     }
 
     fn is_discrete(&self) -> bool {
@@ -67,6 +93,43 @@ impl<R: Rng + ?Sized> Distribution<R> for Poisson {
     }
 }
 
+impl Moments for Poisson {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = u64;
+
+    fn mean(&self) -> Option<f64> {
+        Some(self.rate)
+    }
+
+    fn variance(&self) -> Option<f64> {
+        Some(self.rate)
+    }
+
+    fn mode(&self) -> Option<u64> {
+        Some(self.rate.floor() as u64)
+    }
+}
+
+impl Univariate for Poisson {
+    /// Returns $Q(k + 1, \lambda)$.
+    fn cdf(&self, x: &u64) -> f64 {
+        gamma_q(*x as f64 + 1.0, self.rate)
+    }
+
+    fn sf(&self, x: &u64) -> f64 {
+        gamma_p(*x as f64 + 1.0, self.rate)
+    }
+
+    fn quantile(&self, p: f64) -> u64 {
+        discrete_quantile(p, (0, u64::MAX), |k| self.cdf(&k))
+    }
+
+    fn quantile_sf(&self, q: f64) -> u64 {
+        discrete_quantile_sf(q, (0, u64::MAX), |k| self.sf(&k))
+    }
+}
+
 impl std::fmt::Display for Poisson {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Poisson {{ rate = {} }}", self.rate)
@@ -126,4 +189,27 @@ mod tests {
         let bad = Poisson::new(0.0);
         assert!(bad.is_err());
     }
+
+    #[test]
+    fn poisson_log_prob_large_count() {
+        // at k = rate = 10^6, Stirling's series gives -ln(2 pi k) / 2 - 1 / (12 k) + ...
+        let dist = Poisson::new(1e6).unwrap();
+        let lp = Distribution::<rand::rngs::ThreadRng>::log_prob(&dist, &1_000_000u64);
+        assert!((lp - -7.826693895520143).abs() < 1e-6);
+    }
+
+    #[test]
+    fn poisson_log_prob_batch() {
+        let dist = Poisson::new(3.5).unwrap();
+        let xs = [0u64, 1, 4, 12];
+        let expected: Vec<f64> = xs
+            .iter()
+            .map(|k| Distribution::<rand::rngs::ThreadRng>::log_prob(&dist, k))
+            .collect();
+        let total = Distribution::<rand::rngs::ThreadRng>::log_prob_batch(&dist, &xs);
+        assert!((total - expected.iter().sum::<f64>()).abs() < 1e-10);
+        let mut out = [0.0; 4];
+        Distribution::<rand::rngs::ThreadRng>::log_prob_batch_into(&dist, &xs, &mut out);
+        assert_eq!(out.to_vec(), expected);
+    }
 }
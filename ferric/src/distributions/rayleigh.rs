@@ -1,10 +1,10 @@
 // Copyright 2022 The Ferric AI Project Developers
 
 use rand::Rng;
-use rand_distr::Distribution as Distribution2;
-use rand_distr::Uniform;
 
-use crate::distributions::Distribution;
+use crate::distributions::univariate::check_probability;
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
+use crate::special::EULER_GAMMA;
 
 /// Rayleigh distribution over the non-negative reals.
 ///
@@ -32,6 +32,8 @@ use crate::distributions::Distribution;
 /// ```
 pub struct Rayleigh {
     sigma: f64,
+    /// $2\ln\sigma$.
+    log_sigma_sq: f64,
 }
 
 impl Rayleigh {
@@ -40,14 +42,19 @@ impl Rayleigh {
     /// # Errors
     ///
     /// Returns `Err` if `sigma` is not strictly positive.
-    pub fn new(sigma: f64) -> Result<Rayleigh, String> {
+    pub fn new(sigma: f64) -> Result<Rayleigh, DistributionError> {
         if sigma <= 0.0 {
-            Err(format!(
-                "Rayleigh: illegal scale `{}` should be greater than 0",
-                sigma
-            ))
+            Err(DistributionError::InvalidParameter {
+                distribution: "Rayleigh",
+                name: "scale",
+                value: sigma,
+                constraint: "greater than 0",
+            })
         } else {
-            Ok(Rayleigh { sigma })
+            Ok(Rayleigh {
+                sigma,
+                log_sigma_sq: 2.0 * sigma.ln(),
+            })
         }
     }
 }
@@ -58,7 +65,7 @@ impl<R: Rng + ?Sized> Distribution<R> for Rayleigh {
     /// Draw a sample via the quantile function:
     /// $X = \sigma\sqrt{-2\ln(1-u)}$ where $u \sim U(0,1)$.
     fn sample(&self, rng: &mut R) -> f64 {
-        let u: f64 = Uniform::new(0.0f64, 1.0).sample(rng);
+        let u: f64 = rng.r#gen();
         let u = u.clamp(1e-15, 1.0 - 1e-15);
         self.sigma * (-2.0 * (1.0 - u).ln()).sqrt()
     }
@@ -69,12 +76,58 @@ impl<R: Rng + ?Sized> Distribution<R> for Rayleigh {
         if *x <= 0.0 {
             return f64::NEG_INFINITY;
         }
-        x.ln() - 2.0 * self.sigma.ln() - x * x / (2.0 * self.sigma * self.sigma)
+        x.ln() - self.log_sigma_sq - x * x / (2.0 * self.sigma * self.sigma)
     }
 
     fn is_discrete(&self) -> bool {
         false
     }
+
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+impl Moments for Rayleigh {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = f64;
+
+    fn mean(&self) -> Option<f64> {
+        Some(self.sigma * (std::f64::consts::PI / 2.0).sqrt())
+    }
+
+    fn variance(&self) -> Option<f64> {
+        Some((4.0 - std::f64::consts::PI) / 2.0 * self.sigma * self.sigma)
+    }
+
+    fn mode(&self) -> Option<f64> {
+        Some(self.sigma)
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        Some(1.0 + (self.sigma / 2.0_f64.sqrt()).ln() + EULER_GAMMA / 2.0)
+    }
+}
+
+impl Univariate for Rayleigh {
+    fn cdf(&self, x: &f64) -> f64 {
+        -self.log_sf(x).exp_m1()
+    }
+
+    fn sf(&self, x: &f64) -> f64 {
+        self.log_sf(x).exp()
+    }
+
+    fn log_sf(&self, x: &f64) -> f64 {
+        let x = x.max(0.0);
+        -x * x / (2.0 * self.sigma * self.sigma)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        check_probability(p);
+        self.sigma * (-2.0 * (-p).ln_1p()).sqrt()
+    }
 }
 
 impl std::fmt::Display for Rayleigh {
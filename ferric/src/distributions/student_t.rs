@@ -3,7 +3,9 @@
 use rand_distr::Distribution as Distribution2;
 use rand_distr::StudentT as StudentT2;
 
-use crate::distributions::Distribution;
+use crate::distributions::univariate::{continuous_quantile, continuous_quantile_sf};
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
+use crate::special::{beta_inc, digamma, ln_beta, ln_gamma};
 use rand::Rng;
 
 /// Student's $t$-distribution centred at zero.
@@ -34,6 +36,9 @@ use rand::Rng;
 /// ```
 pub struct StudentT {
     df: f64,
+    sampler: StudentT2<f64>,
+    /// $\ln\Gamma(\tfrac{\nu+1}{2}) - \ln\Gamma(\tfrac{\nu}{2}) - \tfrac{1}{2}\ln(\nu\pi)$.
+    log_norm: f64,
 }
 
 impl StudentT {
@@ -42,15 +47,21 @@ impl StudentT {
     /// # Errors
     ///
     /// Returns `Err` if `df` is not strictly positive.
-    pub fn new(df: f64) -> Result<StudentT, String> {
-        if df <= 0.0 {
-            Err(format!(
-                "StudentT: illegal df `{}` should be greater than 0",
-                df
-            ))
-        } else {
-            Ok(StudentT { df })
-        }
+    pub fn new(df: f64) -> Result<StudentT, DistributionError> {
+        StudentT2::new(df)
+            .map(|sampler| StudentT {
+                df,
+                sampler,
+                log_norm: ln_gamma((df + 1.0) / 2.0)
+                    - ln_gamma(df / 2.0)
+                    - 0.5 * (df * std::f64::consts::PI).ln(),
+            })
+            .map_err(|_| DistributionError::InvalidParameter {
+                distribution: "StudentT",
+                name: "df",
+                value: df,
+                constraint: "greater than 0",
+            })
     }
 }
 
@@ -58,7 +69,7 @@ impl<R: Rng + ?Sized> Distribution<R> for StudentT {
     type Domain = f64;
 
     fn sample(&self, rng: &mut R) -> f64 {
-        StudentT2::new(self.df).unwrap().sample(rng)
+        self.sampler.sample(rng)
     }
 
     /// Returns
@@ -68,10 +79,7 @@ impl<R: Rng + ?Sized> Distribution<R> for StudentT {
     ///   - \tfrac{\nu+1}{2}\ln\!\left(1 + x^2/\nu\right)$.
     fn log_prob(&self, x: &f64) -> f64 {
         let nu = self.df;
-        libm::lgamma((nu + 1.0) / 2.0)
-            - libm::lgamma(nu / 2.0)
-            - 0.5 * (nu * std::f64::consts::PI).ln()
-            - (nu + 1.0) / 2.0 * (1.0 + x * x / nu).ln()
+        self.log_norm - (nu + 1.0) / 2.0 * (1.0 + x * x / nu).ln()
     }
 
     fn is_discrete(&self) -> bool {
@@ -79,6 +87,69 @@ impl<R: Rng + ?Sized> Distribution<R> for StudentT {
     }
 }
 
+impl Moments for StudentT {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = f64;
+
+    /// Defined for $\nu > 1$.
+    fn mean(&self) -> Option<f64> {
+        (self.df > 1.0).then_some(0.0)
+    }
+
+    /// Defined for $\nu > 2$.
+    fn variance(&self) -> Option<f64> {
+        (self.df > 2.0).then(|| self.df / (self.df - 2.0))
+    }
+
+    fn mode(&self) -> Option<f64> {
+        Some(0.0)
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        let nu = self.df;
+        let ln_beta = ln_beta(nu / 2.0, 0.5);
+        Some(
+            (nu + 1.0) / 2.0 * (digamma((nu + 1.0) / 2.0) - digamma(nu / 2.0))
+                + 0.5 * nu.ln()
+                + ln_beta,
+        )
+    }
+}
+
+impl StudentT {
+    /// The probability $P(T > |t|)$ of one tail beyond `t`.
+    fn tail(&self, t: f64) -> f64 {
+        0.5 * beta_inc(self.df / 2.0, 0.5, self.df / (self.df + t * t))
+    }
+}
+
+impl Univariate for StudentT {
+    fn cdf(&self, x: &f64) -> f64 {
+        if *x < 0.0 {
+            self.tail(*x)
+        } else {
+            1.0 - self.tail(*x)
+        }
+    }
+
+    fn sf(&self, x: &f64) -> f64 {
+        if *x > 0.0 {
+            self.tail(*x)
+        } else {
+            1.0 - self.tail(*x)
+        }
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        continuous_quantile(p, (f64::NEG_INFINITY, f64::INFINITY), |x| self.cdf(&x))
+    }
+
+    fn quantile_sf(&self, q: f64) -> f64 {
+        continuous_quantile_sf(q, (f64::NEG_INFINITY, f64::INFINITY), |x| self.sf(&x))
+    }
+}
+
 impl std::fmt::Display for StudentT {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "StudentT {{ df = {} }}", self.df)
@@ -5,7 +5,8 @@ use rand_distr::Distribution as Distribution2;
 use rand_distr::Triangular as Triangular2;
 use rand_distr::TriangularError;
 
-use crate::distributions::Distribution;
+use crate::distributions::univariate::check_probability;
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
 
 /// Triangular distribution over $[a, b]$.
 ///
@@ -36,6 +37,7 @@ pub struct Triangular {
     a: f64,
     b: f64,
     c: f64,
+    sampler: Triangular2<f64>,
 }
 
 impl Triangular {
@@ -46,10 +48,23 @@ impl Triangular {
     ///
     /// Returns `Err` if the parameters are invalid (requires $a < b$ and
     /// $a \le c \le b$).
-    pub fn new(a: f64, b: f64, c: f64) -> Result<Triangular, String> {
+    pub fn new(a: f64, b: f64, c: f64) -> Result<Triangular, DistributionError> {
         Triangular2::new(a, b, c)
-            .map(|_| Triangular { a, b, c })
-            .map_err(|e: TriangularError| format!("Triangular: {:?}", e))
+            .map(|sampler| Triangular { a, b, c, sampler })
+            .map_err(|e| match e {
+                TriangularError::RangeTooSmall => DistributionError::InvalidParameter {
+                    distribution: "Triangular",
+                    name: "b",
+                    value: b,
+                    constraint: "at least a",
+                },
+                TriangularError::ModeRange => DistributionError::InvalidParameter {
+                    distribution: "Triangular",
+                    name: "c",
+                    value: c,
+                    constraint: "between a and b",
+                },
+            })
     }
 }
 
@@ -57,9 +72,7 @@ impl<R: Rng + ?Sized> Distribution<R> for Triangular {
     type Domain = f64;
 
     fn sample(&self, rng: &mut R) -> f64 {
-        Triangular2::new(self.a, self.b, self.c)
-            .unwrap()
-            .sample(rng)
+        self.sampler.sample(rng)
     }
 
     /// Returns the log of the piecewise-linear PDF, or $-\infty$ outside
@@ -80,6 +93,58 @@ impl<R: Rng + ?Sized> Distribution<R> for Triangular {
     fn is_discrete(&self) -> bool {
         false
     }
+
+    fn support(&self) -> (f64, f64) {
+        (self.a, self.b)
+    }
+}
+
+impl Moments for Triangular {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = f64;
+
+    fn mean(&self) -> Option<f64> {
+        Some((self.a + self.b + self.c) / 3.0)
+    }
+
+    fn variance(&self) -> Option<f64> {
+        let (a, b, c) = (self.a, self.b, self.c);
+        Some((a * a + b * b + c * c - a * b - a * c - b * c) / 18.0)
+    }
+
+    fn mode(&self) -> Option<f64> {
+        Some(self.c)
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        Some(0.5 + ((self.b - self.a) / 2.0).ln())
+    }
+}
+
+impl Univariate for Triangular {
+    fn cdf(&self, x: &f64) -> f64 {
+        let (a, b, c) = (self.a, self.b, self.c);
+        if *x <= a {
+            0.0
+        } else if *x >= b {
+            1.0
+        } else if *x <= c {
+            (x - a).powi(2) / ((b - a) * (c - a))
+        } else {
+            1.0 - (b - x).powi(2) / ((b - a) * (b - c))
+        }
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        check_probability(p);
+        let (a, b, c) = (self.a, self.b, self.c);
+        if p < (c - a) / (b - a) {
+            a + (p * (b - a) * (c - a)).sqrt()
+        } else {
+            b - ((1.0 - p) * (b - a) * (b - c)).sqrt()
+        }
+    }
 }
 
 impl std::fmt::Display for Triangular {
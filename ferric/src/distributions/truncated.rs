@@ -0,0 +1,416 @@
+// Copyright 2022 The Ferric AI Project Developers
+
+use std::any::Any;
+
+use rand::Rng;
+use rand::distributions::Open01;
+
+use crate::distributions::univariate::check_probability;
+use crate::distributions::{Distribution, DistributionError, Univariate};
+
+/// A univariate distribution restricted to the closed interval
+/// $[\mathrm{low}, \mathrm{high}]$.
+///
+/// The density (or mass) of the base distribution $D$ is renormalized by the
+/// probability $Z = P_D(\mathrm{low} \le X \le \mathrm{high})$ of the
+/// interval:
+///
+/// $$p(x) = \frac{p_D(x)}{Z}, \qquad \mathrm{low} \le x \le \mathrm{high}$$
+///
+/// Both bounds are inclusive, which matters for discrete distributions.  Use
+/// infinite bounds to truncate a continuous distribution on one side only.
+///
+/// Sampling rejects draws from $D$ that fall outside the interval when at
+/// least half the mass of $D$ lies inside it, and inverts the CDF of $D$
+/// otherwise, so that narrow or distant intervals are still cheap to sample.
+/// Intervals so far into the upper tail that the CDF of $D$ rounds to one are
+/// scored through the survival function of $D$ and sampled through its
+/// inverse, [`Univariate::quantile_sf`].  A sample always lies inside both the
+/// interval and the support of $D$.
+///
+/// See [Truncated distribution](https://en.wikipedia.org/wiki/Truncated_distribution)
+/// on Wikipedia for further details.
+///
+/// # Examples
+///
+/// ```
+/// use ferric::distributions::{Distribution, Normal, Truncated};
+/// use rand::thread_rng;
+///
+/// // a measurement error model for a quantity that cannot be negative
+/// let dist = Truncated::new(Normal::new(0.5, 1.0).unwrap(), 0.0, f64::INFINITY).unwrap();
+/// let x: f64 = dist.sample(&mut thread_rng());
+/// assert!(x >= 0.0);
+/// ```
+///
+/// Inside `make_model!`, chain the constructors with `and_then`:
+///
+/// ```text
+/// let mass : f64 ~ Normal::new( 1.0, 2.0 )
+///     .and_then(|dist| Truncated::new(dist, 0.0, f64::INFINITY));
+/// ```
+pub struct Truncated<D: Univariate> {
+    dist: D,
+    low: D::Domain,
+    high: D::Domain,
+    /// $P_D(X < \mathrm{low})$.
+    below: f64,
+    /// $P_D(X > \mathrm{high})$.
+    above: f64,
+    /// $Z$, computed from whichever tail of $D$ keeps its precision.
+    mass: f64,
+}
+
+impl<D> Truncated<D>
+where
+    D: Univariate,
+    D::Domain: Clone + PartialOrd,
+{
+    /// Restrict `dist` to the interval `[low, high]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if `low` is greater than `high`, or if `dist` assigns no
+    /// probability to the interval.
+    pub fn new(
+        dist: D,
+        low: D::Domain,
+        high: D::Domain,
+    ) -> Result<Truncated<D>, DistributionError> {
+        // also rejects NaN bounds, which are incomparable
+        if low.partial_cmp(&high).is_none_or(|order| order.is_gt()) {
+            return Err(DistributionError::EmptySupport {
+                distribution: "Truncated",
+                reason: "low is greater than high",
+            });
+        }
+        // P(X = low) is only non-zero for discrete distributions
+        let at_low = if dist.is_discrete() {
+            dist.log_prob(&low).exp()
+        } else {
+            0.0
+        };
+        let below = (dist.cdf(&low) - at_low).max(0.0);
+        let above = dist.sf(&high);
+        // 1 - cdf loses the upper tail, so measure from whichever end is
+        // further from the interval
+        let mass = if below > 0.5 {
+            dist.sf(&low) + at_low - above
+        } else {
+            dist.cdf(&high) - below
+        };
+        if mass.is_nan() || mass <= 0.0 {
+            return Err(DistributionError::EmptySupport {
+                distribution: "Truncated",
+                reason: "no probability mass lies between low and high",
+            });
+        }
+        Ok(Truncated {
+            dist,
+            low,
+            high,
+            below,
+            above,
+            mass,
+        })
+    }
+
+    /// The base distribution.
+    pub fn inner(&self) -> &D {
+        &self.dist
+    }
+
+    /// The lower and upper bounds of the interval.
+    pub fn bounds(&self) -> (&D::Domain, &D::Domain) {
+        (&self.low, &self.high)
+    }
+
+    fn contains(&self, x: &D::Domain) -> bool {
+        self.low <= *x && *x <= self.high
+    }
+
+    fn clamp(&self, x: D::Domain) -> D::Domain {
+        if x < self.low {
+            self.low.clone()
+        } else if x > self.high {
+            self.high.clone()
+        } else {
+            x
+        }
+    }
+}
+
+/// The value of `x` if the domain is `f64`, used to narrow `support`.
+fn as_f64<T: 'static>(x: &T) -> Option<f64> {
+    (x as &dyn Any).downcast_ref::<f64>().copied()
+}
+
+impl<R, D> Distribution<R> for Truncated<D>
+where
+    R: Rng + ?Sized,
+    D: Univariate + Distribution<R, Domain = <D as Distribution>::Domain>,
+    <D as Distribution>::Domain: Clone + PartialOrd + 'static,
+{
+    type Domain = <D as Distribution>::Domain;
+
+    fn sample(&self, rng: &mut R) -> Self::Domain {
+        if self.mass >= 0.5 {
+            loop {
+                let x = Distribution::<R>::sample(&self.dist, rng);
+                if self.contains(&x) {
+                    return x;
+                }
+            }
+        }
+        let p = if Distribution::<R>::is_discrete(&self.dist) {
+            // 1 - [0, 1) lies in (0, 1], which maps to the inclusive interval
+            1.0 - rng.r#gen::<f64>()
+        } else {
+            // (0, 1) never maps to an infinite bound
+            rng.sample(Open01)
+        };
+        self.quantile(p)
+    }
+
+    /// Returns $\ln p_D(x) - \ln Z$ inside the interval and $-\infty$ outside.
+    fn log_prob(&self, x: &Self::Domain) -> f64 {
+        if !self.contains(x) {
+            return f64::NEG_INFINITY;
+        }
+        Distribution::<R>::log_prob(&self.dist, x) - self.mass.ln()
+    }
+
+    fn is_discrete(&self) -> bool {
+        Distribution::<R>::is_discrete(&self.dist)
+    }
+
+    fn support(&self) -> (f64, f64) {
+        let (lower, upper) = Distribution::<R>::support(&self.dist);
+        match (as_f64(&self.low), as_f64(&self.high)) {
+            (Some(low), Some(high)) => (lower.max(low), upper.min(high)),
+            _ => (lower, upper),
+        }
+    }
+
+    /// Returns the values of the base distribution's finite support that
+    /// lie inside the interval.
+    fn finite_support(&self) -> Option<Vec<Self::Domain>> {
+        let support = Distribution::<R>::finite_support(&self.dist)?;
+        Some(support.into_iter().filter(|x| self.contains(x)).collect())
+    }
+}
+
+impl<D> Univariate for Truncated<D>
+where
+    D: Univariate,
+    D::Domain: Clone + PartialOrd + 'static,
+{
+    fn cdf(&self, x: &D::Domain) -> f64 {
+        if *x < self.low {
+            0.0
+        } else if *x >= self.high {
+            1.0
+        } else if self.below > 0.5 {
+            1.0 - self.sf(x)
+        } else {
+            ((self.dist.cdf(x) - self.below) / self.mass).clamp(0.0, 1.0)
+        }
+    }
+
+    fn sf(&self, x: &D::Domain) -> f64 {
+        if *x < self.low {
+            1.0
+        } else if *x >= self.high {
+            0.0
+        } else {
+            ((self.dist.sf(x) - self.above) / self.mass).clamp(0.0, 1.0)
+        }
+    }
+
+    /// Inverts the CDF of $D$ when less of its mass lies below the interval
+    /// than above it, and the survival function of $D$ otherwise, so that
+    /// the target probability never rounds to one.
+    fn quantile(&self, p: f64) -> D::Domain {
+        check_probability(p);
+        let x = if self.above < self.below {
+            // F(x) >= below + p Z  <=>  S(x) <= above + (1 - p) Z
+            self.dist
+                .quantile_sf((self.above + (1.0 - p) * self.mass).min(1.0))
+        } else {
+            self.dist.quantile((self.below + p * self.mass).min(1.0))
+        };
+        self.clamp(x)
+    }
+}
+
+impl<D> std::fmt::Display for Truncated<D>
+where
+    D: Univariate + std::fmt::Display,
+    D::Domain: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Truncated {{ dist = {}, low = {:?}, high = {:?} }}",
+            self.dist, self.low, self.high
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributions::{Binomial, Gamma, HalfNormal, Normal, Poisson, Uniform};
+    use rand::rngs::ThreadRng;
+    use rand::thread_rng;
+
+    #[test]
+    fn one_sided_normal_is_half_normal() {
+        let dist = Truncated::new(Normal::new(0.0, 2.0).unwrap(), 0.0, f64::INFINITY).unwrap();
+        let half = HalfNormal::new(2.0).unwrap();
+        for &x in &[0.0, 0.5, 3.0, 10.0] {
+            let lp = <Truncated<Normal> as Distribution>::log_prob(&dist, &x);
+            let expected = <HalfNormal as Distribution>::log_prob(&half, &x);
+            assert!((lp - expected).abs() < 1e-12);
+            assert!((dist.cdf(&x) - half.cdf(&x)).abs() < 1e-12);
+        }
+        assert_eq!(
+            <Truncated<Normal> as Distribution>::log_prob(&dist, &-0.1),
+            f64::NEG_INFINITY
+        );
+        assert_eq!(
+            <Truncated<Normal> as Distribution>::support(&dist),
+            (0.0, f64::INFINITY)
+        );
+        assert!(format!("{}", dist).contains("Truncated"));
+    }
+
+    /// Compare the sample mean with the closed-form mean of a truncated
+    /// standard normal, $(\phi(a) - \phi(b)) / Z$.
+    fn check_normal_mean(low: f64, high: f64) {
+        let standard = Normal::new(0.0, 1.0).unwrap();
+        let dist = Truncated::new(Normal::new(0.0, 1.0).unwrap(), low, high).unwrap();
+        let density = |x: f64| <Normal as Distribution>::log_prob(&standard, &x).exp();
+        let z = standard.cdf(&high) - standard.cdf(&low);
+        let mean = (density(low) - density(high)) / z;
+
+        let mut rng = thread_rng();
+        let trials = 20_000;
+        let mut total = 0.0;
+        for _ in 0..trials {
+            let x = dist.sample(&mut rng);
+            assert!((low..=high).contains(&x));
+            total += x;
+        }
+        let empirical_mean = total / trials as f64;
+        let err = 5.0 * (high - low) / (trials as f64).sqrt();
+        assert!(
+            (empirical_mean - mean).abs() < err,
+            "[{}, {}]: mean {} != {}",
+            low,
+            high,
+            empirical_mean,
+            mean
+        );
+    }
+
+    #[test]
+    fn truncated_normal_samples() {
+        // by rejection
+        check_normal_mean(-1.0, 2.0);
+        // by inverting the CDF
+        check_normal_mean(2.0, 3.0);
+        check_normal_mean(-5.0, -4.0);
+    }
+
+    #[test]
+    fn far_tail_is_scored_through_the_survival_function() {
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let dist = Truncated::new(Normal::new(0.0, 1.0).unwrap(), 10.0, f64::INFINITY).unwrap();
+        let lp = <Truncated<Normal> as Distribution>::log_prob(&dist, &10.0);
+        let expected = <Normal as Distribution>::log_prob(&normal, &10.0) - normal.log_sf(&10.0);
+        assert!((lp - expected).abs() < 1e-9);
+        assert!((dist.sf(&11.0) - normal.sf(&11.0) / normal.sf(&10.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn far_tail_is_sampled_through_the_inverse_survival_function() {
+        let dist = Truncated::new(Normal::new(0.0, 1.0).unwrap(), 10.0, f64::INFINITY).unwrap();
+        assert!((dist.quantile(0.5) - 10.068_4).abs() < 1e-3);
+        // the mean of the tail beyond 10 is phi(10) / S(10) ≈ 10.0981, and its
+        // standard deviation about 0.097
+        let mut rng = thread_rng();
+        let trials = 10_000;
+        let mut total = 0.0;
+        for _ in 0..trials {
+            let x = dist.sample(&mut rng);
+            assert!(x.is_finite() && x >= 10.0, "sampled {}", x);
+            total += x;
+        }
+        let empirical_mean = total / trials as f64;
+        assert!(
+            (empirical_mean - 10.0981).abs() < 0.005,
+            "mean {}",
+            empirical_mean
+        );
+
+        // and the same for a distribution without a closed-form inverse
+        let dist = Truncated::new(Gamma::new(2.0, 1.0).unwrap(), 60.0, f64::INFINITY).unwrap();
+        for _ in 0..1000 {
+            let x = dist.sample(&mut rng);
+            assert!(x.is_finite() && x >= 60.0, "sampled {}", x);
+        }
+    }
+
+    #[test]
+    fn truncated_poisson_is_renormalized() {
+        let dist = Truncated::new(Poisson::new(4.0).unwrap(), 2, 5).unwrap();
+        let total: f64 = (0..20)
+            .map(|k| <Truncated<Poisson> as Distribution>::log_prob(&dist, &k).exp())
+            .sum();
+        assert!((total - 1.0).abs() < 1e-12);
+        assert_eq!(dist.cdf(&1), 0.0);
+        assert_eq!(dist.cdf(&5), 1.0);
+        assert_eq!(dist.quantile(0.0), 2);
+        assert_eq!(dist.quantile(1.0), 5);
+
+        let mut rng = thread_rng();
+        for _ in 0..1000 {
+            assert!((2..=5).contains(&dist.sample(&mut rng)));
+        }
+        // only a fifth of the mass of Poisson(20) lies in [0, 15]
+        let dist = Truncated::new(Poisson::new(20.0).unwrap(), 0, 15).unwrap();
+        for _ in 0..1000 {
+            assert!(dist.sample(&mut rng) <= 15);
+        }
+    }
+
+    #[test]
+    fn finite_support_is_filtered() {
+        let dist = Truncated::new(Binomial::new(10, 0.5).unwrap(), 3, 7).unwrap();
+        assert_eq!(
+            <Truncated<Binomial> as Distribution<ThreadRng>>::finite_support(&dist),
+            Some(vec![3, 4, 5, 6, 7])
+        );
+        let dist = Truncated::new(Poisson::new(1.0).unwrap(), 3, 7).unwrap();
+        assert_eq!(
+            <Truncated<Poisson> as Distribution<ThreadRng>>::finite_support(&dist),
+            None
+        );
+    }
+
+    #[test]
+    fn truncated_invalid() {
+        let normal = || Normal::new(0.0, 1.0).unwrap();
+        assert!(matches!(
+            Truncated::new(normal(), 1.0, -1.0),
+            Err(DistributionError::EmptySupport { .. })
+        ));
+        assert!(Truncated::new(normal(), f64::NAN, 1.0).is_err());
+        assert!(Truncated::new(normal(), 1.0, 1.0).is_err());
+        assert!(Truncated::new(Uniform::new(0.0, 1.0).unwrap(), 2.0, 3.0).is_err());
+        // a single value of a discrete distribution is a legal interval
+        let dist = Truncated::new(Poisson::new(1.0).unwrap(), 3, 3).unwrap();
+        assert_eq!(dist.sample(&mut thread_rng()), 3);
+    }
+}
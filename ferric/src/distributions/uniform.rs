@@ -1,7 +1,10 @@
 // Copyright 2022 The Ferric AI Project Developers
 
-use crate::distributions::Distribution;
+use crate::distributions::univariate::check_probability;
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
 use rand::Rng;
+use rand::distributions::Distribution as Distribution2;
+use rand::distributions::Uniform as Uniform2;
 
 /// Continuous uniform distribution over the closed interval $[a, b]$.
 ///
@@ -27,6 +30,9 @@ use rand::Rng;
 pub struct Uniform {
     low: f64,
     high: f64,
+    sampler: Uniform2<f64>,
+    /// $-\ln(b - a)$.
+    log_p: f64,
 }
 
 impl Uniform {
@@ -34,15 +40,36 @@ impl Uniform {
     ///
     /// # Errors
     ///
-    /// Returns `Err` if `high <= low`.
-    pub fn new(low: f64, high: f64) -> Result<Uniform, String> {
-        if high <= low {
-            Err(format!(
-                "Uniform: illegal interval [{}, {}]; high must be strictly greater than low",
-                low, high
-            ))
+    /// Returns `Err` if `low` or `high` is not finite, or if `high <= low`.
+    pub fn new(low: f64, high: f64) -> Result<Uniform, DistributionError> {
+        if !low.is_finite() {
+            Err(DistributionError::InvalidParameter {
+                distribution: "Uniform",
+                name: "low",
+                value: low,
+                constraint: "finite",
+            })
+        } else if !high.is_finite() {
+            Err(DistributionError::InvalidParameter {
+                distribution: "Uniform",
+                name: "high",
+                value: high,
+                constraint: "finite",
+            })
+        } else if high <= low {
+            Err(DistributionError::InvalidParameter {
+                distribution: "Uniform",
+                name: "high",
+                value: high,
+                constraint: "greater than low",
+            })
         } else {
-            Ok(Uniform { low, high })
+            Ok(Uniform {
+                low,
+                high,
+                sampler: Uniform2::new(low, high),
+                log_p: -(high - low).ln(),
+            })
         }
     }
 }
@@ -51,13 +78,13 @@ impl<R: Rng + ?Sized> Distribution<R> for Uniform {
     type Domain = f64;
 
     fn sample(&self, rng: &mut R) -> f64 {
-        rng.gen_range(self.low..self.high)
+        self.sampler.sample(rng)
     }
 
     /// Returns $-\ln(b - a)$ for $x \in [a, b]$, or $-\infty$ otherwise.
     fn log_prob(&self, x: &f64) -> f64 {
         if *x >= self.low && *x <= self.high {
-            -(self.high - self.low).ln()
+            self.log_p
         } else {
             f64::NEG_INFINITY
         }
@@ -66,6 +93,43 @@ impl<R: Rng + ?Sized> Distribution<R> for Uniform {
     fn is_discrete(&self) -> bool {
         false
     }
+
+    fn support(&self) -> (f64, f64) {
+        (self.low, self.high)
+    }
+}
+
+impl Moments for Uniform {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = f64;
+
+    fn mean(&self) -> Option<f64> {
+        Some((self.low + self.high) / 2.0)
+    }
+
+    fn variance(&self) -> Option<f64> {
+        Some((self.high - self.low).powi(2) / 12.0)
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        Some((self.high - self.low).ln())
+    }
+}
+
+impl Univariate for Uniform {
+    fn cdf(&self, x: &f64) -> f64 {
+        ((x - self.low) / (self.high - self.low)).clamp(0.0, 1.0)
+    }
+
+    fn sf(&self, x: &f64) -> f64 {
+        ((self.high - x) / (self.high - self.low)).clamp(0.0, 1.0)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        check_probability(p);
+        self.low + p * (self.high - self.low)
+    }
 }
 
 impl std::fmt::Display for Uniform {
@@ -0,0 +1,342 @@
+// Copyright 2022 The Ferric AI Project Developers
+
+use crate::distributions::Distribution;
+
+/// Cumulative distribution, survival function and quantiles of a
+/// distribution over an ordered scalar domain.
+///
+/// For a discrete distribution `cdf(x)` is $P(X \le x)$ and `sf(x)` is
+/// $P(X > x)$, so that the two always sum to one.  `quantile(p)` is the
+/// smallest `x` with `cdf(x) >= p`, which makes it the inverse of `cdf` for
+/// continuous distributions and lets [`Univariate::quantile`] turn a
+/// uniform draw into a draw from the distribution.
+///
+/// # Examples
+///
+/// ```
+/// use ferric::distributions::{Normal, Univariate};
+///
+/// let dist = Normal::new(0.0, 1.0).unwrap();
+/// assert!((dist.cdf(&0.0) - 0.5).abs() < 1e-15);
+///
+/// // a 95% central credible interval
+/// let (lo, hi) = (dist.quantile(0.025), dist.quantile(0.975));
+/// assert!((hi - 1.959_964).abs() < 1e-6);
+/// assert!((lo + hi).abs() < 1e-12);
+/// ```
+pub trait Univariate: Distribution {
+    /// The cumulative distribution function $F(x) = P(X \le x)$.
+    fn cdf(&self, x: &Self::Domain) -> f64;
+
+    /// The natural logarithm of [`cdf`](Univariate::cdf).  Distributions
+    /// override the default where $F(x)$ would underflow.
+    fn log_cdf(&self, x: &Self::Domain) -> f64 {
+        self.cdf(x).ln()
+    }
+
+    /// The survival function $S(x) = P(X > x) = 1 - F(x)$.
+    fn sf(&self, x: &Self::Domain) -> f64 {
+        1.0 - self.cdf(x)
+    }
+
+    /// The natural logarithm of [`sf`](Univariate::sf).
+    fn log_sf(&self, x: &Self::Domain) -> f64 {
+        self.sf(x).ln()
+    }
+
+    /// The quantile function $F^{-1}(p) = \min\{x : F(x) \ge p\}$.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not in $[0, 1]$.
+    fn quantile(&self, p: f64) -> Self::Domain;
+
+    /// The inverse survival function, the quantile with upper tail
+    /// probability `q`.  It equals `quantile(1 - q)`, which is the default;
+    /// distributions override it where $1 - q$ would round away a small `q`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is not in $[0, 1]$.
+    fn quantile_sf(&self, q: f64) -> Self::Domain {
+        check_probability(q);
+        self.quantile(1.0 - q)
+    }
+}
+
+/// Panic unless `p` is a probability, as documented on
+/// [`Univariate::quantile`].
+pub(crate) fn check_probability(p: f64) {
+    assert!(
+        (0.0..=1.0).contains(&p),
+        "quantile: probability `{}` should be between 0 and 1",
+        p
+    );
+}
+
+/// Invert a continuous, non-decreasing `cdf` on the support `(lower, upper)`
+/// by bisection.
+pub(crate) fn continuous_quantile(
+    p: f64,
+    (lower, upper): (f64, f64),
+    cdf: impl Fn(f64) -> f64,
+) -> f64 {
+    check_probability(p);
+    if p == 0.0 {
+        return lower;
+    }
+    if p == 1.0 {
+        return upper;
+    }
+    bisect((lower, upper), |x| cdf(x) - p)
+}
+
+/// Invert a continuous, non-increasing survival function `sf` on the support
+/// `(lower, upper)` by bisection, without losing a small `q` to `1 - q`.
+pub(crate) fn continuous_quantile_sf(
+    q: f64,
+    (lower, upper): (f64, f64),
+    sf: impl Fn(f64) -> f64,
+) -> f64 {
+    check_probability(q);
+    if q == 0.0 {
+        return upper;
+    }
+    if q == 1.0 {
+        return lower;
+    }
+    bisect((lower, upper), |x| q - sf(x))
+}
+
+/// The root of a continuous, non-decreasing `excess` on `(lower, upper)`,
+/// which is negative left of the root and positive right of it.
+fn bisect((lower, upper): (f64, f64), excess: impl Fn(f64) -> f64) -> f64 {
+    // Bracket the root, growing outwards from the finite bounds.
+    let mut lo = if lower.is_finite() { lower } else { -1.0 };
+    let mut hi = if upper.is_finite() {
+        upper
+    } else {
+        lo.max(0.0) + 1.0
+    };
+    let mut step = 1.0;
+    while lower == f64::NEG_INFINITY && excess(lo) > 0.0 {
+        hi = lo;
+        lo -= step;
+        step *= 2.0;
+    }
+    step = (hi - lo).max(1.0);
+    while upper == f64::INFINITY && excess(hi) < 0.0 {
+        lo = hi;
+        hi += step;
+        step *= 2.0;
+    }
+    for _ in 0..200 {
+        let mid = 0.5 * (lo + hi);
+        if mid <= lo || mid >= hi {
+            break;
+        }
+        if excess(mid) < 0.0 {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    0.5 * (lo + hi)
+}
+
+/// The smallest `k` in the support `lower..=upper` with `cdf(k) >= p` for a
+/// discrete `cdf` over the integers, found by doubling and then bisection.
+/// Infinite supports pass `u64::MAX` as `upper`, which is also the quantile
+/// of `p = 1`.
+pub(crate) fn discrete_quantile(
+    p: f64,
+    (lower, upper): (u64, u64),
+    cdf: impl Fn(u64) -> f64,
+) -> u64 {
+    check_probability(p);
+    if p == 1.0 {
+        return upper;
+    }
+    first_reaching((lower, upper), |k| cdf(k) >= p)
+}
+
+/// The smallest `k` in the support `lower..=upper` with `sf(k) <= q` for a
+/// discrete survival function `sf`, the counterpart of [`discrete_quantile`]
+/// that keeps a small `q` precise.
+pub(crate) fn discrete_quantile_sf(
+    q: f64,
+    (lower, upper): (u64, u64),
+    sf: impl Fn(u64) -> f64,
+) -> u64 {
+    check_probability(q);
+    if q == 0.0 {
+        return upper;
+    }
+    first_reaching((lower, upper), |k| sf(k) <= q)
+}
+
+/// The smallest `k` in `lower..=upper` for which the monotone `reached`
+/// holds, or `upper` if it holds nowhere before it.
+fn first_reaching((lower, upper): (u64, u64), reached: impl Fn(u64) -> bool) -> u64 {
+    if reached(lower) {
+        return lower;
+    }
+    // Invariant: !reached(lo) && reached(hi).
+    let mut lo = lower;
+    let mut step = 1u64;
+    let mut hi = loop {
+        let candidate = lo.saturating_add(step).min(upper);
+        if candidate == upper || reached(candidate) {
+            break candidate;
+        }
+        lo = candidate;
+        step = step.saturating_mul(2);
+    };
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if reached(mid) {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    hi
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distributions::*;
+
+    /// Check that `quantile` inverts `cdf`, that `quantile_sf` inverts `sf`,
+    /// that `sf` complements `cdf`, and that the logarithms agree, at a few
+    /// probabilities.
+    fn check_continuous<D: Univariate<Domain = f64>>(dist: D) {
+        for &p in &[1e-6, 0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99] {
+            let x = dist.quantile(p);
+            assert!(
+                (dist.cdf(&x) - p).abs() < 1e-9,
+                "{}: cdf(quantile({})) = {}",
+                std::any::type_name::<D>(),
+                p,
+                dist.cdf(&x)
+            );
+            assert!((dist.sf(&dist.quantile_sf(p)) - p).abs() < 1e-9);
+            assert!((dist.cdf(&x) + dist.sf(&x) - 1.0).abs() < 1e-12);
+            assert!((dist.log_cdf(&x) - dist.cdf(&x).ln()).abs() < 1e-9);
+            assert!((dist.log_sf(&x) - dist.sf(&x).ln()).abs() < 1e-9);
+        }
+    }
+
+    /// Check that the discrete `cdf` is the running sum of `log_prob`, and
+    /// that `quantile` and `quantile_sf` are the generalized inverses of `cdf`
+    /// and `sf`.
+    fn check_discrete<D: Univariate<Domain = u64>>(dist: D, max: u64) {
+        let mut total = 0.0;
+        for k in 0..=max {
+            let prob = dist.log_prob(&k).exp();
+            total += prob;
+            assert!(
+                (dist.cdf(&k) - total).abs() < 1e-9,
+                "{}: cdf({}) = {} != {}",
+                std::any::type_name::<D>(),
+                k,
+                dist.cdf(&k),
+                total
+            );
+            assert!((dist.cdf(&k) + dist.sf(&k) - 1.0).abs() < 1e-12);
+            if prob > 1e-6 {
+                assert_eq!(dist.quantile(total - 1e-9), k);
+                assert_eq!(dist.quantile_sf(dist.sf(&k) + 1e-9), k);
+            }
+            if dist.log_prob(&(k + 1)).exp() > 1e-6 {
+                assert_eq!(dist.quantile(total + 1e-9), k + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn continuous_quantiles_invert_cdf() {
+        check_continuous(Beta::new(2.0, 5.0).unwrap());
+        check_continuous(Beta::new(0.5, 0.5).unwrap());
+        check_continuous(Cauchy::new(1.0, 2.0).unwrap());
+        check_continuous(Chi::new(3.0).unwrap());
+        check_continuous(ChiSquared::new(4.0).unwrap());
+        check_continuous(Erlang::new(3, 2.0).unwrap());
+        check_continuous(Exponential::new(1.5).unwrap());
+        check_continuous(FisherF::new(5.0, 20.0).unwrap());
+        check_continuous(Frechet::new(1.0, 2.0, 3.0).unwrap());
+        check_continuous(Gamma::new(0.5, 2.0).unwrap());
+        check_continuous(Gumbel::new(1.0, 2.0).unwrap());
+        check_continuous(HalfNormal::new(2.0).unwrap());
+        check_continuous(InverseGamma::new(3.0, 2.0).unwrap());
+        check_continuous(InverseGaussian::new(1.0, 3.0).unwrap());
+        check_continuous(Laplace::new(1.0, 0.5).unwrap());
+        check_continuous(LogNormal::new(0.0, 0.5).unwrap());
+        check_continuous(Logistic::new(1.0, 2.0).unwrap());
+        check_continuous(Normal::new(-1.0, 3.0).unwrap());
+        check_continuous(Pareto::new(1.0, 3.0).unwrap());
+        check_continuous(Rayleigh::new(2.0).unwrap());
+        check_continuous(StudentT::new(3.0).unwrap());
+        check_continuous(Triangular::new(0.0, 4.0, 1.0).unwrap());
+        check_continuous(Uniform::new(-2.0, 3.0).unwrap());
+        check_continuous(Weibull::new(2.0, 1.5).unwrap());
+    }
+
+    #[test]
+    fn discrete_cdf_sums_log_prob() {
+        check_discrete(BetaBinomial::new(10, 2.0, 3.0).unwrap(), 10);
+        check_discrete(Binomial::new(20, 0.3).unwrap(), 20);
+        check_discrete(Geometric::new(0.3).unwrap(), 30);
+        check_discrete(Hypergeometric::new(20, 7, 5).unwrap(), 5);
+        check_discrete(NegativeBinomial::new(2.5, 0.4).unwrap(), 30);
+        check_discrete(Poisson::new(4.5).unwrap(), 20);
+    }
+
+    #[test]
+    fn other_discrete_domains() {
+        let coin = Bernoulli::new(0.3).unwrap();
+        assert!((coin.cdf(&false) - 0.7).abs() < 1e-15);
+        assert_eq!(coin.cdf(&true), 1.0);
+        assert!(!coin.quantile(0.7));
+        assert!(coin.quantile(0.71));
+
+        let die = DiscreteUniform::new(1, 6).unwrap();
+        assert!((die.cdf(&3) - 0.5).abs() < 1e-15);
+        assert_eq!(die.cdf(&0), 0.0);
+        assert_eq!(die.quantile(0.5), 3);
+        assert_eq!(die.quantile(0.51), 4);
+        assert_eq!(die.quantile(0.0), 1);
+
+        let cat = Categorical::new(vec![0.2, 0.5, 0.3]).unwrap();
+        assert!((cat.cdf(&1) - 0.7).abs() < 1e-15);
+        assert_eq!(cat.cdf(&5), 1.0);
+        assert_eq!(cat.quantile(0.1), 0);
+        assert_eq!(cat.quantile(0.6), 1);
+        assert_eq!(cat.quantile(1.0), 2);
+    }
+
+    #[test]
+    fn tails() {
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        assert!((normal.log_cdf(&-40.0) + 804.608_442_013_753_8).abs() < 1e-9);
+        assert!((normal.log_sf(&40.0) + 804.608_442_013_753_8).abs() < 1e-9);
+        assert!((normal.sf(&10.0) - 7.619_853_024_160_527e-24).abs() < 1e-36);
+        assert!((normal.quantile_sf(7.619_853_024_160_527e-24) - 10.0).abs() < 1e-9);
+        let half_normal = HalfNormal::new(1.0).unwrap();
+        assert!((half_normal.quantile_sf(2.0 * 7.619_853_024_160_527e-24) - 10.0).abs() < 1e-9);
+        let log_normal = LogNormal::new(0.0, 1.0).unwrap();
+        assert!((log_normal.quantile_sf(7.619_853_024_160_527e-24).ln() - 10.0).abs() < 1e-9);
+
+        let exponential = Exponential::new(2.0).unwrap();
+        assert_eq!(exponential.log_sf(&500.0), -1000.0);
+        assert_eq!(exponential.quantile(1.0), f64::INFINITY);
+        assert!((exponential.quantile_sf((-700.0_f64).exp()) - 350.0).abs() < 1e-9);
+        assert_eq!(Poisson::new(3.0).unwrap().quantile(1.0), u64::MAX);
+    }
+
+    #[test]
+    #[should_panic]
+    fn quantile_rejects_non_probability() {
+        Normal::new(0.0, 1.0).unwrap().quantile(1.5);
+    }
+}
@@ -3,8 +3,11 @@
 use rand::Rng;
 use rand_distr::Distribution as Distribution2;
 use rand_distr::Weibull as Weibull2;
+use rand_distr::WeibullError;
 
-use crate::distributions::Distribution;
+use crate::distributions::univariate::check_probability;
+use crate::distributions::{Distribution, DistributionError, Moments, Univariate};
+use crate::special::{EULER_GAMMA, gamma};
 
 /// Weibull distribution over the positive reals.
 ///
@@ -33,6 +36,9 @@ use crate::distributions::Distribution;
 pub struct Weibull {
     lambda: f64,
     k: f64,
+    sampler: Weibull2<f64>,
+    /// $\ln k - k\ln\lambda$.
+    log_norm: f64,
 }
 
 impl Weibull {
@@ -42,20 +48,28 @@ impl Weibull {
     /// # Errors
     ///
     /// Returns `Err` if `lambda` or `k` is not strictly positive.
-    pub fn new(lambda: f64, k: f64) -> Result<Weibull, String> {
-        if lambda <= 0.0 {
-            Err(format!(
-                "Weibull: illegal scale `{}` should be greater than 0",
-                lambda
-            ))
-        } else if k <= 0.0 {
-            Err(format!(
-                "Weibull: illegal shape `{}` should be greater than 0",
-                k
-            ))
-        } else {
-            Ok(Weibull { lambda, k })
-        }
+    pub fn new(lambda: f64, k: f64) -> Result<Weibull, DistributionError> {
+        Weibull2::new(lambda, k)
+            .map(|sampler| Weibull {
+                lambda,
+                k,
+                sampler,
+                log_norm: k.ln() - k * lambda.ln(),
+            })
+            .map_err(|e| match e {
+                WeibullError::ScaleTooSmall => DistributionError::InvalidParameter {
+                    distribution: "Weibull",
+                    name: "scale",
+                    value: lambda,
+                    constraint: "greater than 0",
+                },
+                WeibullError::ShapeTooSmall => DistributionError::InvalidParameter {
+                    distribution: "Weibull",
+                    name: "shape",
+                    value: k,
+                    constraint: "greater than 0",
+                },
+            })
     }
 }
 
@@ -63,7 +77,7 @@ impl<R: Rng + ?Sized> Distribution<R> for Weibull {
     type Domain = f64;
 
     fn sample(&self, rng: &mut R) -> f64 {
-        Weibull2::new(self.lambda, self.k).unwrap().sample(rng)
+        self.sampler.sample(rng)
     }
 
     /// Returns $\ln k - \ln\lambda + (k-1)\ln x - (x/\lambda)^k$, or
@@ -73,12 +87,63 @@ impl<R: Rng + ?Sized> Distribution<R> for Weibull {
             return f64::NEG_INFINITY;
         }
         let z = x / self.lambda;
-        self.k.ln() - self.k * self.lambda.ln() + (self.k - 1.0) * x.ln() - z.powf(self.k)
+        self.log_norm + (self.k - 1.0) * x.ln() - z.powf(self.k)
     }
 
     fn is_discrete(&self) -> bool {
         false
     }
+
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+impl Moments for Weibull {
+    type Mean = f64;
+    type Variance = f64;
+    type Mode = f64;
+
+    fn mean(&self) -> Option<f64> {
+        Some(self.lambda * gamma(1.0 + 1.0 / self.k))
+    }
+
+    fn variance(&self) -> Option<f64> {
+        let g1 = gamma(1.0 + 1.0 / self.k);
+        let g2 = gamma(1.0 + 2.0 / self.k);
+        Some(self.lambda * self.lambda * (g2 - g1 * g1))
+    }
+
+    fn mode(&self) -> Option<f64> {
+        if self.k <= 1.0 {
+            Some(0.0)
+        } else {
+            Some(self.lambda * ((self.k - 1.0) / self.k).powf(1.0 / self.k))
+        }
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        Some(EULER_GAMMA * (1.0 - 1.0 / self.k) + (self.lambda / self.k).ln() + 1.0)
+    }
+}
+
+impl Univariate for Weibull {
+    fn cdf(&self, x: &f64) -> f64 {
+        -self.log_sf(x).exp_m1()
+    }
+
+    fn sf(&self, x: &f64) -> f64 {
+        self.log_sf(x).exp()
+    }
+
+    fn log_sf(&self, x: &f64) -> f64 {
+        -(x.max(0.0) / self.lambda).powf(self.k)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        check_probability(p);
+        self.lambda * (-(-p).ln_1p()).powf(1.0 / self.k)
+    }
 }
 
 impl std::fmt::Display for Weibull {
@@ -90,6 +155,7 @@ impl std::fmt::Display for Weibull {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::special::ln_gamma;
     use rand::rngs::ThreadRng;
     use rand::thread_rng;
 
@@ -121,8 +187,7 @@ mod tests {
         if (x - 1.0).abs() < 1e-12 {
             return 1.0;
         }
-        // Use lgamma from libm via std
-        (libm::lgamma(x)).exp()
+        ln_gamma(x).exp()
     }
 
     #[test]
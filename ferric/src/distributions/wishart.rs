@@ -4,15 +4,22 @@ use nalgebra::{Cholesky, DMatrix, Dyn};
 use rand::Rng;
 use rand_distr::ChiSquared as ChiSquared2;
 use rand_distr::Distribution as Distribution2;
-use rand_distr::Normal as Normal2;
+use rand_distr::StandardNormal;
 
-use crate::distributions::Distribution;
+use crate::distributions::{Distribution, DistributionError, Moments};
+use crate::special::{digamma, ln_gamma};
 
 /// Wishart distribution over symmetric positive-definite matrices.
 pub struct Wishart {
     df: f64,
     scale: DMatrix<f64>,
     chol: Cholesky<f64, Dyn>,
+    /// The lower Cholesky factor $L$ of the scale matrix.
+    l: DMatrix<f64>,
+    /// The squares of the Bartlett diagonal, $c_i^2 \sim \chi^2_{n - i}$.
+    bartlett: Vec<ChiSquared2<f64>>,
+    /// $\frac{np}{2}\ln 2 + \frac{n}{2}\ln|V| + \ln\Gamma_p(n/2)$.
+    log_norm: f64,
 }
 
 impl Wishart {
@@ -23,21 +30,45 @@ impl Wishart {
     ///
     /// Returns `Err` if `scale` is not square, `df <= p - 1`, or `scale` is
     /// not symmetric positive-definite.
-    pub fn new(df: f64, scale: DMatrix<f64>) -> Result<Wishart, String> {
+    pub fn new(df: f64, scale: DMatrix<f64>) -> Result<Wishart, DistributionError> {
         if scale.nrows() != scale.ncols() {
-            return Err("Wishart: scale matrix must be square".into());
+            return Err(DistributionError::DimensionMismatch {
+                distribution: "Wishart",
+                name: "scale",
+                expected: "square".to_string(),
+                found: format!("{}×{}", scale.nrows(), scale.ncols()),
+            });
         }
         let p = scale.nrows();
-        if df <= p as f64 - 1.0 {
-            return Err(format!(
-                "Wishart: df `{}` must be greater than p - 1 ({})",
-                df,
-                p - 1
-            ));
+        if df.is_nan() || df <= p as f64 - 1.0 {
+            return Err(DistributionError::InvalidParameter {
+                distribution: "Wishart",
+                name: "df",
+                value: df,
+                constraint: "greater than the dimension of scale minus 1",
+            });
         }
         match Cholesky::new(scale.clone()) {
-            Some(chol) => Ok(Wishart { df, scale, chol }),
-            None => Err("Wishart: scale matrix is not positive definite".to_string()),
+            Some(chol) => {
+                let bartlett = (0..p)
+                    .map(|i| ChiSquared2::new(df - i as f64).expect("df validated above"))
+                    .collect();
+                let log_norm = (df * p as f64 / 2.0) * 2.0_f64.ln()
+                    + (df / 2.0) * log_det_from_chol(&chol)
+                    + multivariate_lgamma(p, df / 2.0);
+                Ok(Wishart {
+                    df,
+                    scale,
+                    l: chol.l(),
+                    chol,
+                    bartlett,
+                    log_norm,
+                })
+            }
+            None => Err(DistributionError::NotPositiveDefinite {
+                distribution: "Wishart",
+                name: "scale",
+            }),
         }
     }
 }
@@ -47,18 +78,14 @@ impl<R: Rng + ?Sized> Distribution<R> for Wishart {
 
     fn sample(&self, rng: &mut R) -> DMatrix<f64> {
         let p = self.scale.nrows();
-        let normal = Normal2::new(0.0, 1.0).unwrap();
         let mut a = DMatrix::zeros(p, p);
-        for i in 0..p {
-            a[(i, i)] = ChiSquared2::new(self.df - i as f64)
-                .unwrap()
-                .sample(rng)
-                .sqrt();
+        for (i, chi_squared) in self.bartlett.iter().enumerate() {
+            a[(i, i)] = chi_squared.sample(rng).sqrt();
             for j in 0..i {
-                a[(i, j)] = normal.sample(rng);
+                a[(i, j)] = rng.sample(StandardNormal);
             }
         }
-        let la = self.chol.l() * a;
+        let la = &self.l * a;
         &la * la.transpose()
     }
 
@@ -71,13 +98,8 @@ impl<R: Rng + ?Sized> Distribution<R> for Wishart {
             return f64::NEG_INFINITY;
         };
         let log_det_x = log_det_from_chol(&x_chol);
-        let log_det_scale = log_det_from_chol(&self.chol);
         let trace_term = self.chol.solve(x).trace();
-        ((self.df - p as f64 - 1.0) / 2.0) * log_det_x
-            - 0.5 * trace_term
-            - (self.df * p as f64 / 2.0) * 2.0_f64.ln()
-            - (self.df / 2.0) * log_det_scale
-            - multivariate_lgamma(p, self.df / 2.0)
+        ((self.df - p as f64 - 1.0) / 2.0) * log_det_x - 0.5 * trace_term - self.log_norm
     }
 
     fn is_discrete(&self) -> bool {
@@ -93,10 +115,49 @@ fn log_det_from_chol(chol: &Cholesky<f64, Dyn>) -> f64 {
 fn multivariate_lgamma(p: usize, a: f64) -> f64 {
     (p as f64 * (p as f64 - 1.0) / 4.0) * std::f64::consts::PI.ln()
         + (1..=p)
-            .map(|j| libm::lgamma(a + (1.0 - j as f64) / 2.0))
+            .map(|j| ln_gamma(a + (1.0 - j as f64) / 2.0))
             .sum::<f64>()
 }
 
+impl Moments for Wishart {
+    type Mean = DMatrix<f64>;
+    type Variance = DMatrix<f64>;
+    type Mode = DMatrix<f64>;
+
+    fn mean(&self) -> Option<DMatrix<f64>> {
+        Some(&self.scale * self.df)
+    }
+
+    /// Returns the elementwise variances $n(V_{ij}^2 + V_{ii}V_{jj})$.
+    fn variance(&self) -> Option<DMatrix<f64>> {
+        let v = &self.scale;
+        Some(DMatrix::from_fn(v.nrows(), v.ncols(), |i, j| {
+            self.df * (v[(i, j)] * v[(i, j)] + v[(i, i)] * v[(j, j)])
+        }))
+    }
+
+    /// Defined for $n \ge p + 1$.
+    fn mode(&self) -> Option<DMatrix<f64>> {
+        let p = self.scale.nrows() as f64;
+        (self.df >= p + 1.0).then(|| &self.scale * (self.df - p - 1.0))
+    }
+
+    fn entropy(&self) -> Option<f64> {
+        let p = self.scale.nrows();
+        let pf = p as f64;
+        let multivariate_digamma: f64 = (1..=p)
+            .map(|j| digamma(self.df / 2.0 + (1.0 - j as f64) / 2.0))
+            .sum();
+        Some(
+            (pf + 1.0) / 2.0 * log_det_from_chol(&self.chol)
+                + pf * (pf + 1.0) / 2.0 * 2.0_f64.ln()
+                + multivariate_lgamma(p, self.df / 2.0)
+                - (self.df - pf - 1.0) / 2.0 * multivariate_digamma
+                + self.df * pf / 2.0,
+        )
+    }
+}
+
 impl std::fmt::Display for Wishart {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -152,8 +213,20 @@ mod tests {
 
     #[test]
     fn wishart_invalid() {
-        assert!(Wishart::new(1.0, DMatrix::zeros(2, 3)).is_err());
-        assert!(Wishart::new(1.0, DMatrix::identity(2, 2)).is_err());
-        assert!(Wishart::new(3.0, DMatrix::from_vec(2, 2, vec![1.0, 2.0, 2.0, 1.0])).is_err());
+        assert!(matches!(
+            Wishart::new(1.0, DMatrix::zeros(2, 3)),
+            Err(DistributionError::DimensionMismatch { .. })
+        ));
+        assert!(matches!(
+            Wishart::new(1.0, DMatrix::identity(2, 2)),
+            Err(DistributionError::InvalidParameter { name: "df", .. })
+        ));
+        assert_eq!(
+            Wishart::new(3.0, DMatrix::from_vec(2, 2, vec![1.0, 2.0, 2.0, 1.0])).err(),
+            Some(DistributionError::NotPositiveDefinite {
+                distribution: "Wishart",
+                name: "scale",
+            })
+        );
     }
 }
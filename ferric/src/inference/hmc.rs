@@ -0,0 +1,158 @@
+// Copyright 2022 The Ferric AI Project Developers
+
+use rand::Rng;
+use rand_distr::StandardNormal;
+
+use crate::inference::{Kernel, LogDensity};
+
+/// Hamiltonian Monte Carlo with a fixed step size and trajectory length.
+///
+/// Each transition draws a momentum $\mathbf{p} \sim \mathcal{N}(\mathbf{0}, I)$,
+/// simulates Hamiltonian dynamics for `num_steps` leapfrog steps of size
+/// `step_size`, and accepts the end point with probability
+///
+/// $$\min\!\left(1, \exp\!\left(\log\pi(\theta') - \tfrac{1}{2}\|\mathbf{p}'\|^2
+///   - \log\pi(\theta) + \tfrac{1}{2}\|\mathbf{p}\|^2\right)\right).$$
+///
+/// See [Hamiltonian Monte Carlo](https://en.wikipedia.org/wiki/Hamiltonian_Monte_Carlo)
+/// on Wikipedia for further details.  [`Nuts`](crate::inference::Nuts)
+/// chooses both tuning parameters automatically.
+pub struct Hmc {
+    step_size: f64,
+    num_steps: usize,
+}
+
+impl Hmc {
+    /// Construct an HMC kernel taking `num_steps` leapfrog steps of size
+    /// `step_size` per transition.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if `step_size` is not strictly positive or `num_steps`
+    /// is zero.
+    pub fn new(step_size: f64, num_steps: usize) -> Result<Hmc, String> {
+        if step_size.is_nan() || step_size <= 0.0 {
+            Err(format!(
+                "Hmc: illegal step_size `{}` should be greater than 0",
+                step_size
+            ))
+        } else if num_steps == 0 {
+            Err("Hmc: num_steps should be at least 1".into())
+        } else {
+            Ok(Hmc {
+                step_size,
+                num_steps,
+            })
+        }
+    }
+}
+
+impl Kernel for Hmc {
+    fn transition<T: LogDensity>(&mut self, target: &mut T, position: &mut [f64]) {
+        let mut grad = vec![0.0; position.len()];
+        let log_p0 = target.log_density_grad(position, &mut grad);
+        let mut q = position.to_vec();
+        let mut p = sample_momentum(target.rng(), position.len());
+        let h0 = log_p0 - kinetic_energy(&p);
+        let mut log_p = log_p0;
+        for _ in 0..self.num_steps {
+            log_p = leapfrog(target, &mut q, &mut p, &mut grad, self.step_size);
+        }
+        let h1 = log_p - kinetic_energy(&p);
+        // A NaN energy compares false and is rejected.
+        if target.rng().r#gen::<f64>().ln() < h1 - h0 {
+            position.copy_from_slice(&q);
+        }
+    }
+}
+
+/// Draw a standard normal momentum vector of length `dim`.
+pub(crate) fn sample_momentum<R: Rng>(rng: &mut R, dim: usize) -> Vec<f64> {
+    (0..dim).map(|_| rng.sample(StandardNormal)).collect()
+}
+
+/// Kinetic energy $\tfrac{1}{2}\|\mathbf{p}\|^2$ under an identity mass matrix.
+pub(crate) fn kinetic_energy(p: &[f64]) -> f64 {
+    0.5 * p.iter().map(|x| x * x).sum::<f64>()
+}
+
+/// One leapfrog step of size `step_size`.
+///
+/// On entry `grad` must hold the gradient at `q`; on return `q`, `p` and
+/// `grad` are advanced and the log density at the new `q` is returned.
+pub(crate) fn leapfrog<T: LogDensity>(
+    target: &mut T,
+    q: &mut [f64],
+    p: &mut [f64],
+    grad: &mut [f64],
+    step_size: f64,
+) -> f64 {
+    for (pi, gi) in p.iter_mut().zip(grad.iter()) {
+        *pi += 0.5 * step_size * gi;
+    }
+    for (qi, pi) in q.iter_mut().zip(p.iter()) {
+        *qi += step_size * pi;
+    }
+    let log_p = target.log_density_grad(q, grad);
+    for (pi, gi) in p.iter_mut().zip(grad.iter()) {
+        *pi += 0.5 * step_size * gi;
+    }
+    log_p
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::ThreadRng;
+    use rand::thread_rng;
+
+    /// Independent normals with means (1, -2) and standard deviations (1, 0.5).
+    struct Gaussian(ThreadRng);
+
+    impl LogDensity for Gaussian {
+        type Rng = ThreadRng;
+
+        fn rng(&mut self) -> &mut ThreadRng {
+            &mut self.0
+        }
+
+        fn log_density(&mut self, position: &[f64]) -> f64 {
+            let z0 = position[0] - 1.0;
+            let z1 = (position[1] + 2.0) / 0.5;
+            -0.5 * (z0 * z0 + z1 * z1)
+        }
+    }
+
+    #[test]
+    fn hmc_gaussian_moments() {
+        let mut target = Gaussian(thread_rng());
+        let mut kernel = Hmc::new(0.2, 10).unwrap();
+        let mut position = vec![0.0, 0.0];
+        let trials = 5000;
+        let mut sums = [0.0; 2];
+        let mut sq_sums = [0.0; 2];
+        for _ in 0..200 {
+            kernel.transition(&mut target, &mut position);
+        }
+        for _ in 0..trials {
+            kernel.transition(&mut target, &mut position);
+            for i in 0..2 {
+                sums[i] += position[i];
+                sq_sums[i] += position[i] * position[i];
+            }
+        }
+        let means = sums.map(|s| s / trials as f64);
+        let stds = [0, 1].map(|i| (sq_sums[i] / trials as f64 - means[i] * means[i]).sqrt());
+        assert!((means[0] - 1.0).abs() < 0.1, "mean {:?}", means);
+        assert!((means[1] + 2.0).abs() < 0.05, "mean {:?}", means);
+        assert!((stds[0] - 1.0).abs() < 0.1, "std {:?}", stds);
+        assert!((stds[1] - 0.5).abs() < 0.05, "std {:?}", stds);
+    }
+
+    #[test]
+    fn hmc_invalid() {
+        assert!(Hmc::new(0.0, 10).is_err());
+        assert!(Hmc::new(f64::NAN, 10).is_err());
+        assert!(Hmc::new(0.1, 0).is_err());
+    }
+}
@@ -0,0 +1,13 @@
+// Copyright 2022 The Ferric AI Project Developers
+
+use crate::inference::LogDensity;
+
+/// A Markov transition kernel over the unconstrained space of a
+/// [`LogDensity`].
+pub trait Kernel {
+    /// Advance the chain by one transition, updating `position` in place.
+    ///
+    /// The kernel must leave the density $e^{\log \pi(\theta)}$ of `target`
+    /// invariant.
+    fn transition<T: LogDensity>(&mut self, target: &mut T, position: &mut [f64]);
+}
@@ -0,0 +1,40 @@
+// Copyright 2022 The Ferric AI Project Developers
+
+//! Inference algorithms beyond rejection and importance sampling.
+//!
+//! # Gradient-based Markov chain Monte Carlo
+//!
+//! Models whose latent variables are all `f64` or `nalgebra::DVector<f64>`
+//! get a generated `World` that implements [`LogDensity`] over an
+//! unconstrained parameterisation of the latent space.  A [`Kernel`] such
+//! as [`Hmc`] or [`Nuts`] then moves the chain through that space; use the
+//! generated `Model::hmc_sample_iter` or `Model::nuts_sample_iter` to draw
+//! posterior samples.
+//!
+//! # Sequential Monte Carlo
+//!
+//! The generated `ParticleFilter` of a model incorporates one observation
+//! at a time into a population of worlds, using
+//! [`effective_sample_size`] to decide when to [`systematic_resample`].
+//!
+//! # Parallel sampling
+//!
+//! [`par_draw`] runs independent samplers on several threads, each with its
+//! own seeded random number stream; the generated `Model::par_sample` and
+//! `Model::par_weighted_sample` are built on it.
+mod hmc;
+mod kernel;
+mod log_density;
+mod nuts;
+mod parallel;
+mod smc;
+mod transform;
+
+// Re-exports
+pub use self::hmc::Hmc;
+pub use self::kernel::Kernel;
+pub use self::log_density::LogDensity;
+pub use self::nuts::Nuts;
+pub use self::parallel::par_draw;
+pub use self::smc::{effective_sample_size, systematic_resample};
+pub use self::transform::{from_unconstrained, to_unconstrained};
@@ -0,0 +1,390 @@
+// Copyright 2022 The Ferric AI Project Developers
+
+use rand::Rng;
+
+use crate::inference::hmc::{kinetic_energy, leapfrog, sample_momentum};
+use crate::inference::{Kernel, LogDensity};
+
+// Dual-averaging constants recommended by Hoffman & Gelman (2014).
+const GAMMA: f64 = 0.05;
+const T0: f64 = 10.0;
+const KAPPA: f64 = 0.75;
+// Energy error beyond which a trajectory is considered divergent.
+const MAX_ENERGY_ERROR: f64 = 1000.0;
+
+/// The No-U-Turn Sampler, an adaptive variant of [`Hmc`](crate::inference::Hmc).
+///
+/// Each transition doubles a leapfrog trajectory forwards or backwards in
+/// time until it starts to turn back on itself, so the trajectory length
+/// adapts to the local geometry of the target.  During the first
+/// `num_adapt` transitions the step size is tuned by dual averaging so that
+/// the mean acceptance statistic approaches `target_accept`; afterwards it
+/// is frozen, so only the later samples form a valid Markov chain.
+///
+/// This is the efficient slice-sampling variant (Algorithm 6) of
+/// M. D. Hoffman and A. Gelman, *The No-U-Turn Sampler: Adaptively Setting
+/// Path Lengths in Hamiltonian Monte Carlo*, JMLR 15 (2014).
+pub struct Nuts {
+    target_accept: f64,
+    num_adapt: usize,
+    max_depth: usize,
+    iteration: usize,
+    step_size: f64,
+    // dual-averaging state
+    mu: f64,
+    h_bar: f64,
+    log_step_size_bar: f64,
+}
+
+/// A balanced binary tree of leapfrog states built by [`Nuts::build_tree`].
+struct Tree {
+    q_minus: Vec<f64>,
+    p_minus: Vec<f64>,
+    grad_minus: Vec<f64>,
+    q_plus: Vec<f64>,
+    p_plus: Vec<f64>,
+    grad_plus: Vec<f64>,
+    q_proposal: Vec<f64>,
+    /// Number of states inside the slice.
+    n: f64,
+    /// `false` once the tree has made a U-turn or diverged.
+    keep_going: bool,
+    sum_accept: f64,
+    n_accept: f64,
+}
+
+impl Nuts {
+    /// Construct a NUTS kernel that adapts its step size over the first
+    /// `num_adapt` transitions towards a mean acceptance statistic of
+    /// `target_accept`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if `target_accept` is not strictly between 0 and 1.
+    pub fn new(num_adapt: usize, target_accept: f64) -> Result<Nuts, String> {
+        if !(target_accept > 0.0 && target_accept < 1.0) {
+            Err(format!(
+                "Nuts: illegal target_accept `{}` should be between 0 and 1",
+                target_accept
+            ))
+        } else {
+            Ok(Nuts {
+                target_accept,
+                num_adapt,
+                max_depth: 10,
+                iteration: 0,
+                step_size: 1.0,
+                mu: 0.0,
+                h_bar: 0.0,
+                log_step_size_bar: 0.0,
+            })
+        }
+    }
+
+    /// The current leapfrog step size.
+    pub fn step_size(&self) -> f64 {
+        self.step_size
+    }
+
+    /// Heuristic from Hoffman & Gelman (Algorithm 4): double or halve the
+    /// step size until the acceptance probability of one leapfrog step
+    /// crosses 0.5.
+    fn find_reasonable_step_size<T: LogDensity>(&mut self, target: &mut T, position: &[f64]) {
+        let mut grad0 = vec![0.0; position.len()];
+        let log_p0 = target.log_density_grad(position, &mut grad0);
+        let p0 = sample_momentum(target.rng(), position.len());
+        let h0 = log_p0 - kinetic_energy(&p0);
+        let mut step_size = 1.0;
+        let energy_change = |target: &mut T, step_size: f64| {
+            let mut q = position.to_vec();
+            let mut p = p0.clone();
+            let mut grad = grad0.clone();
+            let log_p = leapfrog(target, &mut q, &mut p, &mut grad, step_size);
+            log_p - kinetic_energy(&p) - h0
+        };
+        let mut delta = energy_change(target, step_size);
+        let direction = if delta > 0.5f64.ln() { 1.0 } else { -1.0 };
+        // Bounded so that a flat or pathological target cannot loop forever.
+        for _ in 0..100 {
+            // (p'/p)^a > 2^-a in log space; a NaN energy stops the search
+            let keep_scaling = direction * delta > -direction * 2f64.ln();
+            if !keep_scaling {
+                break;
+            }
+            step_size *= 2f64.powf(direction);
+            delta = energy_change(target, step_size);
+        }
+        self.step_size = step_size;
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn build_tree<T: LogDensity>(
+        &self,
+        target: &mut T,
+        q: &[f64],
+        p: &[f64],
+        grad: &[f64],
+        log_u: f64,
+        direction: f64,
+        depth: usize,
+        h0: f64,
+    ) -> Tree {
+        if depth == 0 {
+            let mut q = q.to_vec();
+            let mut p = p.to_vec();
+            let mut grad = grad.to_vec();
+            let log_p = leapfrog(
+                target,
+                &mut q,
+                &mut p,
+                &mut grad,
+                direction * self.step_size,
+            );
+            let h = log_p - kinetic_energy(&p);
+            // `f64::min` ignores a NaN, so a NaN energy error is rejected explicitly
+            let energy_error = h - h0;
+            let accept = if energy_error.is_nan() {
+                0.0
+            } else {
+                energy_error.exp().min(1.0)
+            };
+            return Tree {
+                q_minus: q.clone(),
+                p_minus: p.clone(),
+                grad_minus: grad.clone(),
+                q_proposal: q.clone(),
+                q_plus: q,
+                p_plus: p,
+                grad_plus: grad,
+                n: if log_u <= h { 1.0 } else { 0.0 },
+                keep_going: h > log_u - MAX_ENERGY_ERROR,
+                sum_accept: accept,
+                n_accept: 1.0,
+            };
+        }
+        let mut tree = self.build_tree(target, q, p, grad, log_u, direction, depth - 1, h0);
+        if tree.keep_going {
+            let other = if direction < 0.0 {
+                let other = self.build_tree(
+                    target,
+                    &tree.q_minus,
+                    &tree.p_minus,
+                    &tree.grad_minus,
+                    log_u,
+                    direction,
+                    depth - 1,
+                    h0,
+                );
+                tree.q_minus.clone_from(&other.q_minus);
+                tree.p_minus.clone_from(&other.p_minus);
+                tree.grad_minus.clone_from(&other.grad_minus);
+                other
+            } else {
+                let other = self.build_tree(
+                    target,
+                    &tree.q_plus,
+                    &tree.p_plus,
+                    &tree.grad_plus,
+                    log_u,
+                    direction,
+                    depth - 1,
+                    h0,
+                );
+                tree.q_plus.clone_from(&other.q_plus);
+                tree.p_plus.clone_from(&other.p_plus);
+                tree.grad_plus.clone_from(&other.grad_plus);
+                other
+            };
+            let n = tree.n + other.n;
+            if n > 0.0 && target.rng().r#gen::<f64>() < other.n / n {
+                tree.q_proposal = other.q_proposal;
+            }
+            tree.sum_accept += other.sum_accept;
+            tree.n_accept += other.n_accept;
+            tree.keep_going = other.keep_going
+                && no_u_turn(&tree.q_minus, &tree.q_plus, &tree.p_minus, &tree.p_plus);
+            tree.n = n;
+        }
+        tree
+    }
+}
+
+impl Default for Nuts {
+    /// 1000 adaptation transitions towards a target acceptance of 0.8.
+    fn default() -> Nuts {
+        Nuts::new(1000, 0.8).unwrap()
+    }
+}
+
+impl Kernel for Nuts {
+    fn transition<T: LogDensity>(&mut self, target: &mut T, position: &mut [f64]) {
+        if self.iteration == 0 {
+            self.find_reasonable_step_size(target, position);
+            self.mu = (10.0 * self.step_size).ln();
+        }
+        self.iteration += 1;
+
+        let mut grad = vec![0.0; position.len()];
+        let log_p0 = target.log_density_grad(position, &mut grad);
+        let p0 = sample_momentum(target.rng(), position.len());
+        let h0 = log_p0 - kinetic_energy(&p0);
+        // slice variable u ~ Uniform(0, exp(h0)), kept in log space
+        let log_u = h0 + target.rng().r#gen::<f64>().ln();
+
+        let mut tree = Tree {
+            q_minus: position.to_vec(),
+            p_minus: p0.clone(),
+            grad_minus: grad.clone(),
+            q_plus: position.to_vec(),
+            p_plus: p0,
+            grad_plus: grad,
+            q_proposal: position.to_vec(),
+            n: 1.0,
+            keep_going: true,
+            sum_accept: 0.0,
+            n_accept: 0.0,
+        };
+        let mut depth = 0;
+        while tree.keep_going && depth < self.max_depth {
+            let direction = if target.rng().r#gen::<bool>() {
+                1.0
+            } else {
+                -1.0
+            };
+            let other = if direction < 0.0 {
+                let other = self.build_tree(
+                    target,
+                    &tree.q_minus,
+                    &tree.p_minus,
+                    &tree.grad_minus,
+                    log_u,
+                    direction,
+                    depth,
+                    h0,
+                );
+                tree.q_minus.clone_from(&other.q_minus);
+                tree.p_minus.clone_from(&other.p_minus);
+                tree.grad_minus.clone_from(&other.grad_minus);
+                other
+            } else {
+                let other = self.build_tree(
+                    target,
+                    &tree.q_plus,
+                    &tree.p_plus,
+                    &tree.grad_plus,
+                    log_u,
+                    direction,
+                    depth,
+                    h0,
+                );
+                tree.q_plus.clone_from(&other.q_plus);
+                tree.p_plus.clone_from(&other.p_plus);
+                tree.grad_plus.clone_from(&other.grad_plus);
+                other
+            };
+            if other.keep_going && target.rng().r#gen::<f64>() < other.n / tree.n {
+                position.copy_from_slice(&other.q_proposal);
+            }
+            tree.n += other.n;
+            tree.keep_going = other.keep_going
+                && no_u_turn(&tree.q_minus, &tree.q_plus, &tree.p_minus, &tree.p_plus);
+            tree.sum_accept = other.sum_accept;
+            tree.n_accept = other.n_accept;
+            depth += 1;
+        }
+
+        if self.iteration <= self.num_adapt {
+            let m = self.iteration as f64;
+            let eta = 1.0 / (m + T0);
+            self.h_bar = (1.0 - eta) * self.h_bar
+                + eta * (self.target_accept - tree.sum_accept / tree.n_accept);
+            let log_step_size = self.mu - m.sqrt() / GAMMA * self.h_bar;
+            let weight = m.powf(-KAPPA);
+            self.log_step_size_bar =
+                weight * log_step_size + (1.0 - weight) * self.log_step_size_bar;
+            self.step_size = if self.iteration == self.num_adapt {
+                self.log_step_size_bar.exp()
+            } else {
+                log_step_size.exp()
+            };
+        }
+    }
+}
+
+/// `true` while neither end of the trajectory is moving back towards the
+/// other.
+fn no_u_turn(q_minus: &[f64], q_plus: &[f64], p_minus: &[f64], p_plus: &[f64]) -> bool {
+    let mut dot_minus = 0.0;
+    let mut dot_plus = 0.0;
+    for i in 0..q_minus.len() {
+        let dq = q_plus[i] - q_minus[i];
+        dot_minus += dq * p_minus[i];
+        dot_plus += dq * p_plus[i];
+    }
+    dot_minus >= 0.0 && dot_plus >= 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::ThreadRng;
+    use rand::thread_rng;
+
+    /// Correlated bivariate normal with unit variances, correlation 0.9 and
+    /// mean (3, -1).
+    struct Correlated(ThreadRng);
+
+    impl LogDensity for Correlated {
+        type Rng = ThreadRng;
+
+        fn rng(&mut self) -> &mut ThreadRng {
+            &mut self.0
+        }
+
+        fn log_density(&mut self, position: &[f64]) -> f64 {
+            let rho: f64 = 0.9;
+            let x = position[0] - 3.0;
+            let y = position[1] + 1.0;
+            -(x * x - 2.0 * rho * x * y + y * y) / (2.0 * (1.0 - rho * rho))
+        }
+    }
+
+    #[test]
+    fn nuts_correlated_gaussian_moments() {
+        let mut target = Correlated(thread_rng());
+        let mut kernel = Nuts::new(500, 0.8).unwrap();
+        let mut position = vec![0.0, 0.0];
+        for _ in 0..500 {
+            kernel.transition(&mut target, &mut position);
+        }
+        let step_size = kernel.step_size();
+        assert!(step_size > 0.0 && step_size.is_finite());
+        let trials = 4000;
+        let (mut sx, mut sy, mut sxx, mut sxy) = (0.0, 0.0, 0.0, 0.0);
+        for _ in 0..trials {
+            kernel.transition(&mut target, &mut position);
+            // step size is frozen after adaptation
+            assert_eq!(kernel.step_size(), step_size);
+            sx += position[0];
+            sy += position[1];
+            sxx += position[0] * position[0];
+            sxy += position[0] * position[1];
+        }
+        let n = trials as f64;
+        let (mx, my) = (sx / n, sy / n);
+        let var_x = sxx / n - mx * mx;
+        let cov_xy = sxy / n - mx * my;
+        assert!((mx - 3.0).abs() < 0.15, "mean x {}", mx);
+        assert!((my + 1.0).abs() < 0.15, "mean y {}", my);
+        assert!((var_x - 1.0).abs() < 0.2, "var x {}", var_x);
+        assert!((cov_xy - 0.9).abs() < 0.2, "cov xy {}", cov_xy);
+    }
+
+    #[test]
+    fn nuts_invalid() {
+        assert!(Nuts::new(100, 0.0).is_err());
+        assert!(Nuts::new(100, 1.0).is_err());
+        assert!(Nuts::new(100, f64::NAN).is_err());
+        assert_eq!(Nuts::default().num_adapt, 1000);
+    }
+}
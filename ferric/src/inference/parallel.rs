@@ -0,0 +1,91 @@
+// Copyright 2022 The Ferric AI Project Developers
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Draw `num_samples` values on `num_threads` threads and concatenate them.
+///
+/// Each thread receives its own [`StdRng`] stream, seeded from `rng`, and a
+/// share of the samples that differs from the others by at most one.  The
+/// thread `t` calls `draw(stream_t, count_t)`, which must return `count_t`
+/// values.  Results are concatenated in thread order, so for a seeded `rng`
+/// the output does not depend on how the threads are scheduled.
+///
+/// The generated `Model::par_sample` and `Model::par_weighted_sample` use
+/// this to run independent worlds in parallel.
+///
+/// # Panics
+///
+/// Panics if `num_threads` is zero or if `draw` panics on any thread.
+///
+/// # Examples
+///
+/// ```
+/// use ferric::inference::par_draw;
+/// use rand::{Rng, thread_rng};
+///
+/// let draws: Vec<f64> = par_draw(1000, 4, &mut thread_rng(), |mut stream, count| {
+///     (0..count).map(|_| stream.r#gen::<f64>()).collect()
+/// });
+/// assert_eq!(draws.len(), 1000);
+/// ```
+pub fn par_draw<T, R, F>(num_samples: usize, num_threads: usize, rng: &mut R, draw: F) -> Vec<T>
+where
+    T: Send,
+    R: Rng + ?Sized,
+    F: Fn(StdRng, usize) -> Vec<T> + Sync,
+{
+    assert!(num_threads > 0, "num_threads must be positive");
+    let streams: Vec<StdRng> = (0..num_threads)
+        .map(|_| StdRng::from_rng(&mut *rng).unwrap())
+        .collect();
+    let draw = &draw;
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = streams
+            .into_iter()
+            .enumerate()
+            .map(|(t, stream)| {
+                let count = num_samples / num_threads + usize::from(t < num_samples % num_threads);
+                scope.spawn(move || draw(stream, count))
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn par_draw_splits_samples() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let counts = par_draw(10, 4, &mut rng, |_, count| vec![count]);
+        assert_eq!(counts, vec![3, 3, 2, 2]);
+        let none: Vec<usize> = par_draw(0, 3, &mut rng, |_, count| vec![0; count]);
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn par_draw_is_reproducible() {
+        let draw = |mut stream: StdRng, count: usize| -> Vec<u64> {
+            (0..count).map(|_| stream.r#gen()).collect()
+        };
+        let first = par_draw(100, 3, &mut StdRng::seed_from_u64(1), draw);
+        let second = par_draw(100, 3, &mut StdRng::seed_from_u64(1), draw);
+        assert_eq!(first, second);
+        // every thread has its own stream
+        assert_ne!(first[..33], first[34..67]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn par_draw_zero_threads() {
+        par_draw(10, 0, &mut StdRng::seed_from_u64(0), |_, count| {
+            vec![0; count]
+        });
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright 2022 The Ferric AI Project Developers
+
+use rand::Rng;
+
+use crate::log_sum_exp;
+
+/// Effective sample size of a population with unnormalised log weights,
+///
+/// $$\mathrm{ESS} = \frac{\left(\sum_i w_i\right)^2}{\sum_i w_i^2}.$$
+///
+/// It ranges from 1, when a single particle carries all the weight, to the
+/// number of particles, when all weights are equal.  Returns 0 if every
+/// weight is zero.
+///
+/// # Examples
+///
+/// ```
+/// use ferric::inference::effective_sample_size;
+///
+/// assert!((effective_sample_size(&[0.0; 4]) - 4.0).abs() < 1e-12);
+/// assert!((effective_sample_size(&[0.0, -1000.0]) - 1.0).abs() < 1e-12);
+/// ```
+pub fn effective_sample_size(log_weights: &[f64]) -> f64 {
+    let log_total = log_sum_exp(log_weights);
+    if log_total == f64::NEG_INFINITY {
+        return 0.0;
+    }
+    let doubled: Vec<f64> = log_weights.iter().map(|lw| 2.0 * lw).collect();
+    (2.0 * log_total - log_sum_exp(&doubled)).exp()
+}
+
+/// Draw ancestor indices by systematic resampling.
+///
+/// A single uniform offset $u \sim \mathcal{U}(0, 1/N)$ selects the $N$
+/// points $u + k/N$ on the cumulative normalised weights, so particle $i$ is
+/// copied either $\lfloor N w_i \rfloor$ or $\lceil N w_i \rceil$ times.
+/// The returned indices are in increasing order.
+///
+/// # Panics
+///
+/// Panics if every weight is zero.
+pub fn systematic_resample<R: Rng + ?Sized>(log_weights: &[f64], rng: &mut R) -> Vec<usize> {
+    let n = log_weights.len();
+    let log_total = log_sum_exp(log_weights);
+    assert!(
+        log_total > f64::NEG_INFINITY,
+        "cannot resample particles that all have zero weight"
+    );
+    let offset: f64 = rng.r#gen::<f64>() / n as f64;
+    let mut ancestors = Vec::with_capacity(n);
+    let mut cumulative = 0.0;
+    let mut i = 0;
+    for (index, lw) in log_weights.iter().enumerate() {
+        cumulative += (lw - log_total).exp();
+        while i < n && offset + (i as f64) / (n as f64) < cumulative {
+            ancestors.push(index);
+            i += 1;
+        }
+    }
+    // rounding can leave the last few points just past the final cumulative sum
+    while ancestors.len() < n {
+        ancestors.push(n - 1);
+    }
+    ancestors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+
+    #[test]
+    fn ess_extremes() {
+        assert!((effective_sample_size(&[-3.0; 10]) - 10.0).abs() < 1e-9);
+        assert!((effective_sample_size(&[0.0, f64::NEG_INFINITY]) - 1.0).abs() < 1e-12);
+        assert_eq!(effective_sample_size(&[f64::NEG_INFINITY; 3]), 0.0);
+    }
+
+    #[test]
+    fn systematic_resample_counts() {
+        // normalised weights 0.5, 0.25, 0.25, 0 over 4 particles: counts 2, 1, 1, 0
+        let log_weights = [0.5f64.ln(), 0.25f64.ln(), 0.25f64.ln(), f64::NEG_INFINITY];
+        let ancestors = systematic_resample(&log_weights, &mut thread_rng());
+        assert_eq!(ancestors, [0, 0, 1, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn systematic_resample_zero_weights() {
+        systematic_resample(&[f64::NEG_INFINITY; 2], &mut thread_rng());
+    }
+}
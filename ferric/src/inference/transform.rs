@@ -0,0 +1,103 @@
+// Copyright 2022 The Ferric AI Project Developers
+
+/// Map `x`, which lies inside the support `(low, high)`, onto the real line.
+///
+/// The support is usually obtained from
+/// [`Distribution::support`](crate::distributions::Distribution::support):
+///
+/// - $(-\infty, \infty)$: $u = x$,
+/// - $(a, \infty)$: $u = \ln(x - a)$,
+/// - $(-\infty, b)$: $u = \ln(b - x)$,
+/// - $(a, b)$: $u = \ln\frac{x - a}{b - x}$.
+///
+/// # Examples
+///
+/// ```
+/// use ferric::inference::{from_unconstrained, to_unconstrained};
+///
+/// let u = to_unconstrained(2.0, (0.0, f64::INFINITY));
+/// let (x, _log_jacobian) = from_unconstrained(u, (0.0, f64::INFINITY));
+/// assert!((x - 2.0).abs() < 1e-12);
+/// ```
+pub fn to_unconstrained(x: f64, (low, high): (f64, f64)) -> f64 {
+    match (low.is_finite(), high.is_finite()) {
+        (false, false) => x,
+        (true, false) => (x - low).ln(),
+        (false, true) => (high - x).ln(),
+        (true, true) => ((x - low) / (high - x)).ln(),
+    }
+}
+
+/// Inverse of [`to_unconstrained`].
+///
+/// Returns the value $x$ in the support `(low, high)` together with the
+/// log-Jacobian $\ln\left|\frac{dx}{du}\right|$ of the mapping, which must be
+/// added to the log density of $x$ to obtain a density over $u$.
+pub fn from_unconstrained(u: f64, (low, high): (f64, f64)) -> (f64, f64) {
+    match (low.is_finite(), high.is_finite()) {
+        (false, false) => (u, 0.0),
+        (true, false) => (low + u.exp(), u),
+        (false, true) => (high - u.exp(), u),
+        (true, true) => {
+            // x = a + (b - a) σ(u),  dx/du = (b - a) σ(u) (1 - σ(u))
+            let sigmoid = 1.0 / (1.0 + (-u).exp());
+            let log_jacobian = (high - low).ln() - log1p_exp(-u) - log1p_exp(u);
+            (low + (high - low) * sigmoid, log_jacobian)
+        }
+    }
+}
+
+// ln(1 + e^a) without overflow for large `a`.
+fn log1p_exp(a: f64) -> f64 {
+    if a > 0.0 {
+        a + (-a).exp().ln_1p()
+    } else {
+        a.exp().ln_1p()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_all_support_kinds() {
+        let cases = [
+            (-1.5, (f64::NEG_INFINITY, f64::INFINITY)),
+            (2.5, (1.0, f64::INFINITY)),
+            (-2.5, (f64::NEG_INFINITY, 1.0)),
+            (0.25, (0.0, 1.0)),
+        ];
+        for (x, support) in cases {
+            let u = to_unconstrained(x, support);
+            let (y, _) = from_unconstrained(u, support);
+            assert!((x - y).abs() < 1e-12, "{} != {} for {:?}", x, y, support);
+        }
+    }
+
+    #[test]
+    fn log_jacobian_matches_numerical_derivative() {
+        let h = 1e-6;
+        for support in [
+            (f64::NEG_INFINITY, f64::INFINITY),
+            (1.0, f64::INFINITY),
+            (f64::NEG_INFINITY, 1.0),
+            (-2.0, 3.0),
+        ] {
+            for u in [-3.0, 0.0, 0.7] {
+                let (_, log_jacobian) = from_unconstrained(u, support);
+                let (up, _) = from_unconstrained(u + h, support);
+                let (down, _) = from_unconstrained(u - h, support);
+                let numerical = ((up - down) / (2.0 * h)).abs().ln();
+                assert!((log_jacobian - numerical).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn interval_transform_is_stable_for_large_u() {
+        let (x, log_jacobian) = from_unconstrained(800.0, (0.0, 1.0));
+        assert_eq!(x, 1.0);
+        assert!((log_jacobian - (-800.0)).abs() < 1e-9);
+    }
+}
@@ -6,9 +6,16 @@
 //! # Key entry points
 //!
 //! - [`make_model!`] — declare a probabilistic model; expands into a module
-//!   with `Model`, `Sample`, `WeightedSample`, and two iterator types.
+//!   with `Model`, `Sample`, `WeightedSample`, and iterator types for
+//!   rejection sampling, importance sampling, Metropolis-Hastings, and
+//!   sequential Monte Carlo, plus exact enumeration for finite discrete
+//!   models.
 //! - [`weighted_mean`] / [`weighted_std`] — posterior summaries from
-//!   self-normalised importance-sampling (SNIS) weights.
+//!   self-normalised importance-sampling (SNIS) weights,
+//!   [`log_evidence`] for the marginal likelihood of the observations, and
+//!   [`log_sum_exp`] for combining log weights.
+//! - [`Diagnostics`] — the worlds a sampler discarded because a distribution
+//!   received invalid parameters.
 //! - [`distributions`] — built-in probability distributions:
 //!   [`Bernoulli`](distributions::Bernoulli),
 //!   [`Binomial`](distributions::Binomial),
@@ -25,6 +32,11 @@
 //!   [`MultivariateNormal`](distributions::MultivariateNormal),
 //!   [`MatrixNormal`](distributions::MatrixNormal), and
 //!   [`Wishart`](distributions::Wishart).
+//! - [`special`] — log-gamma, log-beta, incomplete gamma and beta, error
+//!   functions, and log-space arithmetic used by the distributions.
+//! - [`inference`] — gradient-based samplers
+//!   ([`Hmc`](inference::Hmc), [`Nuts`](inference::Nuts)) for models whose
+//!   latent variables are all real valued.
 //!
 //! See the [README](https://github.com/Ferric-AI/ferric#readme) for a
 //! quick-start guide and worked examples.
@@ -35,11 +47,17 @@ pub use ferric_macros::make_model;
 // Public modules
 pub mod core;
 pub mod distributions;
+pub mod inference;
+pub mod special;
 
-// re-export FeOption and its variants
+// re-export FeOption and its variants, and Diagnostics
+pub use self::core::Diagnostics;
 pub use self::core::FeOption;
 pub use FeOption::{Known, Null, Unknown};
 
+// re-export log_sum_exp, which predates the special module
+pub use self::special::log_sum_exp;
+
 /// Compute the self-normalised importance-weighted mean of `values`.
 ///
 /// Given a collection of values $x_i$ and their corresponding log importance
@@ -135,6 +153,69 @@ pub fn weighted_std(values: &[f64], log_weights: &[f64]) -> f64 {
     variance.sqrt()
 }
 
+/// An importance-sampling estimate of the log marginal likelihood
+/// $\ln p(\text{observations})$, returned by [`log_evidence`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LogEvidence {
+    /// The estimate $\ln \hat{Z}$.
+    pub estimate: f64,
+    /// The Monte Carlo standard error of `estimate`.
+    pub std_error: f64,
+}
+
+/// Estimate the log marginal likelihood from the log importance weights of
+/// likelihood-weighted samples.
+///
+/// With weights $w_i = e^{\tilde{w}_i}$ drawn from the prior, the evidence
+/// $Z = p(\text{observations})$ is estimated without bias by their mean, and
+/// its logarithm is computed with [`log_sum_exp`]:
+///
+/// $$\ln \hat{Z} = \ln \sum_i e^{\tilde{w}_i} - \ln N$$
+///
+/// The standard error of $\ln \hat{Z}$ follows from the delta method,
+///
+/// $$\mathrm{se}(\ln \hat{Z}) \approx \frac{\mathrm{sd}(w)}{\sqrt{N}\,\hat{Z}},$$
+///
+/// and is infinite when fewer than two weights are given or every weight is
+/// zero.  Differences of two estimates give log Bayes factors between
+/// competing models of the same observations.
+///
+/// # Panics
+///
+/// Panics if `log_weights` is empty.
+///
+/// # Examples
+///
+/// ```
+/// use ferric::log_evidence;
+///
+/// // Every sample explains the data with probability 0.5.
+/// let log_weights = vec![0.5_f64.ln(); 10];
+/// let evidence = log_evidence(&log_weights);
+/// assert!((evidence.estimate - 0.5_f64.ln()).abs() < 1e-10);
+/// assert!(evidence.std_error.abs() < 1e-10);
+/// ```
+pub fn log_evidence(log_weights: &[f64]) -> LogEvidence {
+    assert!(!log_weights.is_empty(), "log_weights must not be empty");
+    let n = log_weights.len() as f64;
+    let estimate = log_sum_exp(log_weights) - n.ln();
+    if log_weights.len() < 2 || estimate == f64::NEG_INFINITY {
+        return LogEvidence {
+            estimate,
+            std_error: f64::INFINITY,
+        };
+    }
+    // weights relative to their mean, so that the sum of squares cannot overflow
+    let sum_sq = log_weights
+        .iter()
+        .map(|&lw| ((lw - estimate).exp() - 1.0).powi(2))
+        .sum::<f64>();
+    LogEvidence {
+        estimate,
+        std_error: (sum_sq / (n - 1.0) / n).sqrt(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -156,6 +237,32 @@ mod tests {
         assert!((std - (2.0f64 / 3.0).sqrt()).abs() < 1e-10);
     }
 
+    #[test]
+    fn log_sum_exp_edge_cases() {
+        assert_eq!(log_sum_exp(&[]), f64::NEG_INFINITY);
+        assert_eq!(log_sum_exp(&[f64::NEG_INFINITY; 2]), f64::NEG_INFINITY);
+        assert_eq!(log_sum_exp(&[0.0, f64::INFINITY]), f64::INFINITY);
+        assert!((log_sum_exp(&[0.0, f64::NEG_INFINITY]) - 0.0).abs() < 1e-12);
+        assert!((log_sum_exp(&[-1000.0, -1000.0]) - (-1000.0 + 2f64.ln())).abs() < 1e-10);
+    }
+
+    #[test]
+    fn log_evidence_estimate_and_error() {
+        // weights 1 and 3: Z = 2, sd(w) = sqrt(2), se(ln Z) = sqrt(2) / (sqrt(2) * 2)
+        let evidence = log_evidence(&[0.0, 3f64.ln()]);
+        assert!((evidence.estimate - 2f64.ln()).abs() < 1e-12);
+        assert!((evidence.std_error - 0.5).abs() < 1e-12);
+        // large log weights do not overflow
+        let shifted = log_evidence(&[1000.0, 1000.0 + 3f64.ln()]);
+        assert!((shifted.estimate - (1000.0 + 2f64.ln())).abs() < 1e-9);
+        assert!((shifted.std_error - 0.5).abs() < 1e-9);
+        // degenerate inputs
+        assert_eq!(log_evidence(&[0.0]).std_error, f64::INFINITY);
+        let impossible = log_evidence(&[f64::NEG_INFINITY; 3]);
+        assert_eq!(impossible.estimate, f64::NEG_INFINITY);
+        assert_eq!(impossible.std_error, f64::INFINITY);
+    }
+
     #[test]
     fn weighted_mean_concentrated() {
         // all weight on the last element
@@ -1,4 +1,5 @@
 // Copyright 2022 The Ferric AI Project Developers
+use ferric::distributions::{Beta, Moments};
 use ferric::make_model;
 
 // Beta-Binomial conjugate model.
@@ -37,11 +38,12 @@ fn beta_binomial() {
         log_weights.push(ws.log_weight);
     }
 
-    // Conjugate posterior mean: Beta(8, 4) => 8/12 = 2/3
+    let posterior = Beta::new(8.0, 4.0).unwrap();
     let mean = ferric::weighted_mean(&theta_vals, &log_weights);
-    println!("posterior theta mean = {:.4}", mean);
+    let std = ferric::weighted_std(&theta_vals, &log_weights);
+    println!("posterior theta = {:.4} ± {:.4}", mean, std);
 
-    let expected = 8.0 / 12.0;
+    let expected = posterior.mean().unwrap();
     let tol = 0.03;
     assert!(
         (mean - expected).abs() < tol,
@@ -49,4 +51,5 @@ fn beta_binomial() {
         mean,
         expected
     );
+    assert!((std - posterior.variance().unwrap().sqrt()).abs() < tol);
 }
@@ -0,0 +1,161 @@
+// Copyright 2022 The Ferric AI Project Developers
+use ferric::make_model;
+
+// --- Two dice conditioned on a large total ---
+//
+//   a, b ~ DiscreteUniform(1, 6)
+//   condition a + b >= 10
+//
+// Six of the 36 outcomes satisfy the condition: (4,6), (5,5), (5,6), (6,4),
+// (6,5) and (6,6), so E[ a | a + b >= 10 ] = 32 / 6.
+
+make_model! {
+    mod dice;
+    use ferric::distributions::DiscreteUniform;
+
+    let a : i64 ~ DiscreteUniform::new( 1, 6 );
+    let b : i64 ~ DiscreteUniform::new( 1, 6 );
+    condition a + b >= 10;
+    query a;
+    query b;
+}
+
+const POSTERIOR_MEAN_A: f64 = 32.0 / 6.0;
+
+#[test]
+fn condition_rejection_sampling() {
+    let model = dice::Model {};
+    let num_samples = 20_000;
+    let samples: Vec<dice::Sample> = model.sample_iter().take(num_samples).collect();
+    assert!(samples.iter().all(|s| s.a + s.b >= 10));
+    let mean = samples.iter().map(|s| s.a as f64).sum::<f64>() / num_samples as f64;
+    println!("rejection posterior a = {:.4}", mean);
+    assert!((mean - POSTERIOR_MEAN_A).abs() < 0.03);
+}
+
+#[test]
+fn condition_weighted_sampling() {
+    let model = dice::Model {};
+    let mut values = Vec::new();
+    let mut log_weights = Vec::new();
+    for ws in model.weighted_sample_iter().take(50_000) {
+        if ws.sample.a + ws.sample.b < 10 {
+            assert_eq!(ws.log_weight, f64::NEG_INFINITY);
+        } else {
+            assert_eq!(ws.log_weight, 0.0);
+        }
+        values.push(ws.sample.a as f64);
+        log_weights.push(ws.log_weight);
+    }
+    let mean = ferric::weighted_mean(&values, &log_weights);
+    println!("weighted posterior a = {:.4}", mean);
+    assert!((mean - POSTERIOR_MEAN_A).abs() < 0.05);
+}
+
+#[test]
+fn condition_enumeration() {
+    let model = dice::Model {};
+    let posterior = model.enumerate().unwrap();
+    assert_eq!(posterior.samples.len(), 6);
+    let mean: f64 = posterior
+        .samples
+        .iter()
+        .map(|s| s.probability * s.sample.a as f64)
+        .sum();
+    assert!((mean - POSTERIOR_MEAN_A).abs() < 1e-12);
+    assert!((posterior.log_evidence - (6.0f64 / 36.0).ln()).abs() < 1e-12);
+}
+
+// --- A continuous event ---
+//
+//   x, y ~ Normal(0, 1)
+//   condition x + y > 3
+//
+// By symmetry E[ x | x + y ] = (x + y) / 2, and x + y ~ Normal(0, sqrt(2)) is
+// truncated to (3, inf), so E[ x | x + y > 3 ] is about 1.7544.
+
+make_model! {
+    mod tail_event;
+    use ferric::distributions::Normal;
+
+    let x : f64 ~ Normal::new( 0.0, 1.0 );
+    let y : f64 ~ Normal::new( 0.0, 1.0 );
+    condition x + y > 3.0;
+    query x;
+    query y;
+}
+
+const POSTERIOR_MEAN_X: f64 = 1.754_400_400_889_763_9;
+
+#[test]
+fn continuous_condition_weighted_sampling() {
+    let model = tail_event::Model {};
+    let mut xs = Vec::new();
+    let mut log_weights = Vec::new();
+    for ws in model.weighted_sample_iter().take(200_000) {
+        xs.push(ws.sample.x);
+        log_weights.push(ws.log_weight);
+    }
+    let mean = ferric::weighted_mean(&xs, &log_weights);
+    println!("weighted posterior x = {:.4}", mean);
+    assert!((mean - POSTERIOR_MEAN_X).abs() < 0.05);
+}
+
+#[test]
+fn continuous_condition_markov_chains() {
+    let model = tail_event::Model {};
+    // both chains start from a prior draw that satisfies the condition, and
+    // their moves out of the event are rejected
+    let mh: Vec<tail_event::Sample> = model.mh_sample_iter().skip(2000).take(50_000).collect();
+    assert!(mh.iter().all(|s| s.x + s.y > 3.0));
+    let mh_mean = mh.iter().map(|s| s.x).sum::<f64>() / mh.len() as f64;
+    // NUTS mixes slowly because its steps across the boundary are rejected
+    let nuts: Vec<tail_event::Sample> = model.nuts_sample_iter().take(11_000).collect();
+    assert!(nuts.iter().all(|s| s.x + s.y > 3.0));
+    let nuts_mean = nuts.iter().skip(1000).map(|s| s.x).sum::<f64>() / 10_000.0;
+    println!("mh posterior x = {:.4}, nuts {:.4}", mh_mean, nuts_mean);
+    assert!((mh_mean - POSTERIOR_MEAN_X).abs() < 0.1);
+    assert!((nuts_mean - POSTERIOR_MEAN_X).abs() < 0.25);
+}
+
+// --- An event that never happens ---
+//
+// No state satisfies the condition, so a Markov chain has nowhere to start.
+// It stops after `Diagnostics::MAX_CONSECUTIVE_INVALID` draws instead of
+// retrying forever.
+
+make_model! {
+    mod impossible_event;
+    use ferric::distributions::Uniform;
+
+    let x : f64 ~ Uniform::new( 0.0, 1.0 );
+    condition x > 2.0;
+    query x;
+}
+
+#[test]
+fn metropolis_hastings_start_terminates_without_valid_worlds() {
+    let mut chain = impossible_event::Model {}.mh_sample_iter();
+    assert!(chain.next().is_none());
+    let diagnostics = chain.diagnostics();
+    assert!(diagnostics.stopped());
+    assert_eq!(
+        diagnostics.num_invalid(),
+        ferric::Diagnostics::MAX_CONSECUTIVE_INVALID
+    );
+    assert_eq!(
+        diagnostics.messages()[0],
+        "a `condition` of the model is false"
+    );
+}
+
+#[test]
+fn nuts_start_terminates_without_valid_worlds() {
+    let mut chain = impossible_event::Model {}.nuts_sample_iter();
+    assert!(chain.next().is_none());
+    assert!(chain.diagnostics().stopped());
+    assert_eq!(
+        chain.diagnostics().messages()[0],
+        "a `condition` of the model is false"
+    );
+}
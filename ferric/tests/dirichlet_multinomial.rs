@@ -1,4 +1,5 @@
 // Copyright 2022 The Ferric AI Project Developers
+use ferric::distributions::{Dirichlet, Moments};
 use ferric::make_model;
 
 // Dirichlet-Multinomial conjugate model.
@@ -44,7 +45,6 @@ fn dirichlet_multinomial() {
         log_weights.push(ws.log_weight);
     }
 
-    // Conjugate posterior means: Dirichlet([6,4,3]) => [6/13, 4/13, 3/13]
     let mean0 = ferric::weighted_mean(&theta0_vals, &log_weights);
     let mean1 = ferric::weighted_mean(&theta1_vals, &log_weights);
     let mean2 = ferric::weighted_mean(&theta2_vals, &log_weights);
@@ -54,9 +54,8 @@ fn dirichlet_multinomial() {
         mean0, mean1, mean2
     );
 
-    let expected0 = 6.0 / 13.0;
-    let expected1 = 4.0 / 13.0;
-    let expected2 = 3.0 / 13.0;
+    let expected = Dirichlet::new(vec![6.0, 4.0, 3.0]).unwrap().mean().unwrap();
+    let (expected0, expected1, expected2) = (expected[0], expected[1], expected[2]);
     let tol = 0.03;
 
     assert!(
@@ -0,0 +1,206 @@
+// Copyright 2022 The Ferric AI Project Developers
+use ferric::make_model;
+
+// --- Exact enumeration of the grass Bayesian network ---
+//
+// The grass model from Wikipedia, observing that the grass is wet:
+//
+//   P( Grass Is Wet, Rain )     = 0.2 · (0.01 · 0.99 + 0.99 · 0.8) = 0.16038
+//   P( Grass Is Wet, No Rain )  = 0.8 · (0.4 · 0.9 + 0.6 · 0.0)    = 0.288
+//   P( Grass Is Wet, Sprinkler) = 0.2 · 0.01 · 0.99 + 0.8 · 0.4 · 0.9 = 0.28998
+//
+// so P( Rain | Grass Is Wet ) = 0.16038 / 0.44838 ≈ .3577 exactly.
+
+make_model! {
+    mod exact_grass;
+    use ferric::distributions::Bernoulli;
+
+    let rain : bool ~ Bernoulli::new( 0.2 );
+
+    let sprinkler : bool ~
+        if rain {
+            Bernoulli::new( 0.01 )
+        } else {
+            Bernoulli::new( 0.4 )
+        };
+
+    let grass_wet : bool ~ Bernoulli::new(
+        if sprinkler && rain { 0.99 }
+        else if sprinkler && !rain { 0.9 }
+        else if !sprinkler && rain { 0.8 }
+        else { 0.0 }
+    );
+
+    observe grass_wet;
+    query rain;
+    query sprinkler;
+}
+
+#[test]
+fn enumerate_grass() {
+    let model = exact_grass::Model { grass_wet: true };
+    let posterior = model.enumerate().unwrap();
+
+    // the rain-and-no-sprinkler, no-rain-and-sprinkler and rain-and-sprinkler
+    // assignments are consistent with wet grass
+    assert_eq!(posterior.samples.len(), 3);
+    let total: f64 = posterior.samples.iter().map(|s| s.probability).sum();
+    assert!((total - 1.0).abs() < 1e-12);
+
+    let mut post_rain = 0.0;
+    let mut post_sprinkler = 0.0;
+    for s in posterior.samples.iter() {
+        if s.sample.rain {
+            post_rain += s.probability;
+        }
+        if s.sample.sprinkler {
+            post_sprinkler += s.probability;
+        }
+    }
+    println!(
+        "exact: rain = {:.4} sprinkler = {:.4}",
+        post_rain, post_sprinkler
+    );
+    assert!((post_rain - 0.16038 / 0.44838).abs() < 1e-12);
+    assert!((post_sprinkler - 0.28998 / 0.44838).abs() < 1e-12);
+    assert!((posterior.log_evidence - 0.44838_f64.ln()).abs() < 1e-12);
+}
+
+// --- Latent variables with larger supports ---
+//
+//   coin  ~ Categorical([0.5, 0.5]),   bias = [0.3, 0.7][coin]
+//   heads ~ Binomial(3, bias),         heads = 2
+//
+// P( coin = 1 | heads = 2 ) = 0.7² · 0.3 / (0.3² · 0.7 + 0.7² · 0.3) = 0.7
+
+#[test]
+fn enumerate_categorical_binomial() {
+    make_model! {
+        mod exact_coin;
+        use ferric::distributions::Binomial;
+        use ferric::distributions::Categorical;
+
+        let coin : usize ~ Categorical::new( vec![0.5, 0.5] );
+        let bias : f64 = [0.3, 0.7][coin];
+        let heads : u64 ~ Binomial::new( 3, bias );
+
+        observe heads;
+        query coin;
+    };
+
+    let model = exact_coin::Model { heads: 2 };
+    let posterior = model.enumerate().unwrap();
+    let post_coin: f64 = posterior
+        .samples
+        .iter()
+        .filter(|s| s.sample.coin == 1)
+        .map(|s| s.probability)
+        .sum();
+    assert!((post_coin - 0.7).abs() < 1e-12);
+    // p(heads = 2) = 0.5 · 3 · (0.3² · 0.7 + 0.7² · 0.3)
+    assert!((posterior.log_evidence - (0.5_f64 * 3.0 * 0.21).ln()).abs() < 1e-12);
+}
+
+// --- Observing a deterministic variable ---
+//
+// Two fair dice that sum to 7: every value of the first die is equally
+// likely, and p(total = 7) = 6/36.
+
+#[test]
+fn enumerate_deterministic_observation() {
+    make_model! {
+        mod exact_dice;
+        use ferric::distributions::DiscreteUniform;
+
+        let first : i64 ~ DiscreteUniform::new( 1, 6 );
+        let second : i64 ~ DiscreteUniform::new( 1, 6 );
+        let total : i64 = first + second;
+
+        observe total;
+        query first;
+        query second;
+    };
+
+    let model = exact_dice::Model { total: 7 };
+    let posterior = model.enumerate().unwrap();
+    assert_eq!(posterior.samples.len(), 6);
+    for s in posterior.samples.iter() {
+        assert_eq!(s.sample.first + s.sample.second, 7);
+        assert!((s.probability - 1.0 / 6.0).abs() < 1e-12);
+    }
+    assert!((posterior.log_evidence - (6.0_f64 / 36.0).ln()).abs() < 1e-12);
+}
+
+// The same dice, querying only the first: the six assignments of both dice
+// for each value of the first are summed into one entry.
+
+#[test]
+fn enumerate_marginalises_unqueried_variables() {
+    make_model! {
+        mod exact_first_die;
+        use ferric::distributions::DiscreteUniform;
+
+        let first : i64 ~ DiscreteUniform::new( 1, 6 );
+        let second : i64 ~ DiscreteUniform::new( 1, 6 );
+        let high : bool = first + second > 8;
+
+        observe high;
+        query first;
+    };
+
+    // p(first + second > 8 | first) = 0, 0, 1/6, 2/6, 3/6, 4/6 for first = 1..6
+    let model = exact_first_die::Model { high: true };
+    let posterior = model.enumerate().unwrap();
+    assert_eq!(posterior.samples.len(), 4);
+    for s in posterior.samples.iter() {
+        let expected = (s.sample.first - 2) as f64 / 10.0;
+        assert!((s.probability - expected).abs() < 1e-12);
+    }
+}
+
+// Querying a real-valued function of the first die, which cannot be hashed:
+// the assignments are merged by the value of the first die instead, and the
+// entries come in the order of its support.
+
+#[test]
+fn enumerate_marginalises_real_queries() {
+    make_model! {
+        mod exact_half_die;
+        use ferric::distributions::DiscreteUniform;
+
+        let first : i64 ~ DiscreteUniform::new( 1, 6 );
+        let second : i64 ~ DiscreteUniform::new( 1, 6 );
+        let half : f64 = first as f64 / 2.0;
+        let high : bool = first + second > 8;
+
+        observe high;
+        query half;
+    };
+
+    let model = exact_half_die::Model { high: true };
+    let posterior = model.enumerate().unwrap();
+    let halves: Vec<f64> = posterior.samples.iter().map(|s| s.sample.half).collect();
+    assert_eq!(halves, vec![1.5, 2.0, 2.5, 3.0]);
+    for s in posterior.samples.iter() {
+        let expected = (2.0 * s.sample.half - 2.0) / 10.0;
+        assert!((s.probability - expected).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn enumerate_infinite_support_is_error() {
+    make_model! {
+        mod exact_normal;
+        use ferric::distributions::Normal;
+
+        let mu : f64 ~ Normal::new( 0.0, 1.0 );
+        let x : f64 ~ Normal::new( mu, 1.0 );
+
+        observe x;
+        query mu;
+    };
+
+    let model = exact_normal::Model { x: 0.5 };
+    let err = model.enumerate().err().unwrap();
+    assert!(err.contains("`mu`"), "unexpected error: {}", err);
+}
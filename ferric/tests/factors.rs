@@ -0,0 +1,146 @@
+// Copyright 2022 The Ferric AI Project Developers
+use ferric::make_model;
+
+// --- A soft constraint as a `factor` ---
+//
+//   x ~ Normal(0, 1)
+//   factor -2 (x - 1)^2
+//
+// The factor is a Normal(1, 0.5) pseudo-likelihood of `x`, so the posterior
+// is Normal(0.8, 1/sqrt(5)).
+
+make_model! {
+    mod soft_constraint;
+    use ferric::distributions::Normal;
+
+    let x : f64 ~ Normal::new( 0.0, 1.0 );
+    factor -2.0 * (x - 1.0).powi(2);
+    query x;
+}
+
+const POSTERIOR_MEAN: f64 = 0.8;
+
+#[test]
+fn factor_weighted_sampling() {
+    let model = soft_constraint::Model {};
+    let mut xs = Vec::new();
+    let mut log_weights = Vec::new();
+    for ws in model.weighted_sample_iter().take(100_000) {
+        xs.push(ws.sample.x);
+        log_weights.push(ws.log_weight);
+    }
+    let mean = ferric::weighted_mean(&xs, &log_weights);
+    println!("posterior x = {:.4}", mean);
+    assert!((mean - POSTERIOR_MEAN).abs() < 0.02);
+}
+
+#[test]
+fn factor_markov_chains() {
+    let model = soft_constraint::Model {};
+    let mh: Vec<f64> = model
+        .mh_sample_iter()
+        .skip(1000)
+        .take(40_000)
+        .map(|s| s.x)
+        .collect();
+    let mh_mean = mh.iter().sum::<f64>() / mh.len() as f64;
+    let nuts: Vec<f64> = model
+        .nuts_sample_iter()
+        .skip(1000)
+        .take(4000)
+        .map(|s| s.x)
+        .collect();
+    let nuts_mean = nuts.iter().sum::<f64>() / nuts.len() as f64;
+    println!("mh posterior x = {:.4}, nuts {:.4}", mh_mean, nuts_mean);
+    assert!((mh_mean - POSTERIOR_MEAN).abs() < 0.05);
+    assert!((nuts_mean - POSTERIOR_MEAN).abs() < 0.05);
+}
+
+#[test]
+fn factor_smc() {
+    let model = soft_constraint::Model {};
+    let mut filter = model.particle_filter(20_000);
+    // the factor is the only step
+    assert!(filter.step());
+    assert!(!filter.step());
+    let samples = filter.samples();
+    let xs: Vec<f64> = samples.iter().map(|ws| ws.sample.x).collect();
+    let log_weights: Vec<f64> = samples.iter().map(|ws| ws.log_weight).collect();
+    assert!((ferric::weighted_mean(&xs, &log_weights) - POSTERIOR_MEAN).abs() < 0.03);
+    // the normalizing constant of the factor is sqrt(1/5) exp(-0.4)
+    let expected = 0.5 * 0.2f64.ln() - 0.4;
+    assert!((filter.log_evidence() - expected).abs() < 0.05);
+}
+
+// --- A factor alongside an observation ---
+//
+//   biased ~ Bernoulli(0.5)
+//   heads  ~ Bernoulli(if biased { 0.9 } else { 0.5 })
+//   factor log(0.25) if biased
+// Observation: heads = true
+//
+//   P( biased | heads ) = 0.25 · 0.9 / (0.25 · 0.9 + 0.5) = 0.225 / 0.725
+
+make_model! {
+    mod penalised_coin;
+    use ferric::distributions::Bernoulli;
+
+    let biased : bool ~ Bernoulli::new( 0.5 );
+    let heads : bool ~ Bernoulli::new( if biased { 0.9 } else { 0.5 } );
+    factor if biased { 0.25f64.ln() } else { 0.0 };
+
+    observe heads;
+    query biased;
+}
+
+const POSTERIOR_BIASED: f64 = 0.225 / 0.725;
+
+#[test]
+fn factor_enumeration() {
+    let model = penalised_coin::Model { heads: true };
+    let posterior = model.enumerate().unwrap();
+    let post_biased: f64 = posterior
+        .samples
+        .iter()
+        .filter(|s| s.sample.biased)
+        .map(|s| s.probability)
+        .sum();
+    assert!((post_biased - POSTERIOR_BIASED).abs() < 1e-12);
+    assert!((posterior.log_evidence - (0.5f64 * 0.725).ln()).abs() < 1e-12);
+}
+
+#[test]
+fn factor_rejection_sampling() {
+    let model = penalised_coin::Model { heads: true };
+    let num_samples = 20_000;
+    let num_biased = model
+        .sample_iter()
+        .take(num_samples)
+        .filter(|s| s.biased)
+        .count();
+    let post_biased = num_biased as f64 / num_samples as f64;
+    println!("rejection posterior biased = {:.4}", post_biased);
+    assert!((post_biased - POSTERIOR_BIASED).abs() < 0.02);
+}
+
+// --- A NaN factor gives a world zero weight ---
+
+make_model! {
+    mod nan_factor;
+    use ferric::distributions::Normal;
+
+    let x : f64 ~ Normal::new( 0.0, 1.0 );
+    factor x.ln();
+    query x;
+}
+
+#[test]
+fn nan_factor_is_invalid() {
+    let model = nan_factor::Model {};
+    let mut worlds = model.weighted_sample_iter();
+    // ln(x) is only defined for x >= 0
+    assert!(worlds.by_ref().take(1000).all(|ws| ws.sample.x >= 0.0));
+    let diagnostics = worlds.diagnostics();
+    assert!(diagnostics.num_invalid() > 0);
+    assert!(diagnostics.messages()[0].contains("is NaN"));
+}
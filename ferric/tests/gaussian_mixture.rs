@@ -0,0 +1,62 @@
+// Copyright 2022 The Ferric AI Project Developers
+use ferric::make_model;
+
+// --- A Gaussian mixture likelihood as a single `~` statement ---
+//
+//   mu   ~ Normal(0, 3)
+//   y[i] ~ 0.7 Normal(mu, 1) + 0.3 Normal(mu + 4, 1),  i in 0..5
+// Observations: y = [0.2, -0.5, 4.1, 1.0, 3.6]
+//
+// Each observation marginalises over its component, so no per-point
+// assignment has to be sampled.  The posterior mean of `mu` is computed by
+// quadrature below.
+
+make_model! {
+    mod shifted_mixture;
+    use ferric::distributions::Mixture;
+    use ferric::distributions::Normal;
+
+    let num_points : usize = 5;
+    let mu : f64 ~ Normal::new( 0.0, 3.0 );
+    let y[i in 0..num_points] : f64 ~ [Normal::new( mu, 1.0 ), Normal::new( mu + 4.0, 1.0 )]
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .and_then(|components| Mixture::new(vec![0.7, 0.3], components));
+
+    observe y;
+    query mu;
+}
+
+const DATA: [f64; 5] = [0.2, -0.5, 4.1, 1.0, 3.6];
+
+fn posterior_mean_by_quadrature() -> f64 {
+    let normal =
+        |x: f64, mean: f64, std_dev: f64| (-0.5 * ((x - mean) / std_dev).powi(2)).exp() / std_dev;
+    let (mut total, mut weighted) = (0.0, 0.0);
+    for step in 0..20_000 {
+        let mu = -10.0 + step as f64 * 1e-3;
+        let density = normal(mu, 0.0, 3.0)
+            * DATA
+                .iter()
+                .map(|&y| 0.7 * normal(y, mu, 1.0) + 0.3 * normal(y, mu + 4.0, 1.0))
+                .product::<f64>();
+        total += density;
+        weighted += mu * density;
+    }
+    weighted / total
+}
+
+#[test]
+fn gaussian_mixture_likelihood() {
+    let model = shifted_mixture::Model { y: DATA.to_vec() };
+    let mut mu_vals = Vec::new();
+    let mut log_weights = Vec::new();
+    for ws in model.weighted_sample_iter().take(100_000) {
+        mu_vals.push(ws.sample.mu);
+        log_weights.push(ws.log_weight);
+    }
+    let post_mean = ferric::weighted_mean(&mu_vals, &log_weights);
+    let expected = posterior_mean_by_quadrature();
+    println!("posterior mu = {:.4}, expected {:.4}", post_mean, expected);
+    assert!((post_mean - expected).abs() < 0.05);
+}
@@ -0,0 +1,195 @@
+// Copyright 2022 The Ferric AI Project Developers
+use ferric::make_model;
+
+// A world under which a distribution cannot be constructed has probability
+// zero.  Samplers discard it and count it in their diagnostics instead of
+// panicking.
+//
+// --- A scale that can come out negative ---
+//
+//   scale ~ Normal(0, 1)
+//   y     ~ Normal(0, scale)      invalid when scale <= 0
+//
+// Conditioning on the valid worlds gives the same posterior of `scale` as a
+// HalfNormal(1) prior, whose density is twice as large on scale > 0, so
+//
+//   log p(y) = log p_half_normal(y) - ln 2.
+
+make_model! {
+    mod signed_scale;
+    use ferric::distributions::Normal;
+
+    let scale : f64 ~ Normal::new( 0.0, 1.0 );
+    let y : f64 ~ Normal::new( 0.0, scale );
+
+    observe y;
+    query scale;
+}
+
+make_model! {
+    mod positive_scale;
+    use ferric::distributions::HalfNormal;
+    use ferric::distributions::Normal;
+
+    let scale : f64 ~ HalfNormal::new( 1.0 );
+    let y : f64 ~ Normal::new( 0.0, scale );
+
+    observe y;
+    query scale;
+}
+
+#[test]
+fn weighted_sampling_discards_invalid_worlds() {
+    let model = signed_scale::Model { y: 0.8 };
+    let mut worlds = model.weighted_sample_iter();
+    let samples: Vec<_> = worlds.by_ref().take(10_000).collect();
+    assert!(samples.iter().all(|ws| ws.sample.scale > 0.0));
+
+    // about as many draws were discarded as kept
+    let diagnostics = worlds.diagnostics();
+    println!("discarded {} draws", diagnostics.num_invalid());
+    assert!(diagnostics.num_invalid() > 9_000 && diagnostics.num_invalid() < 11_000);
+    assert_eq!(diagnostics.messages().len(), 10);
+    assert!(
+        diagnostics.messages()[0]
+            .starts_with("invalid distribution of `y`: Normal: illegal std_dev")
+    );
+}
+
+#[test]
+fn log_evidence_counts_invalid_worlds() {
+    let signed = signed_scale::Model { y: 0.8 }.log_evidence(200_000);
+    let positive = positive_scale::Model { y: 0.8 }.log_evidence(200_000);
+    println!("signed {:?} positive {:?}", signed, positive);
+    assert!((positive.estimate - signed.estimate - 2.0_f64.ln()).abs() < 0.02);
+}
+
+#[test]
+fn markov_chains_reject_invalid_worlds() {
+    let model = signed_scale::Model { y: 0.8 };
+    let mut chain = model.mh_sample_iter();
+    let scales: Vec<f64> = chain.by_ref().take(5_000).map(|s| s.scale).collect();
+    assert!(scales.iter().all(|&scale| scale > 0.0));
+    assert!(chain.diagnostics().num_invalid() > 0);
+
+    let mut chain = model.nuts_sample_iter();
+    let scales: Vec<f64> = chain.by_ref().take(500).map(|s| s.scale).collect();
+    assert!(scales.iter().all(|&scale| scale > 0.0));
+}
+
+#[test]
+fn smc_gives_invalid_particles_zero_weight() {
+    let model = signed_scale::Model { y: 0.8 };
+    let result = model.smc(1_000);
+    assert!(result.diagnostics.num_invalid() > 0);
+    assert!(result.samples.iter().all(|ws| ws.sample.scale > 0.0));
+}
+
+// --- A probability that can exceed one ---
+//
+//   k ~ DiscreteUniform(0, 3)
+//   x ~ Bernoulli(k / 2)      invalid when k = 3
+//
+// Given x = true the valid worlds k = 0, 1, 2 have weights 0, 1/2, 1, so
+// P(k = 2 | x) = 2/3 and p(x) = (0 + 1/2 + 1) / 4 = 0.375.
+
+make_model! {
+    mod overflowing_probability;
+    use ferric::distributions::Bernoulli;
+    use ferric::distributions::DiscreteUniform;
+
+    let k : i64 ~ DiscreteUniform::new( 0, 3 );
+    let x : bool ~ Bernoulli::new( k as f64 / 2.0 );
+
+    observe x;
+    query k;
+}
+
+#[test]
+fn enumeration_skips_invalid_assignments() {
+    let model = overflowing_probability::Model { x: true };
+    let posterior = model.enumerate().unwrap();
+    assert_eq!(posterior.diagnostics.num_invalid(), 1);
+    assert!(posterior.diagnostics.messages()[0].starts_with("invalid distribution of `x`"));
+    let p_two: f64 = posterior
+        .samples
+        .iter()
+        .filter(|s| s.sample.k == 2)
+        .map(|s| s.probability)
+        .sum();
+    assert!((p_two - 2.0 / 3.0).abs() < 1e-12);
+    assert!((posterior.log_evidence - 0.375_f64.ln()).abs() < 1e-12);
+}
+
+#[test]
+fn rejection_sampling_rejects_invalid_worlds() {
+    let model = overflowing_probability::Model { x: true };
+    let mut world = model.sample_iter();
+    let num_two = world.by_ref().take(30_000).filter(|s| s.k == 2).count();
+    let p_two = num_two as f64 / 30_000.0;
+    assert!((p_two - 2.0 / 3.0).abs() < 0.02);
+    assert!(world.diagnostics().num_invalid() > 0);
+}
+
+// --- A model without any valid world ---
+//
+// Every draw of `x` is invalid, so the samplers stop after
+// `Diagnostics::MAX_CONSECUTIVE_INVALID` draws in a row instead of retrying
+// forever, and their diagnostics tell why.
+
+make_model! {
+    mod never_valid;
+    use ferric::distributions::Normal;
+
+    let x : f64 ~ Normal::new( 0.0, -1.0 );
+    let y : f64 ~ Normal::new( x, 1.0 );
+
+    observe y;
+    query x;
+}
+
+fn assert_stopped(diagnostics: &ferric::Diagnostics) {
+    assert!(diagnostics.stopped());
+    assert_eq!(
+        diagnostics.num_invalid(),
+        ferric::Diagnostics::MAX_CONSECUTIVE_INVALID
+    );
+    assert!(
+        diagnostics.messages()[0]
+            .starts_with("invalid distribution of `x`: Normal: illegal std_dev")
+    );
+}
+
+#[test]
+fn rejection_sampling_terminates_without_valid_worlds() {
+    let mut worlds = never_valid::Model { y: 0.5 }.sample_iter();
+    assert!(worlds.next().is_none());
+    assert_stopped(worlds.diagnostics());
+    // a stopped sampler stays stopped
+    assert!(worlds.next().is_none());
+    assert_eq!(
+        worlds.diagnostics().num_invalid(),
+        ferric::Diagnostics::MAX_CONSECUTIVE_INVALID
+    );
+}
+
+#[test]
+fn weighted_sampling_terminates_without_valid_worlds() {
+    let mut worlds = never_valid::Model { y: 0.5 }.weighted_sample_iter();
+    assert!(worlds.next().is_none());
+    assert_stopped(worlds.diagnostics());
+}
+
+#[test]
+fn metropolis_hastings_terminates_without_valid_worlds() {
+    let mut chain = never_valid::Model { y: 0.5 }.mh_sample_iter();
+    assert!(chain.next().is_none());
+    assert_stopped(chain.diagnostics());
+}
+
+#[test]
+fn nuts_terminates_without_valid_worlds() {
+    let mut chain = never_valid::Model { y: 0.5 }.nuts_sample_iter();
+    assert!(chain.next().is_none());
+    assert_stopped(chain.diagnostics());
+}
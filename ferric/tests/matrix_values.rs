@@ -0,0 +1,74 @@
+// Copyright 2022 The Ferric AI Project Developers
+use ferric::make_model;
+use nalgebra::DMatrix;
+
+// Dependency expressions read vector and matrix values in place, so children
+// can index a matrix or call its methods without copying it.
+//
+// --- Summaries of a Wishart draw ---
+//
+//   sigma  ~ Wishart(4, V)
+//   total  = tr(sigma)
+//   corner = sigma[0, 1]
+//
+// E[sigma] = 4 V, so E[total] = 4 tr(V) = 12 and E[corner] = 4 V[0, 1] = 2.
+
+make_model! {
+    mod wishart_summaries;
+    use ferric::distributions::Wishart;
+    use nalgebra::DMatrix;
+
+    param scale : DMatrix<f64>;
+    let sigma : DMatrix<f64> ~ Wishart::new( 4.0, scale.clone() );
+    let total : f64 = sigma.trace();
+    let corner : f64 = sigma[(0, 1)];
+
+    query total;
+    query corner;
+}
+
+#[test]
+fn matrix_read_in_place() {
+    let model = wishart_summaries::Model {
+        scale: DMatrix::from_row_slice(2, 2, &[2.0, 0.5, 0.5, 1.0]),
+    };
+    let num_samples = 20_000;
+    let (mut total, mut corner) = (0.0, 0.0);
+    for sample in model.with_seed(7).sample_iter().take(num_samples) {
+        total += sample.total;
+        corner += sample.corner;
+    }
+    let total = total / num_samples as f64;
+    let corner = corner / num_samples as f64;
+    // standard errors ≈ 0.05 and 0.02
+    assert!((total - 12.0).abs() < 0.25, "mean total {}", total);
+    assert!((corner - 2.0).abs() < 0.1, "mean corner {}", corner);
+}
+
+// --- Indexing a data vector per element of a plate ---
+//
+//   beta  ~ Normal(0, 10)
+//   y[i]  ~ Normal(beta * x[i], 1)
+
+make_model! {
+    mod slope;
+    use ferric::distributions::Normal;
+
+    data x : Vec<f64>;
+    let beta : f64 ~ Normal::new( 0.0, 10.0 );
+    let y[i in 0..x.len()] : f64 ~ Normal::new( beta * x[i], 1.0 );
+
+    observe y;
+    query beta;
+}
+
+#[test]
+fn eval_returns_reference() {
+    let x = vec![1.0, 2.0, 3.0];
+    let mut world = slope::World::new(rand::thread_rng(), vec![2.0, 4.1, 5.9], x.clone());
+    assert_eq!(world.eval_x().unwrap(), &x);
+    let beta = *world.eval_beta().unwrap();
+    assert_eq!(world.eval_y().unwrap().len(), 3);
+    // a known value is handed out again rather than redrawn
+    assert_eq!(*world.eval_beta().unwrap(), beta);
+}
@@ -0,0 +1,131 @@
+// Copyright 2022 The Ferric AI Project Developers
+use ferric::make_model;
+
+// --- Metropolis-Hastings on a continuous Normal-Normal conjugate model ---
+//
+// Same model as the sensor_fusion test:
+//   true_mean ~ Normal(0.0, 3.0)
+//   sensor_i  ~ Normal(true_mean, 1.0)
+// Observations: sensor_1 = 1.5, sensor_2 = 2.5
+//
+// Analytical posterior: mean = 36/19 ≈ 1.895, std = sqrt(9/19) ≈ 0.688
+
+#[test]
+fn mh_sensor_fusion() {
+    make_model! {
+        mod mh_sensor_fusion;
+        use ferric::distributions::Normal;
+
+        let true_mean : f64 ~ Normal::new( 0.0, 3.0 );
+        let sensor_1 : f64 ~ Normal::new( true_mean, 1.0 );
+        let sensor_2 : f64 ~ Normal::new( true_mean, 1.0 );
+
+        observe sensor_1;
+        observe sensor_2;
+        query true_mean;
+    };
+
+    let model = mh_sensor_fusion::Model {
+        sensor_1: 1.5,
+        sensor_2: 2.5,
+    };
+    let num_samples = 50000;
+    let mean_vals: Vec<f64> = model
+        .mh_sample_iter()
+        .skip(1000)
+        .take(num_samples)
+        .map(|s| s.true_mean)
+        .collect();
+    let log_weights = vec![0.0; num_samples];
+
+    let post_mean = ferric::weighted_mean(&mean_vals, &log_weights);
+    let post_std = ferric::weighted_std(&mean_vals, &log_weights);
+    println!("mh posterior mean = {:.4} std = {:.4}", post_mean, post_std);
+
+    let mean_ans = 36.0_f64 / 19.0;
+    let std_ans = (9.0_f64 / 19.0).sqrt();
+    assert!(
+        (post_mean - mean_ans).abs() < 0.1,
+        "posterior mean {:.4} not close to analytical {:.4}",
+        post_mean,
+        mean_ans
+    );
+    assert!(
+        (post_std - std_ans).abs() < 0.1,
+        "posterior std {:.4} not close to analytical {:.4}",
+        post_std,
+        std_ans
+    );
+}
+
+// --- Metropolis-Hastings on a discrete Bayesian network ---
+//
+// The grass model from Wikipedia: P( Rain | Grass Is Wet ) = .3577
+// `sprinkler` is not queried but is still updated by the chain, and the
+// deterministic `either` variable must track the current latent state.
+
+#[test]
+fn mh_grass() {
+    make_model! {
+        mod mh_grass;
+        use ferric::distributions::Bernoulli;
+
+        let rain : bool ~ Bernoulli::new( 0.2 );
+
+        let sprinkler : bool ~
+            if rain {
+                Bernoulli::new( 0.01 )
+            } else {
+                Bernoulli::new( 0.4 )
+            };
+
+        let either : bool = rain || sprinkler;
+
+        let grass_wet : bool ~ Bernoulli::new(
+            if sprinkler && rain { 0.99 }
+            else if sprinkler && !rain { 0.9 }
+            else if !sprinkler && rain { 0.8 }
+            else { 0.0 }
+        );
+
+        query rain;
+        query either;
+        observe grass_wet;
+    };
+
+    let model = mh_grass::Model { grass_wet: true };
+    // Moving between the rain-only and sprinkler-only modes requires passing
+    // through the unlikely rain-and-sprinkler state, so successive draws are
+    // strongly correlated and the binomial standard error of their mean would
+    // be far too optimistic.  The tolerance is instead estimated from the
+    // spread of the means of batches much longer than the autocorrelation time.
+    let num_batches = 100;
+    let batch_len = 10_000;
+    let mut draws = model.mh_sample_iter().skip(1000);
+    let mut batch_means = Vec::with_capacity(num_batches);
+    for _ in 0..num_batches {
+        let mut num_rain = 0;
+        for s in draws.by_ref().take(batch_len) {
+            // grass can only be wet if it rained or the sprinkler was on
+            assert!(s.either);
+            if s.rain {
+                num_rain += 1;
+            }
+        }
+        batch_means.push(num_rain as f64 / batch_len as f64);
+    }
+    let post_rain = batch_means.iter().sum::<f64>() / num_batches as f64;
+    let batch_var = batch_means
+        .iter()
+        .map(|m| (m - post_rain).powi(2))
+        .sum::<f64>()
+        / (num_batches - 1) as f64;
+    let std_err = (batch_var / num_batches as f64).sqrt();
+    println!("mh post_rain = {} +- {}", post_rain, std_err);
+    assert!(
+        (post_rain - 0.3577).abs() < 5.0 * std_err,
+        "mh post_rain {} not within 5 standard errors ({}) of 0.3577",
+        post_rain,
+        std_err
+    );
+}
@@ -0,0 +1,187 @@
+// Copyright 2022 The Ferric AI Project Developers
+use ferric::make_model;
+use ferric::{FeOption, Known, Null, Unknown};
+
+// --- A sensor with intermittent dropouts ---
+//
+//   level     ~ Normal(0, 2)
+//   reading[t] ~ Normal(level, 0.5),  t in 0..5
+//   backup     ~ Normal(level, 0.5)
+// Observations: reading = [1.2, -, 0.8, -, 1.0], backup missing
+//
+// Only the three known readings inform `level`, so its posterior is
+// Normal(12 / 12.25, 1 / sqrt(12.25)) with mean about 0.9796.  Each missing
+// reading is imputed from its posterior predictive distribution, whose mean is
+// the posterior mean of `level`.
+
+make_model! {
+    mod dropouts;
+    use ferric::distributions::Normal;
+
+    let num_readings : usize = 5;
+    let level : f64 ~ Normal::new( 0.0, 2.0 );
+    let reading[t in 0..num_readings] : f64 ~ Normal::new( level, 0.5 );
+    let backup : f64 ~ Normal::new( level, 0.5 );
+
+    observe reading?;
+    observe backup?;
+    query level;
+    query reading;
+    query backup;
+}
+
+const POSTERIOR_MEAN: f64 = 12.0 / 12.25;
+
+fn readings() -> Vec<FeOption<f64>> {
+    vec![Known(1.2), Null, Known(0.8), Unknown, Known(1.0)]
+}
+
+#[test]
+fn missing_readings_weighted_sampling() {
+    let model = dropouts::Model {
+        reading: readings(),
+        backup: Unknown,
+    };
+    let num_samples = 100_000;
+    let mut levels = Vec::with_capacity(num_samples);
+    let mut imputed = Vec::with_capacity(num_samples);
+    let mut log_weights = Vec::with_capacity(num_samples);
+    for ws in model.weighted_sample_iter().take(num_samples) {
+        // known readings are conditioned on, not resampled
+        assert_eq!(ws.sample.reading[0], 1.2);
+        assert_eq!(ws.sample.reading[4], 1.0);
+        levels.push(ws.sample.level);
+        imputed.push(ws.sample.reading[1]);
+        log_weights.push(ws.log_weight);
+    }
+    let level = ferric::weighted_mean(&levels, &log_weights);
+    let reading = ferric::weighted_mean(&imputed, &log_weights);
+    println!(
+        "posterior level = {:.4}, imputed reading = {:.4}",
+        level, reading
+    );
+    assert!((level - POSTERIOR_MEAN).abs() < 0.02);
+    assert!((reading - POSTERIOR_MEAN).abs() < 0.05);
+}
+
+#[test]
+fn missing_readings_metropolis_hastings() {
+    let model = dropouts::Model {
+        reading: readings(),
+        backup: Unknown,
+    };
+    let samples: Vec<dropouts::Sample> = model.mh_sample_iter().skip(2000).take(40_000).collect();
+    assert!(samples.iter().all(|s| s.reading[2] == 0.8));
+    let level = samples.iter().map(|s| s.level).sum::<f64>() / samples.len() as f64;
+    let backup = samples.iter().map(|s| s.backup).sum::<f64>() / samples.len() as f64;
+    println!(
+        "mh posterior level = {:.4}, imputed backup = {:.4}",
+        level, backup
+    );
+    assert!((level - POSTERIOR_MEAN).abs() < 0.05);
+    assert!((backup - POSTERIOR_MEAN).abs() < 0.1);
+}
+
+#[test]
+fn missing_readings_nuts() {
+    // a known backup reading is conditioned on like any other observation:
+    // the posterior mean becomes 4 * 3.9 / 16.25
+    let model = dropouts::Model {
+        reading: readings(),
+        backup: Known(0.9),
+    };
+    let samples: Vec<dropouts::Sample> = model.nuts_sample_iter().skip(1000).take(4000).collect();
+    assert!(
+        samples
+            .iter()
+            .all(|s| s.backup == 0.9 && s.reading[0] == 1.2)
+    );
+    let level = samples.iter().map(|s| s.level).sum::<f64>() / samples.len() as f64;
+    let imputed = samples.iter().map(|s| s.reading[3]).sum::<f64>() / samples.len() as f64;
+    println!(
+        "nuts posterior level = {:.4}, imputed reading = {:.4}",
+        level, imputed
+    );
+    assert!((level - 15.6 / 16.25).abs() < 0.05);
+    assert!((imputed - 15.6 / 16.25).abs() < 0.1);
+}
+
+#[test]
+fn missing_readings_from_options() {
+    let options = [Some(1.2), None, Some(0.8), None, Some(1.0)];
+    let model = dropouts::Model {
+        reading: options.into_iter().map(FeOption::from).collect(),
+        backup: None.into(),
+    };
+    let result = model.smc(20_000);
+    let levels: Vec<f64> = result.samples.iter().map(|ws| ws.sample.level).collect();
+    let log_weights: Vec<f64> = result.samples.iter().map(|ws| ws.log_weight).collect();
+    let level = ferric::weighted_mean(&levels, &log_weights);
+    println!("smc posterior level = {:.4}", level);
+    assert!((level - POSTERIOR_MEAN).abs() < 0.05);
+}
+
+// --- Missing coin flips under exact inference ---
+//
+//   fair    ~ Bernoulli(0.5)
+//   flip[i] ~ Bernoulli(if fair { 0.5 } else { 0.9 }),  i in 0..3
+// Observations: flip = [true, -, true]
+//
+//   P( fair | flips ) = 0.5 · 0.25 / (0.5 · 0.25 + 0.5 · 0.81) = 0.125 / 0.53
+//
+// and the missing flip is heads with probability 0.5 P(fair) + 0.9 P(biased).
+
+make_model! {
+    mod missing_flips;
+    use ferric::distributions::Bernoulli;
+
+    let fair : bool ~ Bernoulli::new( 0.5 );
+    let flip[i in 0..3] : bool ~ Bernoulli::new( if fair { 0.5 } else { 0.9 } );
+
+    observe flip?;
+    query fair;
+    query flip;
+}
+
+const POSTERIOR_FAIR: f64 = 0.125 / 0.53;
+
+#[test]
+fn missing_flips_enumeration() {
+    let model = missing_flips::Model {
+        flip: vec![Known(true), Null, Known(true)],
+    };
+    let posterior = model.enumerate().unwrap();
+    // both values of the fairness and of the missing flip
+    assert_eq!(posterior.samples.len(), 4);
+    let mut post_fair = 0.0;
+    let mut post_heads = 0.0;
+    for s in posterior.samples.iter() {
+        assert!(s.sample.flip[0] && s.sample.flip[2]);
+        if s.sample.fair {
+            post_fair += s.probability;
+        }
+        if s.sample.flip[1] {
+            post_heads += s.probability;
+        }
+    }
+    assert!((post_fair - POSTERIOR_FAIR).abs() < 1e-12);
+    let expected_heads = 0.5 * POSTERIOR_FAIR + 0.9 * (1.0 - POSTERIOR_FAIR);
+    assert!((post_heads - expected_heads).abs() < 1e-12);
+    assert!((posterior.log_evidence - 0.53f64.ln()).abs() < 1e-12);
+}
+
+#[test]
+fn missing_flips_rejection_sampling() {
+    let model = missing_flips::Model {
+        flip: vec![Known(true), Unknown, Known(true)],
+    };
+    let num_samples = 20_000;
+    let num_fair = model
+        .sample_iter()
+        .take(num_samples)
+        .filter(|s| s.fair)
+        .count();
+    let post_fair = num_fair as f64 / num_samples as f64;
+    println!("rejection posterior fair = {:.4}", post_fair);
+    assert!((post_fair - POSTERIOR_FAIR).abs() < 0.02);
+}
@@ -0,0 +1,85 @@
+// Copyright 2022 The Ferric AI Project Developers
+use ferric::make_model;
+
+// Two competing explanations of an observed network congestion:
+//
+//   num_packets ~ Poisson(rate),  rate = 5 (quiet) or 10 (busy)
+//   congested   ~ Bernoulli(0.9 if num_packets > 8 else 0.1)
+//   congested   = true
+//
+// The exact evidence of each model is
+//   p(congested) = 0.1 + 0.8 · P(num_packets > 8)
+// which is ≈ 0.1545 for the quiet network and ≈ 0.6337 for the busy one,
+// a log Bayes factor of ≈ 1.41 in favour of the busy network.
+
+make_model! {
+    mod quiet_network;
+    use ferric::distributions::Bernoulli;
+    use ferric::distributions::Poisson;
+
+    let num_packets : u64 ~ Poisson::new( 5.0 );
+    let congested : bool ~ Bernoulli::new(
+        if num_packets > 8 { 0.9 } else { 0.1 }
+    );
+
+    observe congested;
+    query num_packets;
+}
+
+make_model! {
+    mod busy_network;
+    use ferric::distributions::Bernoulli;
+    use ferric::distributions::Poisson;
+
+    let num_packets : u64 ~ Poisson::new( 10.0 );
+    let congested : bool ~ Bernoulli::new(
+        if num_packets > 8 { 0.9 } else { 0.1 }
+    );
+
+    observe congested;
+    query num_packets;
+}
+
+fn exact_evidence(rate: f64) -> f64 {
+    // P(num_packets <= 8) by summing the Poisson PMF
+    let mut pmf = (-rate).exp();
+    let mut cdf = pmf;
+    for k in 1..=8 {
+        pmf *= rate / k as f64;
+        cdf += pmf;
+    }
+    0.1 + 0.8 * (1.0 - cdf)
+}
+
+#[test]
+fn log_evidence_and_bayes_factor() {
+    let num_samples = 100_000;
+    let quiet = quiet_network::Model { congested: true }.log_evidence(num_samples);
+    let busy = busy_network::Model { congested: true }.log_evidence(num_samples);
+    let exact_quiet = exact_evidence(5.0).ln();
+    let exact_busy = exact_evidence(10.0).ln();
+    println!(
+        "log evidence: quiet = {:.4} ± {:.4} (exact {:.4}), busy = {:.4} ± {:.4} (exact {:.4})",
+        quiet.estimate, quiet.std_error, exact_quiet, busy.estimate, busy.std_error, exact_busy
+    );
+
+    assert!(quiet.std_error > 0.0 && quiet.std_error < 0.02);
+    assert!(busy.std_error > 0.0 && busy.std_error < 0.02);
+    assert!((quiet.estimate - exact_quiet).abs() < 5.0 * quiet.std_error);
+    assert!((busy.estimate - exact_busy).abs() < 5.0 * busy.std_error);
+
+    let log_bayes_factor = busy.estimate - quiet.estimate;
+    assert!((log_bayes_factor - (exact_busy - exact_quiet)).abs() < 0.05);
+}
+
+#[test]
+fn log_evidence_matches_weighted_samples() {
+    let model = quiet_network::Model { congested: true };
+    let log_weights: Vec<f64> = model
+        .weighted_sample_iter()
+        .take(50_000)
+        .map(|ws| ws.log_weight)
+        .collect();
+    let evidence = ferric::log_evidence(&log_weights);
+    assert!((evidence.estimate - exact_evidence(5.0).ln()).abs() < 5.0 * evidence.std_error);
+}
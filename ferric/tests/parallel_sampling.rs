@@ -0,0 +1,111 @@
+// Copyright 2022 The Ferric AI Project Developers
+use ferric::make_model;
+use rand::rngs::StdRng;
+
+// Parallel samplers split the draws between threads, each running its own
+// world, so they must agree with the single-threaded answers:
+//
+// grass:          P( Rain | Grass Is Wet ) = .3577
+// sensor fusion:  posterior mean = 36/19 ≈ 1.895, std = sqrt(9/19) ≈ 0.688
+
+make_model! {
+    mod par_grass;
+    use ferric::distributions::Bernoulli;
+
+    let rain : bool ~ Bernoulli::new( 0.2 );
+    let sprinkler : bool ~
+        if rain {
+            Bernoulli::new( 0.01 )
+        } else {
+            Bernoulli::new( 0.4 )
+        };
+    let grass_wet : bool ~ Bernoulli::new(
+        if sprinkler && rain { 0.99 }
+        else if sprinkler && !rain { 0.9 }
+        else if !sprinkler && rain { 0.8 }
+        else { 0.0 }
+    );
+
+    observe grass_wet;
+    query rain;
+}
+
+make_model! {
+    mod par_sensor_fusion;
+    use ferric::distributions::Normal;
+
+    let true_mean : f64 ~ Normal::new( 0.0, 3.0 );
+    let sensor_1 : f64 ~ Normal::new( true_mean, 1.0 );
+    let sensor_2 : f64 ~ Normal::new( true_mean, 1.0 );
+
+    observe sensor_1;
+    observe sensor_2;
+    query true_mean;
+}
+
+fn assert_send<T: Send>() {}
+
+#[test]
+fn worlds_can_move_between_threads() {
+    assert_send::<par_grass::World<StdRng>>();
+    assert_send::<par_sensor_fusion::WeightedWorld<StdRng>>();
+}
+
+#[test]
+fn par_sample_grass() {
+    let model = par_grass::Model { grass_wet: true };
+    let samples = model.par_sample(200_000, 4);
+    assert_eq!(samples.len(), 200_000);
+    let post_rain = samples.iter().filter(|s| s.rain).count() as f64 / samples.len() as f64;
+    println!("parallel post_rain = {}", post_rain);
+    assert!((post_rain - 0.3577).abs() < 0.01);
+}
+
+#[test]
+fn par_weighted_sample_sensor_fusion() {
+    let model = par_sensor_fusion::Model {
+        sensor_1: 1.5,
+        sensor_2: 2.5,
+    };
+    let samples = model.par_weighted_sample(100_001, 3);
+    assert_eq!(samples.len(), 100_001);
+    let values: Vec<f64> = samples.iter().map(|ws| ws.sample.true_mean).collect();
+    let log_weights: Vec<f64> = samples.iter().map(|ws| ws.log_weight).collect();
+    let post_mean = ferric::weighted_mean(&values, &log_weights);
+    let post_std = ferric::weighted_std(&values, &log_weights);
+    println!(
+        "parallel posterior mean = {:.4} std = {:.4}",
+        post_mean, post_std
+    );
+    assert!((post_mean - 36.0 / 19.0).abs() < 0.05);
+    assert!((post_std - (9.0_f64 / 19.0).sqrt()).abs() < 0.05);
+}
+
+#[test]
+fn par_sampling_is_reproducible() {
+    let model = par_sensor_fusion::Model {
+        sensor_1: 1.5,
+        sensor_2: 2.5,
+    };
+    let draws = |seed: u64| -> Vec<f64> {
+        model
+            .with_seed(seed)
+            .par_weighted_sample(1000, 4)
+            .iter()
+            .map(|ws| ws.sample.true_mean)
+            .collect()
+    };
+    assert_eq!(draws(11), draws(11));
+    assert_ne!(draws(11), draws(12));
+
+    let grass = par_grass::Model { grass_wet: true };
+    let rain = |seed: u64| -> Vec<bool> {
+        grass
+            .with_seed(seed)
+            .par_sample(1000, 4)
+            .iter()
+            .map(|s| s.rain)
+            .collect()
+    };
+    assert_eq!(rain(11), rain(11));
+}
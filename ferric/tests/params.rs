@@ -0,0 +1,140 @@
+// Copyright 2022 The Ferric AI Project Developers
+use ferric::make_model;
+
+// Parameters and data are fields of the model, so one definition serves many
+// datasets.
+//
+// --- Coin flips: the number of trials is a parameter ---
+//
+//   p     ~ Beta(a, b)
+//   heads ~ Binomial(num_trials, p)
+//
+// Posterior p ~ Beta(a + heads, b + num_trials - heads), with mean
+// (a + heads) / (a + b + num_trials).
+
+make_model! {
+    mod coin;
+    use ferric::distributions::Beta;
+    use ferric::distributions::Binomial;
+
+    param prior : (f64, f64);
+    param num_trials : u64;
+    let p : f64 ~ Beta::new( prior.0, prior.1 );
+    let heads : u64 ~ Binomial::new( num_trials, p );
+
+    observe heads;
+    query p;
+}
+
+fn posterior_mean(model: &coin::Model) -> f64 {
+    let mut p_vals = Vec::new();
+    let mut log_weights = Vec::new();
+    for ws in model.weighted_sample_iter().take(100_000) {
+        p_vals.push(ws.sample.p);
+        log_weights.push(ws.log_weight);
+    }
+    ferric::weighted_mean(&p_vals, &log_weights)
+}
+
+#[test]
+fn one_model_many_datasets() {
+    let small = coin::Model {
+        heads: 7,
+        prior: (1.0, 1.0),
+        num_trials: 10,
+    };
+    let post_mean = posterior_mean(&small);
+    println!("small posterior mean = {:.4}", post_mean);
+    assert!((post_mean - 8.0 / 12.0).abs() < 0.01);
+
+    let large = coin::Model {
+        heads: 70,
+        prior: (2.0, 2.0),
+        num_trials: 100,
+    };
+    let post_mean = posterior_mean(&large);
+    println!("large posterior mean = {:.4}", post_mean);
+    assert!((post_mean - 72.0 / 104.0).abs() < 0.01);
+}
+
+// --- Linear regression: the design is data ---
+//
+//   beta ~ Normal(0, 10)
+//   y[i] ~ Normal(beta * x[i], 1),  i in 0..x.len()
+//
+// Posterior precision 1/100 + Σ x², mean Σ x y / precision.
+
+make_model! {
+    mod regression;
+    use ferric::distributions::Normal;
+
+    data x : Vec<f64>;
+    let beta : f64 ~ Normal::new( 0.0, 10.0 );
+    let y[i in 0..x.len()] : f64 ~ Normal::new( beta * x[i], 1.0 );
+
+    observe y;
+    query beta;
+}
+
+#[test]
+fn data_in_plate() {
+    let model = regression::Model {
+        y: vec![1.1, 1.9, 3.2, 3.9],
+        x: vec![1.0, 2.0, 3.0, 4.0],
+    };
+    let precision = 0.01 + 30.0;
+    let exp_mean = (1.1 + 3.8 + 9.6 + 15.6) / precision;
+    let vals: Vec<f64> = model
+        .nuts_sample_iter()
+        .skip(1000)
+        .take(4000)
+        .map(|s| s.beta)
+        .collect();
+    let post_mean = vals.iter().sum::<f64>() / vals.len() as f64;
+    println!("nuts beta = {:.4}, expected {:.4}", post_mean, exp_mean);
+    assert!((post_mean - exp_mean).abs() < 0.05);
+}
+
+// --- Counting heads: a parameter sizes a plate ---
+//
+//   coins[i]  ~ Bernoulli(0.5),  i in 0..num_coins
+//   num_heads = number of true coins
+//
+// Given num_heads = 1 every coin is the head with probability 1 / num_coins,
+// and p(num_heads = 1) = num_coins / 2^num_coins.
+
+make_model! {
+    mod counting;
+    use ferric::distributions::Bernoulli;
+
+    param num_coins : usize;
+    let coins[i in 0..num_coins] : bool ~ Bernoulli::new( 0.5 );
+    let num_heads : usize = coins.iter().filter(|c| **c).count();
+
+    observe num_heads;
+    query coins;
+}
+
+#[test]
+fn param_sizes_plate() {
+    for num_coins in [3, 4] {
+        let model = counting::Model {
+            num_heads: 1,
+            num_coins,
+        };
+        let posterior = model.enumerate().unwrap();
+        assert_eq!(posterior.samples.len(), num_coins);
+        let p_first: f64 = posterior
+            .samples
+            .iter()
+            .filter(|s| s.sample.coins[0])
+            .map(|s| s.probability)
+            .sum();
+        assert!((p_first - 1.0 / num_coins as f64).abs() < 1e-12);
+        let exp_evidence = num_coins as f64 / 2.0_f64.powi(num_coins as i32);
+        assert!((posterior.log_evidence - exp_evidence.ln()).abs() < 1e-12);
+
+        let sample = model.sample_iter().next().unwrap();
+        assert_eq!(sample.coins.len(), num_coins);
+    }
+}
@@ -0,0 +1,198 @@
+// Copyright 2022 The Ferric AI Project Developers
+use ferric::make_model;
+
+// --- Observed plate: repeated measurements of one mean ---
+//
+//   mu   ~ Normal(0, 1)
+//   y[i] ~ Normal(mu, 1),  i in 0..4
+// Observations: y = [1.0, 2.0, 0.5, 1.5]
+//
+// Conjugate posterior: precision 1 + 4 = 5, mean = 5.0 / 5 = 1.0,
+// std = sqrt(1/5) ≈ 0.447
+
+make_model! {
+    mod repeated_measurements;
+    use ferric::distributions::Normal;
+
+    let num_measurements : usize = 4;
+    let mu : f64 ~ Normal::new( 0.0, 1.0 );
+    let y[i in 0..num_measurements] : f64 ~ Normal::new( mu, 1.0 );
+
+    observe y;
+    query mu;
+}
+
+fn mean_and_std(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let var = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    (mean, var.sqrt())
+}
+
+fn measurements() -> repeated_measurements::Model {
+    repeated_measurements::Model {
+        y: vec![1.0, 2.0, 0.5, 1.5],
+    }
+}
+
+#[test]
+fn observed_plate_weighted() {
+    let model = measurements();
+    let mut mu_vals = Vec::new();
+    let mut log_weights = Vec::new();
+    for ws in model.weighted_sample_iter().take(100_000) {
+        mu_vals.push(ws.sample.mu);
+        log_weights.push(ws.log_weight);
+    }
+    let post_mean = ferric::weighted_mean(&mu_vals, &log_weights);
+    let post_std = ferric::weighted_std(&mu_vals, &log_weights);
+    println!("weighted mu = {:.4} ± {:.4}", post_mean, post_std);
+    assert!((post_mean - 1.0).abs() < 0.05);
+    assert!((post_std - 0.2_f64.sqrt()).abs() < 0.05);
+}
+
+#[test]
+fn observed_plate_nuts() {
+    let model = measurements();
+    let vals: Vec<f64> = model
+        .nuts_sample_iter()
+        .skip(1000)
+        .take(4000)
+        .map(|s| s.mu)
+        .collect();
+    let (post_mean, post_std) = mean_and_std(&vals);
+    println!("nuts mu = {:.4} ± {:.4}", post_mean, post_std);
+    assert!((post_mean - 1.0).abs() < 0.1);
+    assert!((post_std - 0.2_f64.sqrt()).abs() < 0.1);
+}
+
+#[test]
+fn observed_plate_smc() {
+    // the whole plate is incorporated in one step
+    let model = measurements();
+    let mut filter = model.particle_filter(1000);
+    assert!(filter.step());
+    assert!(!filter.step());
+
+    let result = model.smc(20_000);
+    let mu_vals: Vec<f64> = result.samples.iter().map(|ws| ws.sample.mu).collect();
+    let log_weights: Vec<f64> = result.samples.iter().map(|ws| ws.log_weight).collect();
+    let post_mean = ferric::weighted_mean(&mu_vals, &log_weights);
+    assert!((post_mean - 1.0).abs() < 0.05);
+}
+
+#[test]
+fn observed_plate_of_wrong_length_is_impossible() {
+    let model = repeated_measurements::Model { y: vec![1.0, 2.0] };
+    for ws in model.weighted_sample_iter().take(10) {
+        assert_eq!(ws.log_weight, f64::NEG_INFINITY);
+    }
+}
+
+// --- Latent plates: one hidden state per noisy observation ---
+//
+//   state[i] ~ Bernoulli(0.5)
+//   obs[i]   ~ Bernoulli(0.9 if state[i] else 0.1),  i in 0..3
+// Observations: obs = [true, false, true]
+//
+// The states are independent a posteriori and each matches its observation
+// with probability 0.9.
+
+make_model! {
+    mod hidden_states;
+    use ferric::distributions::Bernoulli;
+
+    let state[i in 0..3] : bool ~ Bernoulli::new( 0.5 );
+    let flipped[i in 0..3] : bool = !state[i];
+    let obs[i in 0..3] : bool ~ Bernoulli::new( if state[i] { 0.9 } else { 0.1 } );
+
+    observe obs;
+    query state;
+    query flipped;
+}
+
+#[test]
+fn latent_plate_enumerate() {
+    let model = hidden_states::Model {
+        obs: vec![true, false, true],
+    };
+    let posterior = model.enumerate().unwrap();
+    assert_eq!(posterior.samples.len(), 8);
+    let mut matches = [0.0; 3];
+    for s in posterior.samples.iter() {
+        assert_eq!(s.sample.state.len(), 3);
+        for (i, matched) in matches.iter_mut().enumerate() {
+            assert_eq!(s.sample.flipped[i], !s.sample.state[i]);
+            if s.sample.state[i] == model.obs[i] {
+                *matched += s.probability;
+            }
+        }
+    }
+    for p in matches {
+        assert!((p - 0.9).abs() < 1e-12);
+    }
+    // p(obs) = 0.5³
+    assert!((posterior.log_evidence - 0.125_f64.ln()).abs() < 1e-12);
+}
+
+#[test]
+fn latent_plate_rejection_and_mh() {
+    let model = hidden_states::Model {
+        obs: vec![true, false, true],
+    };
+    let num_samples = 20_000;
+    let num_match = model
+        .sample_iter()
+        .take(num_samples)
+        .filter(|s| s.state[1] == model.obs[1])
+        .count();
+    let post_match = num_match as f64 / num_samples as f64;
+    assert!((post_match - 0.9).abs() < 0.02);
+
+    let num_match = model
+        .mh_sample_iter()
+        .skip(1000)
+        .take(num_samples)
+        .filter(|s| s.state[1] == model.obs[1])
+        .count();
+    let post_match = num_match as f64 / num_samples as f64;
+    println!("mh P(state[1] = obs[1]) = {:.4}", post_match);
+    assert!((post_match - 0.9).abs() < 0.03);
+}
+
+// --- A latent plate of reals ---
+//
+//   theta[i] ~ Normal(0, 1),  y[i] ~ Normal(theta[i], 1),  i in 0..2
+// Observations: y = [2, -2]
+//
+// Posterior theta[i] ~ Normal(y[i] / 2, 1/2).
+
+#[test]
+fn latent_plate_nuts() {
+    make_model! {
+        mod latent_reals;
+        use ferric::distributions::Normal;
+
+        let theta[i in 0..2] : f64 ~ Normal::new( 0.0, 1.0 );
+        let y[i in 0..2] : f64 ~ Normal::new( theta[i], 1.0 );
+
+        observe y;
+        query theta;
+    };
+
+    let model = latent_reals::Model { y: vec![2.0, -2.0] };
+    let samples: Vec<Vec<f64>> = model
+        .nuts_sample_iter()
+        .skip(1000)
+        .take(4000)
+        .map(|s| s.theta)
+        .collect();
+    let theta_0: Vec<f64> = samples.iter().map(|t| t[0]).collect();
+    let theta_1: Vec<f64> = samples.iter().map(|t| t[1]).collect();
+    let (mean_0, std_0) = mean_and_std(&theta_0);
+    let (mean_1, _) = mean_and_std(&theta_1);
+    println!("nuts theta = [{:.4}, {:.4}]", mean_0, mean_1);
+    assert!((mean_0 - 1.0).abs() < 0.1);
+    assert!((mean_1 + 1.0).abs() < 0.1);
+    assert!((std_0 - 0.5_f64.sqrt()).abs() < 0.1);
+}
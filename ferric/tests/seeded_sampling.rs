@@ -0,0 +1,139 @@
+// Copyright 2022 The Ferric AI Project Developers
+use ferric::make_model;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+// Every sampler draws its random numbers from a user-supplied generator, so
+// runs with the same seed are identical and runs with different seeds are
+// not.
+
+make_model! {
+    mod seeded_grass;
+    use ferric::distributions::Bernoulli;
+
+    let rain : bool ~ Bernoulli::new( 0.2 );
+    let sprinkler : bool ~
+        if rain {
+            Bernoulli::new( 0.01 )
+        } else {
+            Bernoulli::new( 0.4 )
+        };
+    let grass_wet : bool ~ Bernoulli::new(
+        if sprinkler && rain { 0.99 }
+        else if sprinkler && !rain { 0.9 }
+        else if !sprinkler && rain { 0.8 }
+        else { 0.0 }
+    );
+
+    observe grass_wet;
+    query rain;
+    query sprinkler;
+}
+
+make_model! {
+    mod seeded_sensor_fusion;
+    use ferric::distributions::Normal;
+
+    let true_mean : f64 ~ Normal::new( 0.0, 3.0 );
+    let sensor_1 : f64 ~ Normal::new( true_mean, 1.0 );
+    let sensor_2 : f64 ~ Normal::new( true_mean, 1.0 );
+
+    observe sensor_1;
+    observe sensor_2;
+    query true_mean;
+}
+
+fn rejection_draws(model: &seeded_grass::Model, seed: u64) -> Vec<(bool, bool)> {
+    model
+        .with_seed(seed)
+        .sample_iter()
+        .take(100)
+        .map(|s| (s.rain, s.sprinkler))
+        .collect()
+}
+
+#[test]
+fn rejection_sampling_is_reproducible() {
+    let model = seeded_grass::Model { grass_wet: true };
+    assert_eq!(rejection_draws(&model, 7), rejection_draws(&model, 7));
+    assert_ne!(rejection_draws(&model, 7), rejection_draws(&model, 8));
+
+    // with_seed is a shorthand for a seeded StdRng
+    let explicit: Vec<(bool, bool)> = model
+        .sample_iter_with_rng(StdRng::seed_from_u64(7))
+        .take(100)
+        .map(|s| (s.rain, s.sprinkler))
+        .collect();
+    assert_eq!(explicit, rejection_draws(&model, 7));
+}
+
+#[test]
+fn weighted_sampling_is_reproducible() {
+    let model = seeded_sensor_fusion::Model {
+        sensor_1: 1.5,
+        sensor_2: 2.5,
+    };
+    let draws = |seed: u64| -> Vec<(f64, f64)> {
+        model
+            .with_seed(seed)
+            .weighted_sample_iter()
+            .take(100)
+            .map(|ws| (ws.sample.true_mean, ws.log_weight))
+            .collect()
+    };
+    assert_eq!(draws(1), draws(1));
+    assert_ne!(draws(1), draws(2));
+    assert_eq!(
+        model.with_seed(1).log_evidence(1000),
+        model.with_seed(1).log_evidence(1000)
+    );
+}
+
+#[test]
+fn markov_chains_are_reproducible() {
+    let model = seeded_sensor_fusion::Model {
+        sensor_1: 1.5,
+        sensor_2: 2.5,
+    };
+    let mh = |seed: u64| -> Vec<f64> {
+        model
+            .with_seed(seed)
+            .mh_sample_iter()
+            .take(200)
+            .map(|s| s.true_mean)
+            .collect()
+    };
+    assert_eq!(mh(3), mh(3));
+    assert_ne!(mh(3), mh(4));
+
+    let nuts = |seed: u64| -> Vec<f64> {
+        model
+            .with_seed(seed)
+            .nuts_sample_iter()
+            .take(50)
+            .map(|s| s.true_mean)
+            .collect()
+    };
+    assert_eq!(nuts(3), nuts(3));
+    assert_ne!(nuts(3), nuts(4));
+}
+
+#[test]
+fn smc_is_reproducible() {
+    let model = seeded_sensor_fusion::Model {
+        sensor_1: 1.5,
+        sensor_2: 2.5,
+    };
+    let first = model.with_seed(5).smc(500);
+    let second = model.smc_with_rng(500, StdRng::seed_from_u64(5));
+    assert_eq!(first.log_evidence, second.log_evidence);
+    let means = |result: &seeded_sensor_fusion::SmcResult| -> Vec<f64> {
+        result
+            .samples
+            .iter()
+            .map(|ws| ws.sample.true_mean)
+            .collect()
+    };
+    assert_eq!(means(&first), means(&second));
+    assert_ne!(means(&first), means(&model.with_seed(6).smc(500)));
+}
@@ -0,0 +1,245 @@
+// Copyright 2022 The Ferric AI Project Developers
+use ferric::make_model;
+use nalgebra::{Matrix2, Vector2};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+// Gaussian random walk observed with unit noise at three time steps:
+//
+//   x_1 ~ Normal(0, 1),        y_t ~ Normal(x_t, 1)
+//   x_t ~ Normal(x_{t-1}, 1)
+//
+// Observations: y_1 = 0.5, y_2 = 1.5, y_3 = 1.0
+//
+// The Kalman filter gives the exact filtering posterior of the last state
+//   x_3 | y_1..3 ~ Normal(1.0, 0.7845²)
+// and the exact log marginal likelihood log p(y_1..3) = -4.4143.
+
+make_model! {
+    mod random_walk;
+    use ferric::distributions::Normal;
+
+    let x_1 : f64 ~ Normal::new( 0.0, 1.0 );
+    let y_1 : f64 ~ Normal::new( x_1, 1.0 );
+    let x_2 : f64 ~ Normal::new( x_1, 1.0 );
+    let y_2 : f64 ~ Normal::new( x_2, 1.0 );
+    let x_3 : f64 ~ Normal::new( x_2, 1.0 );
+    let y_3 : f64 ~ Normal::new( x_3, 1.0 );
+
+    observe y_1;
+    observe y_2;
+    observe y_3;
+    query x_3;
+}
+
+#[test]
+fn smc_random_walk() {
+    let model = random_walk::Model {
+        y_1: 0.5,
+        y_2: 1.5,
+        y_3: 1.0,
+    };
+    let result = model.smc(20_000);
+    assert_eq!(result.samples.len(), 20_000);
+
+    let mut x_3_vals = Vec::with_capacity(result.samples.len());
+    let mut log_weights = Vec::with_capacity(result.samples.len());
+    for ws in result.samples.iter() {
+        x_3_vals.push(ws.sample.x_3);
+        log_weights.push(ws.log_weight);
+    }
+    let post_mean = ferric::weighted_mean(&x_3_vals, &log_weights);
+    let post_std = ferric::weighted_std(&x_3_vals, &log_weights);
+    println!(
+        "smc x_3 = {:.4} ± {:.4}, log evidence = {:.4}",
+        post_mean, post_std, result.log_evidence
+    );
+
+    assert!((post_mean - 1.0).abs() < 0.05);
+    assert!((post_std - 0.7845).abs() < 0.05);
+    assert!((result.log_evidence - (-4.4143)).abs() < 0.05);
+}
+
+#[test]
+fn particle_filter_incremental() {
+    let model = random_walk::Model {
+        y_1: 0.5,
+        y_2: 1.5,
+        y_3: 1.0,
+    };
+    let mut filter = model.particle_filter(5_000);
+    assert_eq!(filter.num_observed(), 0);
+    assert_eq!(filter.log_evidence(), 0.0);
+
+    // after the first observation: log p(y_1) = log Normal(0.5 | 0, sqrt(2))
+    assert!(filter.step());
+    assert_eq!(filter.num_observed(), 1);
+    let exact = -0.5 * (2.0 * std::f64::consts::PI * 2.0).ln() - 0.25 * 0.25;
+    assert!((filter.log_evidence() - exact).abs() < 0.05);
+    assert!(filter.effective_sample_size() > 0.0);
+
+    assert!(filter.step());
+    assert!(filter.step());
+    assert!(!filter.step());
+    assert_eq!(filter.num_observed(), 3);
+    assert_eq!(filter.samples().len(), 5_000);
+}
+
+#[test]
+fn particle_filter_observations_arrive_over_time() {
+    // the values given to the model are placeholders for readings not taken yet
+    let model = random_walk::Model {
+        y_1: 0.0,
+        y_2: 0.0,
+        y_3: 0.0,
+    };
+    let mut filter = model.particle_filter_with_rng(20_000, StdRng::seed_from_u64(3));
+    assert!(filter.observe_y_1(0.5));
+    assert!(filter.observe_y_2(1.5));
+    // an observation is incorporated only once
+    assert!(!filter.observe_y_2(1.5));
+    assert!(filter.observe_y_3(1.0));
+    assert!(!filter.step());
+    assert_eq!(filter.num_observed(), 3);
+
+    let samples = filter.samples();
+    let x_3_vals: Vec<f64> = samples.iter().map(|ws| ws.sample.x_3).collect();
+    let log_weights: Vec<f64> = samples.iter().map(|ws| ws.log_weight).collect();
+    let post_mean = ferric::weighted_mean(&x_3_vals, &log_weights);
+    let post_std = ferric::weighted_std(&x_3_vals, &log_weights);
+    assert!((post_mean - 1.0).abs() < 0.05);
+    assert!((post_std - 0.7845).abs() < 0.05);
+    assert!((filter.log_evidence() - (-4.4143)).abs() < 0.05);
+}
+
+// --- Tracking a moving object ---
+//
+// Constant-velocity model over five time steps, with the position observed
+// with noise at each step:
+//
+//   p_1 ~ Normal(0, 1),                 v_1 ~ Normal(1, 0.5)
+//   p_t ~ Normal(p_{t-1} + v_{t-1}, 0.3), v_t ~ Normal(v_{t-1}, 0.3)
+//   y_t ~ Normal(p_t, 0.5)
+//
+// A Kalman filter gives the exact filtering mean of every state and the exact
+// log evidence of the readings seen so far.
+
+make_model! {
+    mod tracking;
+    use ferric::distributions::Normal;
+
+    let p_1 : f64 ~ Normal::new( 0.0, 1.0 );
+    let v_1 : f64 ~ Normal::new( 1.0, 0.5 );
+    let y_1 : f64 ~ Normal::new( p_1, 0.5 );
+    let p_2 : f64 ~ Normal::new( p_1 + v_1, 0.3 );
+    let v_2 : f64 ~ Normal::new( v_1, 0.3 );
+    let y_2 : f64 ~ Normal::new( p_2, 0.5 );
+    let p_3 : f64 ~ Normal::new( p_2 + v_2, 0.3 );
+    let v_3 : f64 ~ Normal::new( v_2, 0.3 );
+    let y_3 : f64 ~ Normal::new( p_3, 0.5 );
+    let p_4 : f64 ~ Normal::new( p_3 + v_3, 0.3 );
+    let v_4 : f64 ~ Normal::new( v_3, 0.3 );
+    let y_4 : f64 ~ Normal::new( p_4, 0.5 );
+    let p_5 : f64 ~ Normal::new( p_4 + v_4, 0.3 );
+    let v_5 : f64 ~ Normal::new( v_4, 0.3 );
+    let y_5 : f64 ~ Normal::new( p_5, 0.5 );
+
+    observe y_1;
+    observe y_2;
+    observe y_3;
+    observe y_4;
+    observe y_5;
+    query p_1;
+    query p_2;
+    query p_3;
+    query p_4;
+    query p_5;
+    query v_5;
+}
+
+// Filtering means of (p_t, v_t) and log p(y_1..t) after each reading.
+fn kalman_filter(readings: &[f64]) -> Vec<(Vector2<f64>, f64)> {
+    let transition = Matrix2::new(1.0, 1.0, 0.0, 1.0);
+    let process_noise = Matrix2::new(0.09, 0.0, 0.0, 0.09);
+    let reading_var = 0.25;
+    let mut mean = Vector2::new(0.0, 1.0);
+    let mut cov = Matrix2::new(1.0, 0.0, 0.0, 0.25);
+    let mut log_evidence = 0.0;
+    let mut filtered = Vec::new();
+    for (t, &y) in readings.iter().enumerate() {
+        if t > 0 {
+            mean = transition * mean;
+            cov = transition * cov * transition.transpose() + process_noise;
+        }
+        let innovation_var = cov[(0, 0)] + reading_var;
+        let innovation = y - mean[0];
+        log_evidence += -0.5 * (2.0 * std::f64::consts::PI * innovation_var).ln()
+            - 0.5 * innovation * innovation / innovation_var;
+        let gain = cov.column(0) / innovation_var;
+        mean += gain * innovation;
+        cov -= gain * cov.row(0);
+        filtered.push((mean, log_evidence));
+    }
+    filtered
+}
+
+#[test]
+fn particle_filter_tracks_a_moving_object() {
+    let readings = [0.3, 1.6, 2.4, 3.9, 4.6];
+    let exact = kalman_filter(&readings);
+
+    // the readings arrive one at a time
+    let model = tracking::Model {
+        y_1: 0.0,
+        y_2: 0.0,
+        y_3: 0.0,
+        y_4: 0.0,
+        y_5: 0.0,
+    };
+    let mut filter = model.particle_filter_with_rng(20_000, StdRng::seed_from_u64(11));
+    for (t, &reading) in readings.iter().enumerate() {
+        let incorporated = match t {
+            0 => filter.observe_y_1(reading),
+            1 => filter.observe_y_2(reading),
+            2 => filter.observe_y_3(reading),
+            3 => filter.observe_y_4(reading),
+            _ => filter.observe_y_5(reading),
+        };
+        assert!(incorporated);
+
+        let samples = filter.samples();
+        let positions: Vec<f64> = samples
+            .iter()
+            .map(|ws| match t {
+                0 => ws.sample.p_1,
+                1 => ws.sample.p_2,
+                2 => ws.sample.p_3,
+                3 => ws.sample.p_4,
+                _ => ws.sample.p_5,
+            })
+            .collect();
+        let log_weights: Vec<f64> = samples.iter().map(|ws| ws.log_weight).collect();
+        let (exact_mean, exact_log_evidence) = exact[t];
+        let post_mean = ferric::weighted_mean(&positions, &log_weights);
+        println!(
+            "t = {}: p = {:.4} (exact {:.4}), log evidence = {:.4} (exact {:.4})",
+            t + 1,
+            post_mean,
+            exact_mean[0],
+            filter.log_evidence(),
+            exact_log_evidence
+        );
+        assert!((post_mean - exact_mean[0]).abs() < 0.05);
+        assert!((filter.log_evidence() - exact_log_evidence).abs() < 0.05);
+
+        // the state of each step is drawn afresh from its predecessor, so
+        // resampling has not collapsed the current position
+        assert!(ferric::weighted_std(&positions, &log_weights) > 0.2);
+    }
+
+    let samples = filter.samples();
+    let velocities: Vec<f64> = samples.iter().map(|ws| ws.sample.v_5).collect();
+    let log_weights: Vec<f64> = samples.iter().map(|ws| ws.log_weight).collect();
+    let post_velocity = ferric::weighted_mean(&velocities, &log_weights);
+    assert!((post_velocity - exact[4].0[1]).abs() < 0.05);
+}
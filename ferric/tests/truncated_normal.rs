@@ -0,0 +1,57 @@
+// Copyright 2022 The Ferric AI Project Developers
+use ferric::make_model;
+
+// --- A non-negative quantity measured with Gaussian noise ---
+//
+//   level   ~ Normal(0, 1) truncated to [0, inf)
+//   reading ~ Normal(level, 0.5)
+// Observation: reading = -1.0
+//
+// The posterior is Normal(-0.8, 1/sqrt(5)) truncated to [0, inf), whose mean
+// is mu + sigma * phi(a) / (1 - Phi(a)) with a = -mu / sigma, about 0.1783.
+
+make_model! {
+    mod non_negative_level;
+    use ferric::distributions::Normal;
+    use ferric::distributions::Truncated;
+
+    let level : f64 ~ Normal::new( 0.0, 1.0 )
+        .and_then(|dist| Truncated::new(dist, 0.0, f64::INFINITY));
+    let reading : f64 ~ Normal::new( level, 0.5 );
+
+    observe reading;
+    query level;
+}
+
+const POSTERIOR_MEAN: f64 = 0.178_318_600_187_28;
+
+#[test]
+fn truncated_prior_weighted_sampling() {
+    let model = non_negative_level::Model { reading: -1.0 };
+    let num_samples = 100_000;
+    let mut levels = Vec::with_capacity(num_samples);
+    let mut log_weights = Vec::with_capacity(num_samples);
+    for ws in model.weighted_sample_iter().take(num_samples) {
+        assert!(ws.sample.level >= 0.0);
+        levels.push(ws.sample.level);
+        log_weights.push(ws.log_weight);
+    }
+    let mean = ferric::weighted_mean(&levels, &log_weights);
+    println!("posterior level = {:.4}", mean);
+    assert!((mean - POSTERIOR_MEAN).abs() < 0.01);
+}
+
+#[test]
+fn truncated_prior_nuts_respects_bounds() {
+    let model = non_negative_level::Model { reading: -1.0 };
+    let levels: Vec<f64> = model
+        .nuts_sample_iter()
+        .skip(500)
+        .take(4000)
+        .map(|s| s.level)
+        .collect();
+    assert!(levels.iter().all(|&level| level >= 0.0));
+    let mean = levels.iter().sum::<f64>() / levels.len() as f64;
+    println!("nuts posterior level = {:.4}", mean);
+    assert!((mean - POSTERIOR_MEAN).abs() < 0.05);
+}
@@ -1,5 +1,4 @@
 // Copyright 2022 The Ferric AI Project Developers
-use trybuild;
 
 #[test]
 fn ui() {
@@ -0,0 +1,17 @@
+// Copyright 2022 The Ferric AI Project Developers
+use ferric::make_model;
+
+make_model! {
+    mod shadowed_index;
+    use ferric::distributions::Normal;
+
+    let i : f64 ~ Normal::new( 0.0, 1.0 );
+    let y[i in 0..3] : f64 ~ Normal::new( 0.0, 1.0 );
+
+    observe y;
+    query i;
+}
+
+fn main() {
+    let _model = shadowed_index::Model { y: vec![0.0; 3] };
+}
@@ -0,0 +1,17 @@
+// Copyright 2022 The Ferric AI Project Developers
+use ferric::make_model;
+
+make_model! {
+    mod observed_param;
+    use ferric::distributions::Binomial;
+
+    param num_trials : u64;
+    let heads : u64 ~ Binomial::new( num_trials, 0.5 );
+
+    observe num_trials;
+    query heads;
+}
+
+fn main() {
+    let _model = observed_param::Model { num_trials: 10 };
+}
@@ -0,0 +1,16 @@
+// Copyright 2022 The Ferric AI Project Developers
+use ferric::make_model;
+
+make_model! {
+    mod cyclic;
+    use ferric::distributions::Normal;
+
+    let a : f64 ~ Normal::new( b, 1.0 );
+    let b : f64 ~ Normal::new( a, 1.0 );
+
+    query a;
+}
+
+fn main() {
+    let _model = cyclic::Model {};
+}